@@ -0,0 +1,177 @@
+// src-tauri/benches/concurrent_autosave.rs
+//! List and search latency while other tasks autosave notes.
+//!
+//! Runs twice: once with storage shared as a clone (how the app runs it now),
+//! once with every storage call behind one `Mutex` (the previous
+//! `Arc<Mutex<DbStorage>>` app state) as a baseline.
+//!
+//!     cargo bench --bench concurrent_autosave
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tokio::sync::Mutex;
+
+use app_lib::db;
+use app_lib::db::schema::NoteFilter;
+use app_lib::services::{DbStorage, NoteService};
+use app_lib::settings::Settings;
+
+const NOTES: usize = 1_000;
+const WRITERS: usize = 4;
+const READERS: usize = 4;
+const READS_PER_READER: usize = 200;
+
+/// How readers and writers reach the storage.
+#[derive(Clone, Copy)]
+enum Mode {
+    Shared,
+    Mutex,
+}
+
+struct Bench {
+    dir: PathBuf,
+    storage: DbStorage,
+    settings: Arc<Mutex<Settings>>,
+    ids: Vec<String>,
+}
+
+impl Bench {
+    async fn new() -> Self {
+        let dir = std::env::temp_dir().join(format!("rusty-notes-bench-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let pool = db::init_database(&dir).await.unwrap();
+        let settings = Arc::new(Mutex::new(Settings {
+            notes_folder: dir.join("notes").to_string_lossy().to_string(),
+            ..Settings::default()
+        }));
+        let storage = DbStorage::new(pool, dir.clone());
+
+        let service = NoteService::new(storage.clone(), settings.clone());
+        let mut ids = Vec::with_capacity(NOTES);
+        for i in 0..NOTES {
+            let body = format!("<p>Meeting {} about project {} and groceries</p>", i, i % 37);
+            ids.push(service.create(format!("Note {}", i), body).await.unwrap().id);
+        }
+
+        Self { dir, storage, settings, ids }
+    }
+
+    async fn run(&self, mode: Mode) -> (Vec<Duration>, Vec<Duration>, usize) {
+        let gate = Arc::new(Mutex::new(()));
+        let stop = Arc::new(AtomicBool::new(false));
+        let saves = Arc::new(AtomicUsize::new(0));
+
+        let writers: Vec<_> = (0..WRITERS)
+            .map(|w| {
+                let service = NoteService::new(self.storage.clone(), self.settings.clone());
+                let ids = self.ids.clone();
+                let (gate, stop, saves) = (gate.clone(), stop.clone(), saves.clone());
+                tokio::spawn(async move {
+                    let mut n = w;
+                    while !stop.load(Ordering::Relaxed) {
+                        let id = ids[n % ids.len()].clone();
+                        let body = format!("<p>autosave {}</p>", n);
+                        let _guard = match mode {
+                            Mode::Shared => None,
+                            Mode::Mutex => Some(gate.lock().await),
+                        };
+                        service.update(id, format!("Note {}", n), body, None).await.unwrap();
+                        saves.fetch_add(1, Ordering::Relaxed);
+                        n += WRITERS;
+                    }
+                })
+            })
+            .collect();
+
+        let readers: Vec<_> = (0..READERS)
+            .map(|r| {
+                let storage = self.storage.clone();
+                let gate = gate.clone();
+                tokio::spawn(async move {
+                    let (mut list, mut search) = (Vec::new(), Vec::new());
+                    for i in 0..READS_PER_READER {
+                        let started = Instant::now();
+                        {
+                            let _guard = match mode {
+                                Mode::Shared => None,
+                                Mode::Mutex => Some(gate.lock().await),
+                            };
+                            storage.list_notes(&NoteFilter::default()).await.unwrap();
+                        }
+                        list.push(started.elapsed());
+
+                        let started = Instant::now();
+                        {
+                            let _guard = match mode {
+                                Mode::Shared => None,
+                                Mode::Mutex => Some(gate.lock().await),
+                            };
+                            storage.search_notes(&format!("project {}", (r + i) % 37), &NoteFilter::default()).await.unwrap();
+                        }
+                        search.push(started.elapsed());
+                    }
+                    (list, search)
+                })
+            })
+            .collect();
+
+        let (mut list, mut search) = (Vec::new(), Vec::new());
+        for reader in readers {
+            let (l, s) = reader.await.unwrap();
+            list.extend(l);
+            search.extend(s);
+        }
+        stop.store(true, Ordering::Relaxed);
+        for writer in writers {
+            writer.await.unwrap();
+        }
+
+        (list, search, saves.load(Ordering::Relaxed))
+    }
+}
+
+impl Drop for Bench {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    let index = ((sorted.len() - 1) as f64 * p).round() as usize;
+    sorted[index]
+}
+
+fn report(label: &str, mut samples: Vec<Duration>) {
+    samples.sort();
+    println!(
+        "  {:<7} p50 {:>8.2?}  p95 {:>8.2?}  p99 {:>8.2?}  max {:>8.2?}",
+        label,
+        percentile(&samples, 0.50),
+        percentile(&samples, 0.95),
+        percentile(&samples, 0.99),
+        samples[samples.len() - 1],
+    );
+}
+
+fn main() {
+    let runtime = tokio::runtime::Builder::new_multi_thread().enable_all().build().unwrap();
+    runtime.block_on(async {
+        let bench = Bench::new().await;
+        println!(
+            "{} notes, {} autosaving tasks, {} readers x {} list + search each",
+            NOTES, WRITERS, READERS, READS_PER_READER
+        );
+
+        for (name, mode) in [("shared storage", Mode::Shared), ("single mutex (baseline)", Mode::Mutex)] {
+            let started = Instant::now();
+            let (list, search, saves) = bench.run(mode).await;
+            println!("{} — {} autosaves in {:.2?}", name, saves, started.elapsed());
+            report("list", list);
+            report("search", search);
+        }
+    });
+}
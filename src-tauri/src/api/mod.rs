@@ -0,0 +1,125 @@
+// src-tauri/src/api/mod.rs
+//! Opt-in local HTTP/JSON API (`settings.api`).
+//!
+//! Binds to 127.0.0.1 only and requires `Authorization: Bearer <token>`.
+//! Handlers go through `NoteService`, so DB + files stay consistent with
+//! the UI, and changes reach the webview through the storage event bus.
+
+mod routes;
+
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Manager};
+use tokio::sync::{oneshot, Mutex};
+use uuid::Uuid;
+
+use crate::services::DbStorage;
+use crate::settings::{ApiSettings, Settings};
+use crate::vaults::StorageSlot;
+use crate::error::{AppError, AppResult};
+
+type StorageState = StorageSlot;
+type SettingsState = Arc<Mutex<Settings>>;
+
+/// Running server, kept in app state so settings changes can restart it.
+#[derive(Default)]
+pub struct ApiServer {
+    shutdown: Option<oneshot::Sender<()>>,
+    task: Option<JoinHandle<()>>,
+}
+
+pub type ApiServerState = Mutex<ApiServer>;
+
+/// Shared with every request handler. The token is read from `settings`
+/// on each request, so regenerating it needs no restart. `storage` is the
+/// vault open when the server started; opening another one restarts it.
+#[derive(Clone)]
+pub(crate) struct ApiState {
+    pub storage: DbStorage,
+    pub settings: SettingsState,
+}
+
+/// Starts the server if `settings.api.enabled`. No-op when disabled.
+pub async fn start(app: &AppHandle) -> AppResult<()> {
+    let settings_state = app.state::<SettingsState>().inner().clone();
+    let storage = app.state::<StorageState>().get();
+
+    let api = {
+        let mut settings = settings_state.lock().await;
+        if !settings.api.enabled {
+            return Ok(());
+        }
+        if settings.api.token.is_empty() {
+            settings.api.token = generate_token();
+            crate::commands::settings_commands::settings_files(app)
+                .save(&settings)
+                .map_err(AppError::internal("Failed to save settings"))?;
+        }
+        settings.api.clone()
+    };
+
+    let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, api.port));
+    let listener = tokio::net::TcpListener::bind(addr)
+        .await
+        .map_err(AppError::io(format!("Failed to bind API server to {}", addr)))?;
+
+    let state = ApiState {
+        storage,
+        settings: settings_state,
+    };
+    let router = routes::router(state, api.max_upload_mb);
+    let (tx, rx) = oneshot::channel::<()>();
+
+    let task = tauri::async_runtime::spawn(async move {
+        let server = axum::serve(listener, router).with_graceful_shutdown(async {
+            let _ = rx.await;
+        });
+        if let Err(e) = server.await {
+            log::error!("API server stopped: {}", e);
+        }
+    });
+
+    let server_state = app.state::<ApiServerState>();
+    let mut server = server_state.lock().await;
+    server.shutdown = Some(tx);
+    server.task = Some(task);
+    log::info!("✓ API server listening on http://{}", addr);
+    Ok(())
+}
+
+/// Stops the server if it is running and waits until the port is released.
+pub async fn stop(app: &AppHandle) {
+    let (shutdown, task) = {
+        let server_state = app.state::<ApiServerState>();
+        let mut server = server_state.lock().await;
+        (server.shutdown.take(), server.task.take())
+    };
+
+    if let Some(tx) = shutdown {
+        let _ = tx.send(());
+    }
+    if let Some(task) = task {
+        // Graceful shutdown finishes in-flight requests, then drops the listener
+        let _ = task.await;
+        log::info!("✓ API server stopped");
+    }
+}
+
+/// True when a settings change needs the listener rebuilt
+/// (the token alone is picked up live).
+pub fn needs_restart(old: &ApiSettings, new: &ApiSettings) -> bool {
+    old.enabled != new.enabled || old.port != new.port || old.max_upload_mb != new.max_upload_mb
+}
+
+/// Applies changed `settings.api` (enable/disable, port, upload limit).
+pub async fn restart(app: &AppHandle) -> AppResult<()> {
+    stop(app).await;
+    start(app).await
+}
+
+/// 128-bit random token, hex encoded.
+pub fn generate_token() -> String {
+    Uuid::new_v4().simple().to_string()
+}
@@ -0,0 +1,344 @@
+// src-tauri/src/api/routes.rs
+use std::time::Instant;
+
+use axum::body::{Body, Bytes};
+use axum::extract::rejection::{JsonRejection, QueryRejection};
+use axum::extract::{DefaultBodyLimit, Path, Query, Request, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::Deserialize;
+
+use super::{ApiState, SettingsState};
+use crate::db::schema::{Attachment, Note, NoteWithAttachments};
+use crate::error::AppError;
+use crate::services::NoteService;
+
+pub(crate) fn router(state: ApiState, max_upload_mb: u64) -> Router {
+    let body_limit = usize::try_from(max_upload_mb.saturating_mul(1024 * 1024)).unwrap_or(usize::MAX);
+
+    Router::new()
+        .route("/api/notes", get(list_notes).post(create_note))
+        .route("/api/notes/{id}", get(get_note).put(update_note).delete(delete_note))
+        .route("/api/notes/{id}/attachments", get(list_attachments).post(add_attachment))
+        .route("/api/attachments/{id}/content", get(attachment_content))
+        .route("/api/search", get(search_notes))
+        .layer(DefaultBodyLimit::max(body_limit))
+        .layer(middleware::from_fn_with_state(state.settings.clone(), authenticate))
+        .layer(middleware::from_fn(log_request))
+        .with_state(state)
+}
+
+/// JSON error body: `{ "error": "...", "current": ... }` (`current` only on 409).
+pub(crate) struct ApiError {
+    status: StatusCode,
+    message: String,
+    current: Option<Note>,
+}
+
+impl ApiError {
+    fn new(status: StatusCode, message: impl Into<String>) -> Self {
+        ApiError { status, message: message.into(), current: None }
+    }
+
+    fn bad_request(message: impl Into<String>) -> Self {
+        ApiError::new(StatusCode::BAD_REQUEST, message)
+    }
+
+    fn not_found(message: impl Into<String>) -> Self {
+        ApiError::new(StatusCode::NOT_FOUND, message)
+    }
+}
+
+/// Status by error code; a stale `expected_version` → 409 with the stored note.
+impl From<AppError> for ApiError {
+    fn from(error: AppError) -> Self {
+        let status = match error {
+            AppError::NotFound(_) => StatusCode::NOT_FOUND,
+            AppError::Conflict { .. } => StatusCode::CONFLICT,
+            AppError::Validation(_) | AppError::InvalidFields(_) => StatusCode::BAD_REQUEST,
+            AppError::PermissionDenied(_) => StatusCode::FORBIDDEN,
+            AppError::Locked | AppError::NoteLocked(_) => StatusCode::LOCKED,
+            AppError::Io { .. } | AppError::Database { .. } | AppError::Internal(_) => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        };
+        let message = error.to_string();
+        let current = match error {
+            AppError::Conflict { current, .. } => Some(*current),
+            _ => None,
+        };
+        ApiError { status, message, current }
+    }
+}
+
+impl From<JsonRejection> for ApiError {
+    fn from(rejection: JsonRejection) -> Self {
+        ApiError::bad_request(rejection.body_text())
+    }
+}
+
+impl From<QueryRejection> for ApiError {
+    fn from(rejection: QueryRejection) -> Self {
+        ApiError::bad_request(rejection.body_text())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let mut body = serde_json::json!({ "error": self.message });
+        if let Some(current) = self.current {
+            body["current"] = serde_json::json!(current);
+        }
+        (self.status, Json(body)).into_response()
+    }
+}
+
+type ApiResult<T> = Result<T, ApiError>;
+
+#[derive(Deserialize)]
+pub(crate) struct NoteInput {
+    title: String,
+    body: String,
+    /// On update: the version the client edited (409 if it is stale).
+    #[serde(default)]
+    expected_version: Option<i64>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct SearchQuery {
+    q: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct AttachmentQuery {
+    file_name: String,
+}
+
+impl ApiState {
+    fn notes(&self) -> NoteService {
+        NoteService::new(self.storage.clone(), self.settings.clone())
+    }
+
+    /// The active note, or 404.
+    async fn require_note(&self, id: &str) -> ApiResult<NoteWithAttachments> {
+        self.storage
+            .find_note(id)
+            .await?
+            .ok_or_else(|| ApiError::not_found(format!("Note {} not found", id)))
+    }
+}
+
+/// Logs `METHOD /path -> status (ms)` for every request.
+async fn log_request(request: Request, next: Next) -> Response {
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let started = Instant::now();
+
+    let response = next.run(request).await;
+
+    log::info!(
+        "API {} {} -> {} ({} ms)",
+        method,
+        path,
+        response.status().as_u16(),
+        started.elapsed().as_millis()
+    );
+    response
+}
+
+/// Requires the bearer token and a localhost `Host` header
+/// (the latter blocks DNS-rebinding from web pages).
+/// The token is read per request so a regenerated one applies immediately.
+async fn authenticate(State(settings): State<SettingsState>, request: Request, next: Next) -> Response {
+    let host_ok = request
+        .headers()
+        .get(header::HOST)
+        .and_then(|h| h.to_str().ok())
+        .map(|h| h.rsplit_once(':').map_or(h, |(host, _)| host))
+        .is_some_and(|host| host == "127.0.0.1" || host == "localhost");
+    if !host_ok {
+        return ApiError::new(StatusCode::FORBIDDEN, "Only localhost clients are allowed").into_response();
+    }
+
+    let token = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|h| h.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "));
+
+    let expected = settings.lock().await.api.token.clone();
+    match token {
+        Some(token) if !expected.is_empty() && constant_time_eq(token.as_bytes(), expected.as_bytes()) => {
+            next.run(request).await
+        }
+        _ => ApiError::new(StatusCode::UNAUTHORIZED, "Missing or invalid API token").into_response(),
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+async fn list_notes(State(state): State<ApiState>, Query(query): Query<SearchQuery>) -> ApiResult<Json<Vec<Note>>> {
+    let notes = match query.q {
+        Some(q) if !q.is_empty() => state.notes().search(q).await?,
+        _ => state.notes().list().await?,
+    };
+    Ok(Json(notes))
+}
+
+async fn search_notes(State(state): State<ApiState>, Query(query): Query<SearchQuery>) -> ApiResult<Json<Vec<Note>>> {
+    Ok(Json(state.notes().search(query.q.unwrap_or_default()).await?))
+}
+
+async fn create_note(
+    State(state): State<ApiState>,
+    input: Result<Json<NoteInput>, JsonRejection>,
+) -> ApiResult<(StatusCode, Json<Note>)> {
+    let Json(input) = input?;
+    let note = state.notes().create(input.title, input.body).await?;
+    Ok((StatusCode::CREATED, Json(note)))
+}
+
+async fn get_note(State(state): State<ApiState>, Path(id): Path<String>) -> ApiResult<Json<NoteWithAttachments>> {
+    Ok(Json(state.require_note(&id).await?))
+}
+
+async fn update_note(
+    State(state): State<ApiState>,
+    Path(id): Path<String>,
+    input: Result<Json<NoteInput>, JsonRejection>,
+) -> ApiResult<Json<Note>> {
+    let Json(input) = input?;
+    state.require_note(&id).await?;
+    let note = state.notes().update(id, input.title, input.body, input.expected_version).await?;
+    Ok(Json(note))
+}
+
+async fn delete_note(State(state): State<ApiState>, Path(id): Path<String>) -> ApiResult<StatusCode> {
+    state.require_note(&id).await?;
+    state.notes().delete(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn list_attachments(State(state): State<ApiState>, Path(id): Path<String>) -> ApiResult<Json<Vec<Attachment>>> {
+    Ok(Json(state.require_note(&id).await?.attachments))
+}
+
+/// Raw request body is the file; `?file_name=` names it, `Content-Type` is its MIME type.
+async fn add_attachment(
+    State(state): State<ApiState>,
+    Path(id): Path<String>,
+    query: Result<Query<AttachmentQuery>, QueryRejection>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> ApiResult<(StatusCode, Json<Attachment>)> {
+    let Query(query) = query?;
+    if query.file_name.trim().is_empty() {
+        return Err(ApiError::bad_request("file_name must not be empty"));
+    }
+    if body.is_empty() {
+        return Err(ApiError::bad_request("Request body (the file) is empty"));
+    }
+    state.require_note(&id).await?;
+
+    let mime_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|h| h.to_str().ok())
+        .map(str::to_string);
+
+    let attachment = state
+        .notes()
+        .add_attachment(id, query.file_name, mime_type, body.to_vec())
+        .await?;
+    Ok((StatusCode::CREATED, Json(attachment)))
+}
+
+async fn attachment_content(State(state): State<ApiState>, Path(id): Path<String>) -> ApiResult<Response> {
+    let attachment = state
+        .storage
+        .find_attachment(&id)
+        .await?
+        .ok_or_else(|| ApiError::not_found(format!("Attachment {} not found", id)))?;
+    let data = tokio::fs::read(&attachment.file_path)
+        .await
+        .map_err(|e| ApiError::not_found(format!("Attachment file missing: {}", e)))?;
+    let protected = state.storage.note_password_lock(&attachment.note_id).await?.is_some();
+    let data = state.storage.keyring().open(&data)?;
+    let data = state.storage.keyring().open_note_data(&attachment.note_id, protected, data)?;
+
+    let mime = attachment.mime_type.unwrap_or_else(|| "application/octet-stream".to_string());
+    Ok(([(header::CONTENT_TYPE, mime)], Body::from(data)).into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::http::Request as HttpRequest;
+    use tokio::sync::Mutex;
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::settings::{ApiSettings, Settings};
+
+    fn settings_with_token(token: &str) -> SettingsState {
+        Arc::new(Mutex::new(Settings {
+            api: ApiSettings { token: token.to_string(), ..ApiSettings::default() },
+            ..Settings::default()
+        }))
+    }
+
+    fn protected(settings: SettingsState) -> Router {
+        Router::new()
+            .route("/ping", get(|| async { "pong" }))
+            .layer(middleware::from_fn_with_state(settings, authenticate))
+    }
+
+    async fn status(router: Router, host: &str, authorization: Option<&str>) -> StatusCode {
+        let mut request = HttpRequest::builder().uri("/ping").header(header::HOST, host);
+        if let Some(value) = authorization {
+            request = request.header(header::AUTHORIZATION, value);
+        }
+        router.oneshot(request.body(Body::empty()).unwrap()).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn accepts_the_configured_token_from_localhost() {
+        let settings = settings_with_token("secret");
+        assert_eq!(status(protected(settings.clone()), "127.0.0.1:27645", Some("Bearer secret")).await, StatusCode::OK);
+        assert_eq!(status(protected(settings), "localhost", Some("Bearer secret")).await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn rejects_missing_or_wrong_tokens() {
+        let settings = settings_with_token("secret");
+        assert_eq!(status(protected(settings.clone()), "localhost", None).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(status(protected(settings.clone()), "localhost", Some("Bearer nope")).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(status(protected(settings), "localhost", Some("secret")).await, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn empty_configured_token_never_matches() {
+        assert_eq!(status(protected(settings_with_token("")), "localhost", Some("Bearer ")).await, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn rejects_foreign_host_headers() {
+        let settings = settings_with_token("secret");
+        assert_eq!(status(protected(settings), "evil.example:27645", Some("Bearer secret")).await, StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn regenerated_token_applies_without_restart() {
+        let settings = settings_with_token("old");
+        let router = protected(settings.clone());
+        settings.lock().await.api.token = "new".to_string();
+
+        assert_eq!(status(router.clone(), "localhost", Some("Bearer old")).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(status(router, "localhost", Some("Bearer new")).await, StatusCode::OK);
+    }
+}
@@ -0,0 +1,378 @@
+// src-tauri/src/bin/rusty-notes-cli.rs
+//! Headless access to the same DB, settings and notes folder as the GUI,
+//! in the vault opened last unless `--vault` names another.
+//!
+//! Safe to run while the app is open: it goes through the same services,
+//! SQLite waits on locks (busy timeout) and settings are only read.
+//!
+//! An encrypted vault is unlocked first, with the passphrase from
+//! `RUSTY_NOTES_PASSPHRASE` or, in a terminal, a prompt.
+
+use std::io::{self, IsTerminal, Read, Write};
+use std::path::PathBuf;
+use std::process::{Command, ExitCode};
+use std::sync::Arc;
+
+use clap::{Parser, Subcommand};
+use serde::Serialize;
+use tokio::sync::Mutex;
+
+use app_lib::db;
+use app_lib::services::export::{html_to_text, ExportFormat};
+use app_lib::services::import::{html, ImportFormat};
+use app_lib::error::{AppError, AppResult};
+use app_lib::services::{maintenance, DbStorage, ExportService, ImportService, NoteService, VaultEncryption};
+use app_lib::settings::Settings;
+use app_lib::vaults::VaultRegistry;
+
+/// Same identifier as `tauri.conf.json`, so we find the GUI's data.
+const APP_IDENTIFIER: &str = "com.rustynotes.app";
+
+/// Passphrase of an encrypted vault, for scripts.
+const PASSPHRASE_VAR: &str = "RUSTY_NOTES_PASSPHRASE";
+
+#[derive(Parser)]
+#[command(name = "rusty-notes-cli", version, about = "Command-line access to rusty-notes")]
+struct Cli {
+    /// Print machine-readable JSON instead of text
+    #[arg(long, global = true)]
+    json: bool,
+
+    /// Override the app data dir (where the default vault's rusty-notes.db lives)
+    #[arg(long, global = true)]
+    data_dir: Option<PathBuf>,
+
+    /// Override the app config dir (where settings.toml lives)
+    #[arg(long, global = true)]
+    config_dir: Option<PathBuf>,
+
+    /// Vault to open, by name or id (defaults to the one opened last)
+    #[arg(long, global = true)]
+    vault: Option<String>,
+
+    #[command(subcommand)]
+    command: Cmd,
+}
+
+#[derive(Subcommand)]
+enum Cmd {
+    /// List notes, newest first
+    List,
+    /// Show a note
+    Show {
+        id: String,
+        /// Print the stored HTML instead of plain text
+        #[arg(long)]
+        raw: bool,
+    },
+    /// Create a note; the body is read from stdin
+    New {
+        #[arg(short, long)]
+        title: String,
+        /// Treat stdin as HTML instead of plain text
+        #[arg(long)]
+        html: bool,
+    },
+    /// Edit a note in $EDITOR (first line: title, then a blank line, then the HTML body)
+    Edit { id: String },
+    /// Search titles and bodies
+    Search { query: String },
+    /// Delete a note (honours the delete behaviour setting)
+    Rm { id: String },
+    /// Export a note as html, text or json
+    Export {
+        id: String,
+        #[arg(short, long, value_parser = parse_export_format, default_value = "html")]
+        format: ExportFormat,
+        /// Output file (defaults to stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Import from joplin, simplenote, keep or json
+    Import {
+        #[arg(value_parser = parse_import_format)]
+        format: ImportFormat,
+        path: PathBuf,
+    },
+    /// Snapshot the DB and notes folder into a new folder under DEST
+    Backup { dest: PathBuf },
+    /// Check DB integrity and that every note/attachment file exists
+    Check,
+}
+
+fn parse_export_format(value: &str) -> Result<ExportFormat, String> {
+    serde_json::from_value(serde_json::Value::String(value.to_lowercase()))
+        .map_err(|_| "expected html, text or json".to_string())
+}
+
+fn parse_import_format(value: &str) -> Result<ImportFormat, String> {
+    serde_json::from_value(serde_json::Value::String(value.to_lowercase()))
+        .map_err(|_| "expected joplin, simplenote, keep or json".to_string())
+}
+
+struct Context {
+    storage: DbStorage,
+    settings: Arc<Mutex<Settings>>,
+    notes_folder: String,
+    json: bool,
+}
+
+impl Context {
+    fn notes(&self) -> NoteService {
+        NoteService::new(self.storage.clone(), self.settings.clone())
+    }
+
+    fn print<T: Serialize>(&self, value: &T, text: impl FnOnce() -> String) -> AppResult<()> {
+        if self.json {
+            let out = serde_json::to_string_pretty(value).map_err(AppError::internal("Failed to serialize output"))?;
+            println!("{}", out);
+        } else {
+            println!("{}", text());
+        }
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(cli).await {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(cli: Cli) -> AppResult<ExitCode> {
+    let data_dir = match cli.data_dir {
+        Some(dir) => dir,
+        None => dirs::data_dir()
+            .ok_or_else(|| AppError::NotFound("Data directory on this platform".to_string()))?
+            .join(APP_IDENTIFIER),
+    };
+    let config_dir = match cli.config_dir {
+        Some(dir) => dir,
+        None => dirs::config_dir()
+            .ok_or_else(|| AppError::NotFound("Config directory on this platform".to_string()))?
+            .join(APP_IDENTIFIER),
+    };
+
+    let registry = VaultRegistry::load(&config_dir, &data_dir)?;
+    let vault = match cli.vault.as_deref() {
+        Some(name) => registry.find(name)?,
+        None => registry.last_opened(),
+    };
+    let data_dir = vault.data_dir.clone();
+
+    std::fs::create_dir_all(&data_dir).map_err(AppError::io("Failed to create data dir"))?;
+    let mut settings = vault
+        .settings_files(&config_dir)
+        .read()
+        .map_err(AppError::internal("Failed to load settings"))?;
+    // Same resolution as the GUI: relative folders live under the vault's data dir
+    settings.resolve_notes_folder(&data_dir);
+    let pool = db::init_database(&data_dir).await?;
+    let storage = DbStorage::new(pool, data_dir.clone());
+    storage.set_notes_folder(&settings.notes_folder);
+
+    let ctx = Context {
+        notes_folder: settings.notes_folder.clone(),
+        storage,
+        settings: Arc::new(Mutex::new(settings)),
+        json: cli.json,
+    };
+    if ctx.storage.keyring().is_encrypted() {
+        let passphrase = read_passphrase(&vault.name)?;
+        VaultEncryption::new(ctx.storage.clone(), ctx.settings.clone(), &data_dir).unlock(&passphrase)?;
+    }
+
+    match cli.command {
+        Cmd::List => {
+            let notes = ctx.notes().list().await?;
+            ctx.print(&notes, || {
+                notes
+                    .iter()
+                    .map(|n| format!("{}  {}  {}", n.id, format_time(n.updated_at), n.title))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })?;
+        }
+        Cmd::Show { id, raw } => {
+            let note = ctx.notes().get(id).await?;
+            ctx.print(&note, || {
+                let body = if raw { note.note.body.clone() } else { html_to_text(&note.note.body) };
+                format!("# {}\n\n{}", note.note.title, body)
+            })?;
+        }
+        Cmd::New { title, html: is_html } => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input).map_err(AppError::io("Failed to read stdin"))?;
+            let body = if is_html { input } else { html::text_to_html(input.trim_end()) };
+
+            let note = ctx.notes().create(title, body).await?;
+            ctx.print(&note, || note.id.clone())?;
+        }
+        Cmd::Edit { id } => {
+            let note = ctx.notes().get(id.clone()).await?.note;
+            let (title, body) = edit_in_editor(&note.title, &note.body)?;
+
+            if title == note.title && body == note.body {
+                eprintln!("No changes.");
+                return Ok(ExitCode::SUCCESS);
+            }
+            // Someone may have saved the note while the editor was open
+            let updated = match ctx.notes().update(id, title.clone(), body.clone(), Some(note.version)).await {
+                Err(e @ AppError::Conflict { .. }) => {
+                    let kept = std::env::temp_dir().join(format!("rusty-notes-{}.html", uuid::Uuid::new_v4()));
+                    std::fs::write(&kept, format!("{}\n\n{}\n", title, body))
+                        .map_err(AppError::io("Failed to save your edit"))?;
+                    eprintln!("Your edit was saved to {}", kept.display());
+                    return Err(e);
+                }
+                result => result?,
+            };
+            ctx.print(&updated, || updated.id.clone())?;
+        }
+        Cmd::Search { query } => {
+            let notes = ctx.notes().search(query).await?;
+            ctx.print(&notes, || {
+                notes
+                    .iter()
+                    .map(|n| format!("{}  {}", n.id, n.title))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })?;
+        }
+        Cmd::Rm { id } => {
+            ctx.notes().delete(id.clone()).await?;
+            ctx.print(&serde_json::json!({ "deleted": id }), || format!("Deleted {}", id))?;
+        }
+        Cmd::Export { id, format, output } => {
+            let exported = ExportService::new(ctx.storage.clone()).export_note(&id, format).await?;
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, &exported.contents)
+                        .map_err(AppError::io(format!("Failed to write {}", path.display())))?;
+                    eprintln!("Wrote {}", path.display());
+                }
+                None => io::stdout()
+                    .write_all(exported.contents.as_bytes())
+                    .map_err(AppError::io("Failed to write to stdout"))?,
+            }
+        }
+        Cmd::Import { format, path } => {
+            let service = ImportService::new(ctx.storage.clone(), ctx.settings.clone());
+            let report = service
+                .run(format.importer(), path, |p| {
+                    if !ctx.json {
+                        eprint!("\r{}/{}", p.processed, p.total);
+                    }
+                })
+                .await?;
+            if !ctx.json {
+                eprintln!();
+            }
+
+            ctx.print(&report, || {
+                let mut out = format!(
+                    "Imported {} of {} ({} duplicates skipped)",
+                    report.imported, report.total, report.skipped_duplicates
+                );
+                for issue in &report.errors {
+                    out.push_str(&format!("\n  ! {}: {}", issue.item, issue.message));
+                }
+                out
+            })?;
+        }
+        Cmd::Backup { dest } => {
+            // No note file or row changes halfway through the copy
+            let _writes = ctx.storage.lock_writes().await;
+            let target = maintenance::backup(&ctx.storage, &ctx.notes_folder, &dest).await?;
+            ctx.print(&serde_json::json!({ "backup": target }), || format!("Backup written to {}", target.display()))?;
+        }
+        Cmd::Check => {
+            let report = maintenance::check(&ctx.storage, &ctx.notes_folder).await?;
+            ctx.print(&report, || {
+                let mut out = format!("database: {}", report.database.join("; "));
+                for id in &report.missing_note_files {
+                    out.push_str(&format!("\nmissing note file: {}", id));
+                }
+                for path in &report.missing_attachments {
+                    out.push_str(&format!("\nmissing attachment: {}", path));
+                }
+                for path in &report.orphan_files {
+                    out.push_str(&format!("\norphan file: {}", path));
+                }
+                out
+            })?;
+            if !report.is_ok() {
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn format_time(ts: i64) -> String {
+    chrono::DateTime::from_timestamp(ts, 0)
+        .map(|dt| dt.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default()
+}
+
+/// `RUSTY_NOTES_PASSPHRASE`, else asked for on the terminal without echo.
+fn read_passphrase(vault: &str) -> AppResult<String> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_VAR) {
+        return Ok(passphrase);
+    }
+    if !io::stdin().is_terminal() {
+        return Err(AppError::Validation(format!(
+            "Vault \"{}\" is encrypted; set {} to unlock it",
+            vault, PASSPHRASE_VAR
+        )));
+    }
+
+    eprint!("Passphrase for vault \"{}\": ", vault);
+    io::stderr().flush().map_err(AppError::io("Failed to write prompt"))?;
+    // Best effort: without stty the passphrase is echoed
+    let hidden = Command::new("stty").arg("-echo").status().is_ok_and(|status| status.success());
+    let mut line = String::new();
+    let read = io::stdin().read_line(&mut line);
+    if hidden {
+        let _ = Command::new("stty").arg("echo").status();
+        eprintln!();
+    }
+    read.map_err(AppError::io("Failed to read passphrase"))?;
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+/// Opens `$VISUAL` / `$EDITOR` (fallback `vi`) on a temp file and parses it back.
+fn edit_in_editor(title: &str, body: &str) -> AppResult<(String, String)> {
+    let path = std::env::temp_dir().join(format!("rusty-notes-{}.html", uuid::Uuid::new_v4()));
+    std::fs::write(&path, format!("{}\n\n{}\n", title, body))
+        .map_err(AppError::io("Failed to write temp file"))?;
+
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().ok_or_else(|| AppError::Validation("EDITOR is empty".to_string()))?;
+
+    let status = Command::new(program)
+        .args(parts)
+        .arg(&path)
+        .status()
+        .map_err(AppError::io(format!("Failed to start {}", editor)));
+    let contents = std::fs::read_to_string(&path);
+    let _ = std::fs::remove_file(&path);
+
+    if !status?.success() {
+        return Err(AppError::Internal(format!("{} exited with an error; note unchanged", editor)));
+    }
+    let contents = contents.map_err(AppError::io("Failed to read temp file"))?;
+
+    let (new_title, new_body) = contents.split_once('\n').unwrap_or((contents.as_str(), ""));
+    Ok((new_title.trim().to_string(), new_body.trim().to_string()))
+}
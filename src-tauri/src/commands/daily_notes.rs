@@ -0,0 +1,26 @@
+// src-tauri/src/commands/daily_notes.rs
+use std::sync::Arc;
+
+use tauri::State;
+use tokio::sync::Mutex;
+
+use crate::error::AppResult;
+use crate::services::daily_notes::{DailyNote, DailyNotes};
+use crate::settings::Settings;
+use crate::vaults::StorageSlot;
+
+type SettingsState = Arc<Mutex<Settings>>;
+
+/// The note for `date` (`YYYY-MM-DD`, default today), created from the
+/// daily note settings if missing. `previous` / `next` are the closest
+/// dates with a daily note, to open in turn.
+#[tauri::command]
+pub async fn open_daily_note(
+    date: Option<String>,
+    storage: State<'_, StorageSlot>,
+    settings_state: State<'_, SettingsState>,
+) -> AppResult<DailyNote> {
+    DailyNotes::new(storage.get(), settings_state.inner().clone())
+        .open(date.as_deref())
+        .await
+}
@@ -0,0 +1,93 @@
+// src-tauri/src/commands/encryption.rs
+//! Encryption at rest for the open vault: turn on, unlock, lock, change
+//! the passphrase, and lock automatically after
+//! `settings.encryption.auto_lock_minutes` without note access.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager};
+use tokio::sync::Mutex;
+
+use crate::error::{AppError, AppResult};
+use crate::services::encryption::{EncryptionReport, EncryptionStatus};
+use crate::services::VaultEncryption;
+use crate::settings::Settings;
+use crate::vaults::{OpenVaultState, StorageSlot};
+
+type SettingsState = Arc<Mutex<Settings>>;
+
+/// How often the idle time is checked.
+const AUTO_LOCK_TICK: Duration = Duration::from_secs(30);
+
+fn encryption(app: &AppHandle) -> VaultEncryption {
+    let data_dir = {
+        let open = app.state::<OpenVaultState>();
+        let open = open.read().expect("open vault state poisoned");
+        open.vault.data_dir.clone()
+    };
+    VaultEncryption::new(
+        app.state::<StorageSlot>().get(),
+        app.state::<SettingsState>().inner().clone(),
+        &data_dir,
+    )
+}
+
+#[tauri::command]
+pub async fn encryption_status(app: AppHandle) -> AppResult<EncryptionStatus> {
+    Ok(encryption(&app).status())
+}
+
+/// Encrypts the open vault; calling it again resumes an interrupted run.
+#[tauri::command]
+pub async fn enable_encryption(app: AppHandle, passphrase: String) -> AppResult<EncryptionReport> {
+    encryption(&app).enable(&passphrase).await
+}
+
+#[tauri::command]
+pub async fn unlock_vault(app: AppHandle, passphrase: String) -> AppResult<()> {
+    let encryption = encryption(&app);
+    // Deriving the key takes a while; keep it off the async workers
+    tauri::async_runtime::spawn_blocking(move || encryption.unlock(&passphrase))
+        .await
+        .map_err(AppError::internal("Unlock task failed"))??;
+
+    // Links of notes stored before they were indexed, skipped while locked
+    match app.state::<StorageSlot>().get().index_unindexed_links().await {
+        Ok(0) => {}
+        Ok(indexed) => log::info!("✓ Indexed links of {} notes", indexed),
+        Err(e) => log::error!("❌ Failed to index note links: {}", e),
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn lock_vault(app: AppHandle) -> AppResult<()> {
+    encryption(&app).lock();
+    Ok(())
+}
+
+/// Only the key file changes; notes and attachments are not rewritten.
+#[tauri::command]
+pub async fn change_passphrase(app: AppHandle, old_passphrase: String, new_passphrase: String) -> AppResult<()> {
+    let encryption = encryption(&app);
+    tauri::async_runtime::spawn_blocking(move || encryption.change_passphrase(&old_passphrase, &new_passphrase))
+        .await
+        .map_err(AppError::internal("Passphrase change task failed"))?
+}
+
+/// Locks the open vault once it has been idle for the configured time.
+/// Follows vault switches, as it looks the vault up on every tick.
+pub async fn auto_lock(app: AppHandle) {
+    loop {
+        tokio::time::sleep(AUTO_LOCK_TICK).await;
+        let minutes = app.state::<SettingsState>().lock().await.encryption.auto_lock_minutes;
+        if minutes == 0 {
+            continue;
+        }
+        let keyring = app.state::<StorageSlot>().get().keyring().clone();
+        if !keyring.is_locked() && keyring.is_encrypted() && keyring.idle() >= Duration::from_secs(minutes * 60) {
+            encryption(&app).lock();
+        }
+    }
+}
@@ -0,0 +1,21 @@
+// src-tauri/src/commands/export.rs
+use tauri::State;
+
+use crate::services::ExportService;
+use crate::vaults::StorageSlot;
+use crate::services::export::{ExportFormat, ExportedNote};
+use crate::error::AppResult;
+
+type StorageState = StorageSlot;
+
+/// Exports a single note as standalone HTML, plain text or lossless JSON.
+/// The frontend picks the destination (save dialog) and writes `contents`.
+#[tauri::command]
+pub async fn export_note(
+    id: String,
+    format: ExportFormat,
+    storage: State<'_, StorageState>,
+) -> AppResult<ExportedNote> {
+    let service = ExportService::new(storage.get());
+    service.export_note(&id, format).await
+}
@@ -0,0 +1,25 @@
+// src-tauri/src/commands/graph.rs
+use tauri::State;
+
+use crate::services::graph::{Graph, GraphFilter, GraphFormat, NoteGraph};
+use crate::services::export::ExportedNote;
+use crate::vaults::StorageSlot;
+use crate::error::AppResult;
+
+type StorageState = StorageSlot;
+
+/// Notes and the links between them; no `filter` means the whole vault.
+#[tauri::command]
+pub async fn get_graph(filter: Option<GraphFilter>, storage: State<'_, StorageState>) -> AppResult<Graph> {
+    NoteGraph::new(storage.get()).build(&filter.unwrap_or_default()).await
+}
+
+/// The graph as DOT, GraphML or JSON; the frontend saves `contents`.
+#[tauri::command]
+pub async fn export_graph(
+    format: GraphFormat,
+    filter: Option<GraphFilter>,
+    storage: State<'_, StorageState>,
+) -> AppResult<ExportedNote> {
+    NoteGraph::new(storage.get()).export(&filter.unwrap_or_default(), format).await
+}
@@ -0,0 +1,34 @@
+// src-tauri/src/commands/import.rs
+use tauri::{AppHandle, Emitter, State};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use crate::services::import::{ImportFormat, ImportReport, ImportService};
+use crate::settings::Settings;
+use crate::vaults::StorageSlot;
+use crate::error::AppResult;
+
+type StorageState = StorageSlot;
+type SettingsState = Arc<Mutex<Settings>>;
+
+/// Imports a Joplin (JEX/RAW), Simplenote or Google Keep export.
+/// Emits `import-progress` after every item.
+#[tauri::command]
+pub async fn import_notes(
+    format: ImportFormat,
+    path: String,
+    app: AppHandle,
+    storage: State<'_, StorageState>,
+    settings_state: State<'_, SettingsState>,
+) -> AppResult<ImportReport> {
+    let service = ImportService::new(storage.get(), settings_state.inner().clone());
+
+    service
+        .run(format.importer(), PathBuf::from(path), |progress| {
+            if let Err(e) = app.emit("import-progress", progress) {
+                eprintln!("Failed to emit import-progress event: {e}");
+            }
+        })
+        .await
+}
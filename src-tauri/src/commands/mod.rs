@@ -1,4 +1,12 @@
 // src/commands/mod.rs
 pub mod notes;
+pub mod import;
+pub mod export;
+pub mod graph;
+pub mod templates;
+pub mod daily_notes;
 pub mod settings_commands;
+pub mod vaults;
 
+pub mod quick_capture;
+pub mod encryption;
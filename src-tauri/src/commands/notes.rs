@@ -3,11 +3,14 @@ use tauri::State;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
-use crate::services::{DbStorage, DeleteService, NoteService};
-use crate::db::schema::{Note, NoteWithAttachments};
+use crate::services::{NoteLocks, NoteService};
+use crate::services::links::{BrokenLink, OutgoingLink};
+use crate::vaults::StorageSlot;
+use crate::db::schema::{Note, NoteFilter, NoteWithAttachments};
 use crate::settings::Settings;
+use crate::error::AppResult;
 
-type StorageState = Arc<Mutex<DbStorage>>;
+type StorageState = StorageSlot;
 type SettingsState = Arc<Mutex<Settings>>;
 
 #[tauri::command]
@@ -16,8 +19,8 @@ pub async fn create_note(
     body: String,
     storage: State<'_, StorageState>,
     settings_state: State<'_, SettingsState>,
-) -> Result<Note, String> {
-    let service = NoteService::new(storage.inner().clone(), settings_state.inner().clone());
+) -> AppResult<Note> {
+    let service = NoteService::new(storage.get(), settings_state.inner().clone());
     service.create(title, body).await
 }
 
@@ -26,11 +29,12 @@ pub async fn update_note(
     id: String,
     title: String,
     body: String,
+    expected_version: Option<i64>,
     storage: State<'_, StorageState>,
     settings_state: State<'_, SettingsState>,
-) -> Result<Note, String> {
-    let service = NoteService::new(storage.inner().clone(), settings_state.inner().clone());
-    service.update(id, title, body).await
+) -> AppResult<Note> {
+    let service = NoteService::new(storage.get(), settings_state.inner().clone());
+    service.update(id, title, body, expected_version).await
 }
 
 #[tauri::command]
@@ -38,28 +42,31 @@ pub async fn get_note(
     id: String,
     storage: State<'_, StorageState>,
     settings_state: State<'_, SettingsState>,
-) -> Result<NoteWithAttachments, String> {
-    let service = NoteService::new(storage.inner().clone(), settings_state.inner().clone());
+) -> AppResult<NoteWithAttachments> {
+    let service = NoteService::new(storage.get(), settings_state.inner().clone());
     service.get(id).await
 }
 
+/// Without `filter`: active, non-archived notes. Pinned ones come first.
 #[tauri::command]
 pub async fn list_notes(
+    filter: Option<NoteFilter>,
     storage: State<'_, StorageState>,
     settings_state: State<'_, SettingsState>,
-) -> Result<Vec<Note>, String> {
-    let service = NoteService::new(storage.inner().clone(), settings_state.inner().clone());
-    service.list().await
+) -> AppResult<Vec<Note>> {
+    let service = NoteService::new(storage.get(), settings_state.inner().clone());
+    service.list_filtered(filter.unwrap_or_default()).await
 }
 
 #[tauri::command]
 pub async fn search_notes(
     query: String,
+    filter: Option<NoteFilter>,
     storage: State<'_, StorageState>,
     settings_state: State<'_, SettingsState>,
-) -> Result<Vec<Note>, String> {
-    let service = NoteService::new(storage.inner().clone(), settings_state.inner().clone());
-    service.search(query).await
+) -> AppResult<Vec<Note>> {
+    let service = NoteService::new(storage.get(), settings_state.inner().clone());
+    service.search_filtered(query, filter.unwrap_or_default()).await
 }
 
 #[tauri::command]
@@ -67,12 +74,63 @@ pub async fn delete_note(
     id: String,
     storage: State<'_, StorageState>,
     settings_state: State<'_, SettingsState>,
-) -> Result<(), String> {
-    let settings = settings_state.lock().await;
-    let notes_folder = settings.notes_folder.clone();
-    
-    let note_with_attachments = storage.lock().await.get_note(&id).await?;
-    
-    DeleteService::delete_note(&notes_folder, &note_with_attachments, &settings).await?;
-    storage.lock().await.delete_note(&id).await
+) -> AppResult<()> {
+    let service = NoteService::new(storage.get(), settings_state.inner().clone());
+    service.delete(id).await
+}
+
+/// Password protects the note; it stays locked until `unlock_note`.
+#[tauri::command]
+pub async fn protect_note(id: String, passphrase: String, storage: State<'_, StorageState>) -> AppResult<()> {
+    NoteLocks::new(storage.get()).protect(&id, &passphrase).await
+}
+
+#[tauri::command]
+pub async fn unprotect_note(id: String, passphrase: String, storage: State<'_, StorageState>) -> AppResult<()> {
+    NoteLocks::new(storage.get()).unprotect(&id, &passphrase).await
+}
+
+/// Makes a protected note readable until the app quits (or `lock_note`).
+#[tauri::command]
+pub async fn unlock_note(id: String, passphrase: String, storage: State<'_, StorageState>) -> AppResult<()> {
+    NoteLocks::new(storage.get()).unlock(&id, &passphrase).await
+}
+
+#[tauri::command]
+pub async fn lock_note(id: String, storage: State<'_, StorageState>) -> AppResult<()> {
+    NoteLocks::new(storage.get()).lock(&id);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_note_pinned(id: String, pinned: bool, storage: State<'_, StorageState>) -> AppResult<()> {
+    storage.get().set_note_pinned(&id, pinned).await
+}
+
+#[tauri::command]
+pub async fn set_note_favorite(id: String, favorite: bool, storage: State<'_, StorageState>) -> AppResult<()> {
+    storage.get().set_note_favorite(&id, favorite).await
+}
+
+/// Archived notes leave the default list and search, but not for the trash.
+#[tauri::command]
+pub async fn set_note_archived(id: String, archived: bool, storage: State<'_, StorageState>) -> AppResult<()> {
+    storage.get().set_note_archived(&id, archived).await
+}
+
+/// Active notes linking to note `id`, newest first.
+#[tauri::command]
+pub async fn get_backlinks(id: String, storage: State<'_, StorageState>) -> AppResult<Vec<Note>> {
+    storage.get().backlinks(&id).await
+}
+
+#[tauri::command]
+pub async fn get_outgoing_links(id: String, storage: State<'_, StorageState>) -> AppResult<Vec<OutgoingLink>> {
+    storage.get().outgoing_links(&id).await
+}
+
+/// Links, in any note, to notes that are missing or in the trash.
+#[tauri::command]
+pub async fn get_broken_links(storage: State<'_, StorageState>) -> AppResult<Vec<BrokenLink>> {
+    storage.get().broken_links().await
 }
@@ -0,0 +1,54 @@
+// src-tauri/src/commands/quick_capture.rs
+//! Commands for the quick capture window.
+
+use std::sync::Arc;
+
+use tauri::{AppHandle, Emitter, State};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use tokio::sync::Mutex;
+
+use crate::db::schema::Note;
+use crate::error::AppResult;
+use crate::services::QuickCapture;
+use crate::settings::Settings;
+use crate::shortcuts::window;
+use crate::vaults::StorageSlot;
+
+type SettingsState = Arc<Mutex<Settings>>;
+
+/// Saves the note into the inbox notebook and hides the window. The main
+/// window gets a `quick-capture-saved` event with the note.
+#[tauri::command]
+pub async fn save_quick_capture(
+    app: AppHandle,
+    title: String,
+    text: String,
+    storage: State<'_, StorageSlot>,
+    settings_state: State<'_, SettingsState>,
+) -> AppResult<Note> {
+    let note = QuickCapture::new(storage.get(), settings_state.inner().clone())
+        .save(&title, &text)
+        .await?;
+
+    window::hide_quick_capture(&app);
+    if let Err(e) = app.emit_to(window::MAIN_WINDOW, "quick-capture-saved", &note) {
+        eprintln!("Failed to emit quick-capture-saved event: {e}");
+    }
+    Ok(note)
+}
+
+#[tauri::command]
+pub async fn dismiss_quick_capture(app: AppHandle) -> AppResult<()> {
+    window::hide_quick_capture(&app);
+    Ok(())
+}
+
+/// Clipboard text to start the body with, if that is turned on.
+#[tauri::command]
+pub async fn quick_capture_prefill(app: AppHandle, settings_state: State<'_, SettingsState>) -> AppResult<Option<String>> {
+    if !settings_state.lock().await.quick_capture.prefill_from_clipboard {
+        return Ok(None);
+    }
+    // Empty or non-text clipboards just leave the body empty
+    Ok(app.clipboard().read_text().ok().filter(|text| !text.trim().is_empty()))
+}
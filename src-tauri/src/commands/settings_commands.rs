@@ -1,35 +1,93 @@
 // src-tauri/src/commands/settings_commands.rs
 
-use crate::settings::{Settings, save};
+use crate::api;
+use crate::services::folder_sync::{self, FolderWatcher};
+use crate::services::{AppEvent, EventBus, FolderMove, FolderMoveReport, FolderSync};
+use crate::settings::validation::validate_update;
+use crate::settings::{Settings, SettingsFiles};
+use crate::vaults::{OpenVaultState, StorageSlot};
+use crate::shortcuts;
+use crate::tray;
+use crate::error::{AppError, AppResult};
 use tauri::{State, AppHandle, Manager};  // ✅ Add Manager here
+use serde::Serialize;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::Mutex;
 
 type SettingsState = Arc<Mutex<Settings>>;
 
-fn settings_file_path(app_handle: &AppHandle) -> PathBuf {
-    let dir = app_handle.path().app_config_dir()
-        .expect("failed to get app config dir");
-    Settings::config_path(dir)
+/// Watcher on the current notes folder (`None` if it couldn't start).
+pub type FolderWatcherState = Mutex<Option<FolderWatcher>>;
+
+/// Where the open vault's settings are saved.
+pub(crate) fn settings_files(app_handle: &AppHandle) -> SettingsFiles {
+    let open = app_handle.state::<OpenVaultState>();
+    let open = open.read().expect("open vault state poisoned");
+    open.settings_files.clone()
+}
+
+/// Data dir of the open vault (its DB, default notes folder, journals).
+pub(crate) fn open_vault_dir(app_handle: &AppHandle) -> PathBuf {
+    let open = app_handle.state::<OpenVaultState>();
+    let open = open.read().expect("open vault state poisoned");
+    open.vault.data_dir.clone()
+}
+
+/// (Re)starts watching `settings.notes_folder` and picks up files that
+/// changed while nobody was watching.
+pub(crate) async fn restart_folder_watcher(app: &AppHandle) -> AppResult<()> {
+    let settings = app.state::<SettingsState>().inner().clone();
+    let storage = app.state::<StorageSlot>().get();
+    let bus = app.state::<EventBus>().inner().clone();
+    let folder = PathBuf::from(&settings.lock().await.notes_folder);
+
+    let watcher_state = app.state::<FolderWatcherState>();
+    let mut watcher = watcher_state.lock().await;
+    *watcher = None;
+
+    let sync = FolderSync::new(storage.clone(), settings.clone());
+    *watcher = Some(folder_sync::watch(sync, &folder, bus, Duration::from_millis(500))?);
+    drop(watcher);
+
+    let changed = FolderSync::new(storage, settings).scan().await?;
+    log::info!("✓ Watching {} ({} notes synced from disk)", folder.display(), changed.len());
+    Ok(())
+}
+
+/// Settings as the webview gets them.
+#[derive(Debug, Serialize)]
+pub struct SettingsView {
+    #[serde(flatten)]
+    pub settings: Settings,
+    /// Set when the settings file couldn't be read and was reset to
+    /// defaults on opening the vault; the old file was kept here.
+    pub recovered_from: Option<String>,
 }
 
 #[tauri::command]
-pub async fn get_settings(state: State<'_, SettingsState>) -> Result<Settings, String> {
+pub async fn get_settings(state: State<'_, SettingsState>, app: AppHandle) -> AppResult<SettingsView> {
+    let recovered_from = {
+        let open = app.state::<OpenVaultState>();
+        let open = open.read().expect("open vault state poisoned");
+        open.settings_recovered_from.as_ref().map(|path| path.display().to_string())
+    };
     let settings = state.lock().await;
-    Ok(settings.clone())
+    Ok(SettingsView { settings: settings.clone(), recovered_from })
 }
 
 #[tauri::command]
 pub async fn complete_onboarding(
     state: State<'_, SettingsState>,
+    events: State<'_, EventBus>,
     app: AppHandle
-) -> Result<(), String> {
+) -> AppResult<()> {
     let mut settings = state.lock().await;
     if !settings.onboarding_completed {
         settings.onboarding_completed = true;
-        let path = settings_file_path(&app);
-        save(&path, &settings).map_err(|e| e.to_string())?;
+        settings_files(&app).save(&settings).map_err(AppError::internal("Failed to save settings"))?;
+        events.publish(AppEvent::SettingsChanged);
     }
     Ok(())
 }
@@ -37,15 +95,118 @@ pub async fn complete_onboarding(
 #[tauri::command]
 pub async fn update_settings(
     state: State<'_, SettingsState>,
+    events: State<'_, EventBus>,
     app: AppHandle,
-    new_settings: Settings,
-) -> Result<(), String> {
+    mut new_settings: Settings,
+) -> AppResult<()> {
+    let vault_dir = open_vault_dir(&app);
+    new_settings.resolve_notes_folder(&vault_dir);
+
     let mut settings = state.lock().await;
+    new_settings.keep_backend_fields(&settings);
+    validate_update(&new_settings, &settings)?;
+
+    let api_changed = api::needs_restart(&settings.api, &new_settings.api);
+    let folder_changed = settings.notes_folder != new_settings.notes_folder;
+    settings_files(&app).save(&new_settings).map_err(AppError::internal("Failed to save settings"))?;
     *settings = new_settings;
-    let path = settings_file_path(&app);
-    save(&path, &settings).map_err(|e| e.to_string())?;
-    
-    println!("✓ Settings saved: {}", settings.notes_folder);
-    
-    Ok(())
+    if folder_changed {
+        // Stored paths are relative, so notes now resolve in the new folder
+        // (e.g. a synced copy of the vault); `move_notes_folder` moves files
+        app.state::<StorageSlot>().get().set_notes_folder(&settings.notes_folder);
+    }
+
+    log::info!("✓ Settings saved: {}", settings.notes_folder);
+    drop(settings);
+    events.publish(AppEvent::SettingsChanged);
+
+    apply_changes(&app, api_changed, folder_changed).await
+}
+
+/// Side effects of saved settings. Every step runs even if an earlier one
+/// fails; the settings stay saved and the failures are returned together.
+pub(crate) async fn apply_changes(app: &AppHandle, api_changed: bool, folder_changed: bool) -> AppResult<()> {
+    let mut failures = Vec::new();
+
+    if folder_changed {
+        if let Err(e) = restart_folder_watcher(app).await {
+            failures.push(e);
+        }
+    }
+    if api_changed {
+        if let Err(e) = api::restart(app).await {
+            failures.push(e);
+        }
+    }
+    // So shortcut and tray changes apply without a restart
+    let (shortcut_settings, tray_settings) = {
+        let settings = app.state::<SettingsState>();
+        let settings = settings.lock().await;
+        (settings.shortcuts.clone(), settings.tray.clone())
+    };
+    if let Err(e) = shortcuts::reregister_all(app, &shortcut_settings) {
+        failures.push(AppError::Internal(format!("Failed to register shortcuts: {}", e)));
+    }
+    tray::apply(app, &tray_settings).await;
+
+    for failure in &failures {
+        log::error!("❌ Settings change not fully applied: {}", failure);
+    }
+    match failures.len() {
+        0 => Ok(()),
+        1 => Err(failures.remove(0)),
+        _ => Err(AppError::Internal(format!(
+            "Settings saved, but not fully applied: {}",
+            failures.iter().map(ToString::to_string).collect::<Vec<_>>().join("; ")
+        ))),
+    }
+}
+
+/// Moves the notes folder with all notes, attachments and trash to
+/// `new_path` and switches to it. Running it again after a failure resumes
+/// the same move.
+#[tauri::command]
+pub async fn move_notes_folder(
+    state: State<'_, SettingsState>,
+    storage: State<'_, StorageSlot>,
+    events: State<'_, EventBus>,
+    app: AppHandle,
+    new_path: String,
+) -> AppResult<FolderMoveReport> {
+    let vault_dir = open_vault_dir(&app);
+    let mut target = Settings { notes_folder: new_path, ..Settings::default() };
+    target.resolve_notes_folder(&vault_dir);
+    if target.notes_folder.trim().is_empty() {
+        return Err(AppError::Validation("Choose a folder for your notes".to_string()));
+    }
+
+    // The watcher would see every copied file as an outside edit
+    *app.state::<FolderWatcherState>().lock().await = None;
+
+    let mover = FolderMove::new(storage.get(), state.inner().clone(), settings_files(&app), &vault_dir);
+    let result = mover.run(PathBuf::from(&target.notes_folder)).await;
+    if result.is_ok() {
+        events.publish(AppEvent::SettingsChanged);
+    }
+
+    if let Err(e) = restart_folder_watcher(&app).await {
+        log::error!("❌ Notes folder watcher disabled: {}", e);
+    }
+    result
+}
+
+/// Replaces the local API token (invalidates existing clients) and returns it.
+/// The running server checks the token per request, so no restart is needed.
+#[tauri::command]
+pub async fn regenerate_api_token(
+    state: State<'_, SettingsState>,
+    events: State<'_, EventBus>,
+    app: AppHandle,
+) -> AppResult<String> {
+    let mut settings = state.lock().await;
+    settings.api.token = api::generate_token();
+    let token = settings.api.token.clone();
+    settings_files(&app).save(&settings).map_err(AppError::internal("Failed to save settings"))?;
+    events.publish(AppEvent::SettingsChanged);
+    Ok(token)
 }
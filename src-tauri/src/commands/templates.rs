@@ -0,0 +1,37 @@
+// src-tauri/src/commands/templates.rs
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use tauri::State;
+use tokio::sync::Mutex;
+
+use crate::db::schema::Note;
+use crate::error::AppResult;
+use crate::services::templates::{Template, Templates};
+use crate::settings::Settings;
+use crate::vaults::StorageSlot;
+
+type SettingsState = Arc<Mutex<Settings>>;
+
+/// Templates in the `templates/` folder of the notes folder.
+#[tauri::command]
+pub async fn list_templates(
+    storage: State<'_, StorageSlot>,
+    settings_state: State<'_, SettingsState>,
+) -> AppResult<Vec<Template>> {
+    Templates::new(storage.get(), settings_state.inner().clone()).list().await
+}
+
+/// New note from a template; `vars` fills custom placeholders and may
+/// override `date`, `time` and `title`.
+#[tauri::command]
+pub async fn create_note_from_template(
+    template_id: String,
+    vars: Option<HashMap<String, String>>,
+    storage: State<'_, StorageSlot>,
+    settings_state: State<'_, SettingsState>,
+) -> AppResult<Note> {
+    Templates::new(storage.get(), settings_state.inner().clone())
+        .create_note(&template_id, vars.unwrap_or_default())
+        .await
+}
@@ -0,0 +1,145 @@
+// src-tauri/src/commands/vaults.rs
+//! List, create, open, rename and close vaults. Opening one swaps the
+//! storage and settings state in place and restarts everything bound to
+//! the old vault (watchers, API, shortcuts); no app restart needed.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Manager};
+use tokio::sync::Mutex;
+
+use crate::api;
+use crate::commands::settings_commands::{apply_changes, FolderWatcherState};
+use crate::db;
+use crate::error::{AppError, AppResult};
+use crate::services::events;
+use crate::services::{AppEvent, EventBus};
+use crate::settings::Settings;
+use crate::vaults::{self, OpenVaultState, StorageSlot, Vault, VaultRegistry};
+
+type SettingsState = Arc<Mutex<Settings>>;
+
+/// Polls the open vault's DB for writes by other processes (the CLI).
+pub type DbWatcherState = Mutex<Option<JoinHandle<()>>>;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VaultList {
+    pub vaults: Vec<Vault>,
+    pub open: String,
+}
+
+fn dirs(app: &AppHandle) -> AppResult<(PathBuf, PathBuf)> {
+    let config_dir = app.path().app_config_dir().map_err(AppError::internal("No app config dir"))?;
+    let app_data_dir = app.path().app_data_dir().map_err(AppError::internal("No app data dir"))?;
+    Ok((config_dir, app_data_dir))
+}
+
+fn open_id(app: &AppHandle) -> String {
+    let open = app.state::<OpenVaultState>();
+    let open = open.read().expect("open vault state poisoned");
+    open.vault.id.clone()
+}
+
+/// (Re)starts the external change watcher on `data_dir`'s DB.
+pub(crate) async fn restart_db_watcher(app: &AppHandle, data_dir: &Path) {
+    let bus = app.state::<EventBus>().inner().clone();
+    let state = app.state::<DbWatcherState>();
+    let mut watcher = state.lock().await;
+    if let Some(task) = watcher.take() {
+        task.abort();
+    }
+
+    let data_dir = data_dir.to_path_buf();
+    *watcher = Some(tauri::async_runtime::spawn(async move {
+        match db::open_connection(&data_dir).await {
+            Ok(conn) => events::watch_external_changes(conn, bus, Duration::from_secs(2)).await,
+            Err(e) => eprintln!("❌ External change watcher disabled: {}", e),
+        }
+    }));
+}
+
+#[tauri::command]
+pub async fn list_vaults(app: AppHandle) -> AppResult<VaultList> {
+    let (config_dir, app_data_dir) = dirs(&app)?;
+    let registry = VaultRegistry::load(&config_dir, &app_data_dir)?;
+    Ok(VaultList { vaults: registry.vaults, open: open_id(&app) })
+}
+
+/// Adds an empty vault (it is not opened).
+#[tauri::command]
+pub async fn create_vault(app: AppHandle, name: String) -> AppResult<Vault> {
+    let (config_dir, app_data_dir) = dirs(&app)?;
+    let mut registry = VaultRegistry::load(&config_dir, &app_data_dir)?;
+    let vault = registry.create(&name, &app_data_dir)?;
+    std::fs::create_dir_all(&vault.data_dir).map_err(AppError::io("Failed to create vault dir"))?;
+    registry.save(&config_dir)?;
+
+    println!("✓ Created vault {} at {}", vault.name, vault.data_dir.display());
+    Ok(vault)
+}
+
+#[tauri::command]
+pub async fn rename_vault(app: AppHandle, id: String, name: String) -> AppResult<Vault> {
+    let (config_dir, app_data_dir) = dirs(&app)?;
+    let mut registry = VaultRegistry::load(&config_dir, &app_data_dir)?;
+    let vault = registry.rename(&id, &name)?;
+    registry.save(&config_dir)?;
+
+    if vault.id == open_id(&app) {
+        app.state::<OpenVaultState>().write().expect("open vault state poisoned").vault = vault.clone();
+    }
+    Ok(vault)
+}
+
+/// Opens `id` and remembers it for the next start.
+#[tauri::command]
+pub async fn open_vault(app: AppHandle, id: String) -> AppResult<Vault> {
+    let (config_dir, app_data_dir) = dirs(&app)?;
+    let mut registry = VaultRegistry::load(&config_dir, &app_data_dir)?;
+    let vault = registry.get(&id)?.clone();
+
+    if vault.id != open_id(&app) {
+        switch_to(&app, &vault, &config_dir).await?;
+    }
+    registry.last_opened = Some(vault.id.clone());
+    registry.save(&config_dir)?;
+    Ok(vault)
+}
+
+/// Removes a vault from the list (its files stay on disk). Closing the
+/// open vault switches to the default one first.
+#[tauri::command]
+pub async fn close_vault(app: AppHandle, id: String) -> AppResult<()> {
+    let (config_dir, app_data_dir) = dirs(&app)?;
+    let mut registry = VaultRegistry::load(&config_dir, &app_data_dir)?;
+    let vault = registry.close(&id)?;
+
+    if vault.id == open_id(&app) {
+        switch_to(&app, registry.last_opened(), &config_dir).await?;
+    }
+    registry.save(&config_dir)?;
+
+    println!("✓ Closed vault {}", vault.name);
+    Ok(())
+}
+
+/// Loads `vault`, swaps it in and restarts what served the old one.
+async fn switch_to(app: &AppHandle, vault: &Vault, config_dir: &Path) -> AppResult<()> {
+    let bus = app.state::<EventBus>().inner().clone();
+    let loaded = vaults::load(vault, config_dir, bus.clone()).await?;
+
+    // Nothing may keep writing to the old vault past this point
+    *app.state::<FolderWatcherState>().lock().await = None;
+    api::stop(app).await;
+    let open = vaults::swap_in(&app.state::<StorageSlot>(), &app.state::<SettingsState>(), loaded).await;
+    *app.state::<OpenVaultState>().write().expect("open vault state poisoned") = open;
+
+    println!("✓ Opened vault {}", vault.name);
+    bus.publish(AppEvent::VaultOpened { id: vault.id.clone() });
+    restart_db_watcher(app, &vault.data_dir).await;
+    apply_changes(app, true, true).await
+}
@@ -0,0 +1,168 @@
+// src-tauri/src/crypto/keyfile.rs
+//! `encryption.json` in a vault's data dir: the vault key, wrapped by a key
+//! derived from the passphrase. Its presence marks the vault as encrypted.
+//! Password protected notes keep their own key wrapped the same way
+//! ([`WrappedKey`]).
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::OsRng;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use crate::crypto::VaultKey;
+use crate::error::{AppError, AppResult};
+
+const FILE_NAME: &str = "encryption.json";
+const FORMAT_VERSION: u32 = 1;
+const SALT_LEN: usize = 16;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyFile {
+    pub version: u32,
+    #[serde(flatten)]
+    pub key: WrappedKey,
+}
+
+/// A key sealed with a key derived from a passphrase.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WrappedKey {
+    pub kdf: KdfParams,
+    /// The key sealed with the passphrase key (base64).
+    pub wrapped_key: String,
+}
+
+/// Argon2id parameters, kept with the file so they can be raised later
+/// without breaking existing vaults.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KdfParams {
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+    /// base64
+    pub salt: String,
+}
+
+impl KdfParams {
+    /// OWASP's baseline for Argon2id, with a fresh salt.
+    fn new() -> Self {
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        Self { memory_kib: 19 * 1024, iterations: 2, parallelism: 1, salt: BASE64.encode(salt) }
+    }
+
+    fn derive(&self, passphrase: &str) -> AppResult<VaultKey> {
+        let salt = BASE64.decode(&self.salt).map_err(AppError::internal("Invalid key file salt"))?;
+        let params = Params::new(self.memory_kib, self.iterations, self.parallelism, Some(32))
+            .map_err(AppError::internal("Invalid key file parameters"))?;
+
+        let mut key = Zeroizing::new([0u8; 32]);
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(passphrase.as_bytes(), &salt, key.as_mut())
+            .map_err(AppError::internal("Failed to derive key"))?;
+        Ok(VaultKey::from_bytes(*key))
+    }
+}
+
+impl WrappedKey {
+    /// A new random key and that key wrapped with `passphrase`.
+    pub fn create(passphrase: &str) -> AppResult<(Self, VaultKey)> {
+        let key = VaultKey::generate();
+        Ok((Self::wrap(&key, passphrase)?, key))
+    }
+
+    fn wrap(key: &VaultKey, passphrase: &str) -> AppResult<Self> {
+        let kdf = KdfParams::new();
+        let wrapped = kdf.derive(passphrase)?.seal(key.as_bytes())?;
+        Ok(Self { kdf, wrapped_key: BASE64.encode(wrapped) })
+    }
+
+    /// The key, if `passphrase` is right.
+    pub fn unlock(&self, passphrase: &str) -> AppResult<VaultKey> {
+        let wrapped = BASE64.decode(&self.wrapped_key).map_err(AppError::internal("Invalid wrapped key"))?;
+        let bytes = Zeroizing::new(
+            self.kdf
+                .derive(passphrase)?
+                .open(&wrapped)
+                .map_err(|_| AppError::Validation("Wrong passphrase".to_string()))?,
+        );
+        let bytes: [u8; 32] = bytes.as_slice().try_into().map_err(AppError::internal("Invalid wrapped key"))?;
+        Ok(VaultKey::from_bytes(bytes))
+    }
+
+    /// Same key under `new` (with a new salt).
+    pub fn rewrap(&self, old: &str, new: &str) -> AppResult<Self> {
+        Self::wrap(&self.unlock(old)?, new)
+    }
+}
+
+impl KeyFile {
+    pub fn path(data_dir: &Path) -> PathBuf {
+        data_dir.join(FILE_NAME)
+    }
+
+    pub fn exists(data_dir: &Path) -> bool {
+        Self::path(data_dir).is_file()
+    }
+
+    /// A new vault key and the key file protecting it with `passphrase`.
+    pub fn create(passphrase: &str) -> AppResult<(Self, VaultKey)> {
+        let (key, vault_key) = WrappedKey::create(passphrase)?;
+        Ok((Self { version: FORMAT_VERSION, key }, vault_key))
+    }
+
+    /// The vault key, if `passphrase` is right.
+    pub fn unlock(&self, passphrase: &str) -> AppResult<VaultKey> {
+        self.key.unlock(passphrase)
+    }
+
+    /// Same vault key under `new` (with a new salt); nothing else changes.
+    pub fn rewrap(&self, old: &str, new: &str) -> AppResult<Self> {
+        Ok(Self { version: FORMAT_VERSION, key: self.key.rewrap(old, new)? })
+    }
+
+    pub fn load(data_dir: &Path) -> AppResult<Self> {
+        let contents = fs::read_to_string(Self::path(data_dir)).map_err(AppError::io("Failed to read key file"))?;
+        let key_file: Self = serde_json::from_str(&contents).map_err(AppError::internal("Invalid key file"))?;
+        if key_file.version > FORMAT_VERSION {
+            return Err(AppError::Validation(format!(
+                "This vault was encrypted by a newer version (key file v{})",
+                key_file.version
+            )));
+        }
+        Ok(key_file)
+    }
+
+    /// Written to a temporary file first: a crash never leaves the vault
+    /// without a readable key file.
+    pub fn save(&self, data_dir: &Path) -> AppResult<()> {
+        let json = serde_json::to_string_pretty(self).map_err(AppError::internal("Failed to encode key file"))?;
+        fs::create_dir_all(data_dir).map_err(AppError::io("Failed to create vault dir"))?;
+        let path = Self::path(data_dir);
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, json).map_err(AppError::io("Failed to write key file"))?;
+        fs::rename(&tmp, &path).map_err(AppError::io("Failed to write key file"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rewrapping_keeps_the_vault_key() {
+        let (key_file, key) = KeyFile::create("correct horse").unwrap();
+        let sealed = key.seal(b"note").unwrap();
+        assert!(matches!(key_file.unlock("wrong"), Err(AppError::Validation(_))));
+
+        let rewrapped = key_file.rewrap("correct horse", "battery staple").unwrap();
+        assert_ne!(rewrapped.key.kdf.salt, key_file.key.kdf.salt);
+        assert!(rewrapped.unlock("correct horse").is_err());
+        assert_eq!(rewrapped.unlock("battery staple").unwrap().open(&sealed).unwrap(), b"note");
+    }
+}
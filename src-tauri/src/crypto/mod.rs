@@ -0,0 +1,377 @@
+// src-tauri/src/crypto/mod.rs
+//! Encryption at rest for vaults that turn it on.
+//!
+//! A random 256-bit vault key encrypts note files, attachments and the
+//! `title`/`body` columns with XChaCha20-Poly1305. The vault key is stored
+//! in `encryption.json` wrapped by a key derived from the passphrase
+//! (Argon2id, see [`KeyFile`]), so changing the passphrase rewraps that one
+//! key and no file is rewritten. Tags, notebooks, file names and timestamps
+//! stay readable.
+//!
+//! Data that isn't sealed (written before encryption was turned on, or by
+//! another tool) is read as is, so turning encryption on can be resumed.
+//!
+//! Password protected notes are sealed once more, inside the vault layer,
+//! with a key of their own (wrapped by the note's passphrase, see
+//! [`WrappedKey`]). Their keys are held by the [`Keyring`] once unlocked
+//! for the session.
+
+pub mod keyfile;
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use zeroize::Zeroizing;
+
+use crate::error::{AppError, AppResult};
+
+pub use keyfile::{KeyFile, WrappedKey};
+
+/// Starts every sealed file: format marker, then nonce, then ciphertext.
+const MAGIC: &[u8; 4] = b"RNX1";
+/// Starts every sealed DB column, followed by the base64 sealed bytes.
+const TEXT_PREFIX: &str = "rnx1:";
+/// Same for data sealed with a note's own key.
+const NOTE_MAGIC: &[u8; 4] = b"RNN1";
+const NOTE_TEXT_PREFIX: &str = "rnn1:";
+const NONCE_LEN: usize = 24;
+
+/// 256-bit key; wiped from memory when dropped.
+pub struct VaultKey(Zeroizing<[u8; 32]>);
+
+impl VaultKey {
+    pub fn generate() -> Self {
+        Self::from_bytes(XChaCha20Poly1305::generate_key(&mut OsRng).into())
+    }
+
+    fn from_bytes(bytes: [u8; 32]) -> Self {
+        Self(Zeroizing::new(bytes))
+    }
+
+    fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    fn cipher(&self) -> XChaCha20Poly1305 {
+        XChaCha20Poly1305::new(Key::from_slice(self.as_bytes()))
+    }
+
+    /// Encrypts with a fresh random nonce.
+    pub fn seal(&self, plaintext: &[u8]) -> AppResult<Vec<u8>> {
+        self.seal_as(MAGIC, plaintext)
+    }
+
+    /// Decrypts what [`VaultKey::seal`] produced; fails if it was made with
+    /// another key or has been tampered with.
+    pub fn open(&self, sealed: &[u8]) -> AppResult<Vec<u8>> {
+        self.open_as(MAGIC, sealed)
+    }
+
+    fn seal_as(&self, magic: &[u8; 4], plaintext: &[u8]) -> AppResult<Vec<u8>> {
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = self.cipher().encrypt(&nonce, plaintext).map_err(AppError::internal("Failed to encrypt"))?;
+
+        let mut sealed = Vec::with_capacity(magic.len() + NONCE_LEN + ciphertext.len());
+        sealed.extend_from_slice(magic);
+        sealed.extend_from_slice(&nonce);
+        sealed.extend_from_slice(&ciphertext);
+        Ok(sealed)
+    }
+
+    fn open_as(&self, magic: &[u8; 4], sealed: &[u8]) -> AppResult<Vec<u8>> {
+        if !sealed.starts_with(magic) || sealed.len() < magic.len() + NONCE_LEN {
+            return Err(AppError::Internal("Not encrypted data".to_string()));
+        }
+        let (nonce, ciphertext) = sealed[magic.len()..].split_at(NONCE_LEN);
+        self.cipher()
+            .decrypt(XNonce::from_slice(nonce), ciphertext)
+            .map_err(|_| AppError::Internal("Encrypted data is damaged or from another vault".to_string()))
+    }
+
+    /// A note body sealed with a note key, as text (stored in the DB and
+    /// the note file alike).
+    pub fn seal_note_text(&self, text: &str) -> AppResult<String> {
+        Ok(format!("{}{}", NOTE_TEXT_PREFIX, BASE64.encode(self.seal_as(NOTE_MAGIC, text.as_bytes())?)))
+    }
+
+    pub fn open_note_text(&self, sealed: &str) -> AppResult<String> {
+        let encoded = sealed.strip_prefix(NOTE_TEXT_PREFIX).unwrap_or(sealed);
+        let sealed = BASE64.decode(encoded).map_err(AppError::internal("Protected note is damaged"))?;
+        String::from_utf8(self.open_as(NOTE_MAGIC, &sealed)?).map_err(AppError::internal("Protected note is damaged"))
+    }
+
+    /// Attachment bytes sealed with a note key.
+    pub fn seal_note_data(&self, data: &[u8]) -> AppResult<Vec<u8>> {
+        self.seal_as(NOTE_MAGIC, data)
+    }
+
+    pub fn open_note_data(&self, sealed: &[u8]) -> AppResult<Vec<u8>> {
+        self.open_as(NOTE_MAGIC, sealed)
+    }
+}
+
+/// True for output of [`VaultKey::seal`].
+pub fn is_sealed(data: &[u8]) -> bool {
+    data.starts_with(MAGIC)
+}
+
+/// True for output of [`Keyring::seal_text`] in an encrypted vault.
+pub fn is_sealed_text(text: &str) -> bool {
+    text.starts_with(TEXT_PREFIX)
+}
+
+/// True for the body of a password protected note.
+pub fn is_note_sealed_text(text: &str) -> bool {
+    text.starts_with(NOTE_TEXT_PREFIX)
+}
+
+/// True for an attachment of a password protected note.
+pub fn is_note_sealed(data: &[u8]) -> bool {
+    data.starts_with(NOTE_MAGIC)
+}
+
+/// Whether a vault is encrypted and, if so, its key while unlocked.
+/// Clones share the state, so locking applies to every storage clone.
+#[derive(Clone)]
+pub struct Keyring(Arc<KeyringState>);
+
+struct KeyringState {
+    encrypted: AtomicBool,
+    key: RwLock<Option<Arc<VaultKey>>>,
+    /// Keys of password protected notes unlocked this session, by note id.
+    notes: RwLock<HashMap<String, Arc<VaultKey>>>,
+    last_used: Mutex<Instant>,
+}
+
+impl Keyring {
+    /// For a vault without encryption: everything passes through.
+    pub fn plain() -> Self {
+        Self::new(false)
+    }
+
+    /// Locked if `data_dir` has a key file, plain otherwise.
+    pub fn for_vault(data_dir: &Path) -> Self {
+        Self::new(KeyFile::exists(data_dir))
+    }
+
+    fn new(encrypted: bool) -> Self {
+        Self(Arc::new(KeyringState {
+            encrypted: AtomicBool::new(encrypted),
+            key: RwLock::new(None),
+            notes: RwLock::new(HashMap::new()),
+            last_used: Mutex::new(Instant::now()),
+        }))
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        self.0.encrypted.load(Ordering::SeqCst)
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.is_encrypted() && self.0.key.read().expect("keyring poisoned").is_none()
+    }
+
+    /// Loads the key; a plain vault becomes encrypted from here on.
+    pub fn unlock(&self, key: VaultKey) {
+        *self.0.key.write().expect("keyring poisoned") = Some(Arc::new(key));
+        self.0.encrypted.store(true, Ordering::SeqCst);
+        self.touch();
+    }
+
+    /// Forgets the key, and those of unlocked notes; they are wiped once
+    /// in-flight operations drop them.
+    pub fn lock(&self) {
+        *self.0.key.write().expect("keyring poisoned") = None;
+        self.0.notes.write().expect("keyring poisoned").clear();
+    }
+
+    /// Keeps the key of a password protected note for the session.
+    pub fn unlock_note(&self, note_id: &str, key: VaultKey) {
+        self.0.notes.write().expect("keyring poisoned").insert(note_id.to_string(), Arc::new(key));
+    }
+
+    pub fn lock_note(&self, note_id: &str) {
+        self.0.notes.write().expect("keyring poisoned").remove(note_id);
+    }
+
+    /// Key of a protected note, `None` unless unlocked this session.
+    pub fn note_key(&self, note_id: &str) -> Option<Arc<VaultKey>> {
+        self.0.notes.read().expect("keyring poisoned").get(note_id).cloned()
+    }
+
+    /// A plain body as it's written for note `note_id`: sealed with its key
+    /// while the note is protected and unlocked, as is otherwise. Never
+    /// decided from the text, which may look sealed.
+    pub fn seal_note_text(&self, note_id: &str, text: &str) -> AppResult<String> {
+        match self.note_key(note_id) {
+            Some(key) => key.seal_note_text(text),
+            None => Ok(text.to_string()),
+        }
+    }
+
+    /// Attachment bytes as they're written for note `note_id` (see
+    /// [`Keyring::seal_note_text`]).
+    pub fn seal_note_data(&self, note_id: &str, data: &[u8]) -> AppResult<Vec<u8>> {
+        match self.note_key(note_id) {
+            Some(key) => key.seal_note_data(data),
+            None => Ok(data.to_vec()),
+        }
+    }
+
+    /// Attachment bytes of note `note_id` as read (after [`Keyring::open`]);
+    /// `NoteLocked` if the note is `protected` and isn't unlocked. Data of
+    /// other notes is never opened, whatever it starts with.
+    pub fn open_note_data(&self, note_id: &str, protected: bool, data: Vec<u8>) -> AppResult<Vec<u8>> {
+        // Plain files are left from a protect that was cut short
+        if !protected || !is_note_sealed(&data) {
+            return Ok(data);
+        }
+        match self.note_key(note_id) {
+            Some(key) => key.open_note_data(&data),
+            None => Err(AppError::NoteLocked(note_id.to_string())),
+        }
+    }
+
+    /// Time since data was last sealed or opened.
+    pub fn idle(&self) -> Duration {
+        self.0.last_used.lock().expect("keyring poisoned").elapsed()
+    }
+
+    fn touch(&self) {
+        *self.0.last_used.lock().expect("keyring poisoned") = Instant::now();
+    }
+
+    /// The key to use: `None` for a plain vault, `Locked` while locked.
+    fn key(&self) -> AppResult<Option<Arc<VaultKey>>> {
+        if !self.is_encrypted() {
+            return Ok(None);
+        }
+        let key = self.0.key.read().expect("keyring poisoned").clone().ok_or(AppError::Locked)?;
+        self.touch();
+        Ok(Some(key))
+    }
+
+    /// File contents as they go to disk.
+    pub fn seal(&self, data: &[u8]) -> AppResult<Vec<u8>> {
+        match self.key()? {
+            Some(key) => key.seal(data),
+            None => Ok(data.to_vec()),
+        }
+    }
+
+    /// File contents as read from disk.
+    pub fn open(&self, data: &[u8]) -> AppResult<Vec<u8>> {
+        if !is_sealed(data) {
+            return Ok(data.to_vec());
+        }
+        match self.key()? {
+            Some(key) => key.open(data),
+            // Sealed data in a vault without a key file: nothing can open it
+            None => Err(AppError::Locked),
+        }
+    }
+
+    /// A column value as it goes to the DB.
+    pub fn seal_text(&self, text: &str) -> AppResult<String> {
+        match self.key()? {
+            Some(key) => Ok(format!("{}{}", TEXT_PREFIX, BASE64.encode(key.seal(text.as_bytes())?))),
+            None => Ok(text.to_string()),
+        }
+    }
+
+    /// A column value as read from the DB.
+    pub fn open_text(&self, stored: String) -> AppResult<String> {
+        if !is_sealed_text(&stored) {
+            return Ok(stored);
+        }
+        let encoded = &stored[TEXT_PREFIX.len()..];
+        let sealed = BASE64
+            .decode(encoded)
+            .map_err(AppError::internal("Encrypted column is damaged"))?;
+        String::from_utf8(self.open(&sealed)?).map_err(AppError::internal("Encrypted column is damaged"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unlocked() -> Keyring {
+        let keyring = Keyring::new(true);
+        keyring.unlock(VaultKey::generate());
+        keyring
+    }
+
+    #[test]
+    fn seals_and_opens_files_and_columns() {
+        let keyring = unlocked();
+
+        let sealed = keyring.seal(b"<p>customer</p>").unwrap();
+        assert!(is_sealed(&sealed));
+        assert_ne!(keyring.seal(b"<p>customer</p>").unwrap(), sealed, "nonce reused");
+        assert_eq!(keyring.open(&sealed).unwrap(), b"<p>customer</p>");
+
+        let column = keyring.seal_text("Acme call").unwrap();
+        assert!(column.starts_with(TEXT_PREFIX) && !column.contains("Acme"));
+        assert_eq!(keyring.open_text(column).unwrap(), "Acme call");
+
+        // Written before encryption was on
+        assert_eq!(keyring.open(b"<p>old</p>").unwrap(), b"<p>old</p>");
+        assert_eq!(keyring.open_text("Old".to_string()).unwrap(), "Old");
+    }
+
+    #[test]
+    fn locked_keyring_refuses_and_plain_passes_through() {
+        let keyring = unlocked();
+        let sealed = keyring.seal(b"secret").unwrap();
+
+        keyring.lock();
+        assert!(keyring.is_locked());
+        assert!(matches!(keyring.open(&sealed), Err(AppError::Locked)));
+        assert!(matches!(keyring.seal_text("x"), Err(AppError::Locked)));
+
+        let plain = Keyring::plain();
+        assert_eq!(plain.seal(b"x").unwrap(), b"x");
+        assert!(matches!(plain.open(&sealed), Err(AppError::Locked)));
+    }
+
+    #[test]
+    fn note_keys_apply_per_note_until_the_vault_locks() {
+        let keyring = Keyring::plain();
+        assert_eq!(keyring.seal_note_text("n1", "<p>x</p>").unwrap(), "<p>x</p>");
+
+        keyring.unlock_note("n1", VaultKey::generate());
+        let sealed = keyring.seal_note_text("n1", "<p>x</p>").unwrap();
+        assert!(is_note_sealed_text(&sealed) && !is_sealed_text(&sealed));
+        assert_eq!(keyring.note_key("n1").unwrap().open_note_text(&sealed).unwrap(), "<p>x</p>");
+        // Plain text that only looks sealed is sealed all the same
+        let lookalike = keyring.seal_note_text("n1", "rnn1:x").unwrap();
+        assert_eq!(keyring.note_key("n1").unwrap().open_note_text(&lookalike).unwrap(), "rnn1:x");
+
+        let data = keyring.seal_note_data("n1", b"png").unwrap();
+        assert!(is_note_sealed(&data) && !is_sealed(&data));
+        assert_eq!(keyring.seal_note_data("n2", b"png").unwrap(), b"png");
+
+        keyring.lock();
+        assert!(keyring.note_key("n1").is_none());
+        assert!(matches!(keyring.open_note_data("n1", true, data), Err(AppError::NoteLocked(_))));
+    }
+
+    #[test]
+    fn another_key_or_tampering_fails() {
+        let key = VaultKey::generate();
+        let mut sealed = key.seal(b"secret").unwrap();
+        assert!(VaultKey::generate().open(&sealed).is_err());
+
+        let last = sealed.len() - 1;
+        sealed[last] ^= 1;
+        assert!(key.open(&sealed).is_err());
+    }
+}
@@ -1,15 +1,20 @@
-use sqlx::sqlite::{SqlitePool, SqlitePoolOptions};
+use sqlx::sqlite::{
+    SqliteConnectOptions, SqliteConnection, SqliteJournalMode, SqlitePool, SqlitePoolOptions, SqliteSynchronous,
+};
 use sqlx::migrate::MigrateDatabase;
-use sqlx::Sqlite;
-use std::path::PathBuf;
+use sqlx::{ConnectOptions, Sqlite};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+use crate::error::{AppError, AppResult};
 
 pub mod schema;
 
 /// Initialize SQLite:
 /// - If the DB file doesn't exist, create it.
 /// - Connect and run simple CREATE TABLE IF NOT EXISTS migrations.
-/// - Does NOT try to alter or migrate existing schemas.
-pub async fn init_database(app_data_dir: &PathBuf) -> Result<SqlitePool, String> {
+/// - Adds columns introduced after the first release (see `ensure_column`).
+pub async fn init_database(app_data_dir: &PathBuf) -> AppResult<SqlitePool> {
     let db_path = app_data_dir.join("rusty-notes.db");
     let db_url = format!("sqlite:{}", db_path.display());
 
@@ -20,15 +25,16 @@ pub async fn init_database(app_data_dir: &PathBuf) -> Result<SqlitePool, String>
     {
         Sqlite::create_database(&db_url)
             .await
-            .map_err(|e| format!("Failed to create database: {}", e))?;
+            .map_err(AppError::db("Failed to create database"))?;
     }
 
-    // Connect to database
+    let options = connect_options(app_data_dir)?;
     let pool = SqlitePoolOptions::new()
-        .max_connections(5)
-        .connect(&db_url)
+        .max_connections(pool_size())
+        .min_connections(1)
+        .connect_with(options)
         .await
-        .map_err(|e| format!("Failed to connect to database: {}", e))?;
+        .map_err(AppError::db("Failed to connect to database"))?;
 
     // For a brand-new DB, this will create tables.
     // For an existing DB, this will NO-OP (tables already exist).
@@ -37,7 +43,39 @@ pub async fn init_database(app_data_dir: &PathBuf) -> Result<SqlitePool, String>
     Ok(pool)
 }
 
-async fn run_migrations(pool: &SqlitePool) -> Result<(), String> {
+/// Opens one connection outside the pool, for work that must not share a
+/// connection with the app's own writes (e.g. `PRAGMA data_version` polling).
+pub async fn open_connection(app_data_dir: &Path) -> AppResult<SqliteConnection> {
+    connect_options(app_data_dir)?
+        .connect()
+        .await
+        .map_err(AppError::db("Failed to connect to database"))
+}
+
+/// The GUI and the CLI may have the DB open at the same time, so wait for
+/// locks instead of failing with SQLITE_BUSY.
+/// WAL lets readers run alongside the (single) writer; `NORMAL` sync is
+/// durable in WAL mode except for the last commits on power loss.
+fn connect_options(app_data_dir: &Path) -> AppResult<SqliteConnectOptions> {
+    let db_url = format!("sqlite:{}", app_data_dir.join("rusty-notes.db").display());
+    Ok(SqliteConnectOptions::from_str(&db_url)
+        .map_err(AppError::db("Invalid database path"))?
+        .journal_mode(SqliteJournalMode::Wal)
+        .synchronous(SqliteSynchronous::Normal)
+        .busy_timeout(Duration::from_secs(5)))
+}
+
+/// One connection per core for parallel reads (SQLite still runs one
+/// writer at a time), but never fewer than 8 so a burst of list/search
+/// calls doesn't leave an autosave waiting for a free connection.
+fn pool_size() -> u32 {
+    std::thread::available_parallelism()
+        .map(|n| n.get() as u32)
+        .unwrap_or(4)
+        .clamp(8, 16)
+}
+
+async fn run_migrations(pool: &SqlitePool) -> AppResult<()> {
     sqlx::query(
         r#"
         -- Notes table (new DBs get file_path from day one)
@@ -69,11 +107,86 @@ async fn run_migrations(pool: &SqlitePool) -> Result<(), String> {
 
         CREATE INDEX IF NOT EXISTS idx_notes_updated 
             ON notes(updated_at DESC);
+
+        -- Free-form labels (Keep labels, Simplenote/Joplin tags)
+        CREATE TABLE IF NOT EXISTS note_tags (
+            note_id     TEXT NOT NULL,
+            tag         TEXT NOT NULL,
+            PRIMARY KEY(note_id, tag),
+            FOREIGN KEY(note_id) REFERENCES notes(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_note_tags_tag 
+            ON note_tags(tag);
+
+        -- Which external item each imported note came from (dedup on re-import)
+        CREATE TABLE IF NOT EXISTS import_sources (
+            source      TEXT NOT NULL,
+            source_id   TEXT NOT NULL,
+            note_id     TEXT NOT NULL,
+            imported_at INTEGER NOT NULL,
+            PRIMARY KEY(source, source_id)
+        );
+
+        -- Links parsed out of note bodies ([[Title]] or note://<id>)
+        CREATE TABLE IF NOT EXISTS note_links (
+            source_id   TEXT NOT NULL,
+            position    INTEGER NOT NULL,
+            target      TEXT NOT NULL,
+            by_id       INTEGER NOT NULL,
+            PRIMARY KEY(source_id, position),
+            FOREIGN KEY(source_id) REFERENCES notes(id) ON DELETE CASCADE
+        );
         "#
     )
     .execute(pool)
     .await
-    .map_err(|e| format!("Failed to run migrations: {}", e))?;
+    .map_err(AppError::db("Failed to run migrations"))?;
+
+    // Columns added after the first release
+    ensure_column(pool, "notes", "notebook", "TEXT").await?;
+    ensure_column(pool, "notes", "is_archived", "INTEGER DEFAULT 0").await?;
+    ensure_column(pool, "notes", "is_pinned", "INTEGER DEFAULT 0").await?;
+    ensure_column(pool, "notes", "is_favorite", "INTEGER DEFAULT 0").await?;
+    // Bumped on every change to a note; carried on change events
+    ensure_column(pool, "notes", "version", "INTEGER NOT NULL DEFAULT 1").await?;
+    // Wrapped key of a password protected note (JSON), NULL otherwise
+    ensure_column(pool, "notes", "password_lock", "TEXT").await?;
+    // Set once the note's links are in note_links (older notes get backfilled)
+    ensure_column(pool, "notes", "links_indexed", "INTEGER NOT NULL DEFAULT 0").await?;
+    // Date (YYYY-MM-DD) of a daily note, NULL for other notes
+    ensure_column(pool, "notes", "daily_date", "TEXT").await?;
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_notes_daily_date ON notes(daily_date)")
+        .execute(pool)
+        .await
+        .map_err(AppError::db("Failed to run migrations"))?;
+
+    Ok(())
+}
+
+/// Adds `column` to `table` if an older DB doesn't have it yet.
+/// SQLite has no `ADD COLUMN IF NOT EXISTS`, so check `table_info` first.
+async fn ensure_column(
+    pool: &SqlitePool,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> AppResult<()> {
+    let exists: Option<(String,)> = sqlx::query_as(
+        "SELECT name FROM pragma_table_info(?) WHERE name = ?"
+    )
+    .bind(table)
+    .bind(column)
+    .fetch_optional(pool)
+    .await
+    .map_err(AppError::db(format!("Failed to inspect {}", table)))?;
+
+    if exists.is_none() {
+        sqlx::query(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition))
+            .execute(pool)
+            .await
+            .map_err(AppError::db(format!("Failed to add {}.{}", table, column)))?;
+    }
 
     Ok(())
 }
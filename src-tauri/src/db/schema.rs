@@ -23,6 +23,49 @@ pub struct Note {
     /// Full filesystem path to the note’s file (e.g. HTML/Markdown).
     /// This is what your delete / trash logic will use instead of guessing.
     pub file_path: String,
+
+    /// Optional notebook (folder) the note belongs to, e.g. a Joplin notebook.
+    #[serde(default)]
+    pub notebook: Option<String>,
+
+    /// Incremented by every change (content, tags, attachments, flags).
+    /// 0 means "not from this DB" and is left out of JSON (see `NoteExport`).
+    #[serde(default, skip_serializing_if = "is_unversioned")]
+    pub version: i64,
+
+    /// Listed before all other notes. Flags are left out of JSON when false.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub pinned: bool,
+
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub favorite: bool,
+
+    /// Hidden from the default list and search, but not in the trash.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub archived: bool,
+}
+
+/// Which notes a list or search returns; the default is every active,
+/// non-archived note. Pinned notes always come first.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct NoteFilter {
+    /// Archived notes instead of the others.
+    #[serde(default)]
+    pub archived: bool,
+    /// Favorites only.
+    #[serde(default)]
+    pub favorites: bool,
+    /// Pinned notes only.
+    #[serde(default)]
+    pub pinned: bool,
+}
+
+fn is_unversioned(version: &i64) -> bool {
+    *version == 0
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
 }
 
 /// File/asset attached to a note.
@@ -70,4 +113,9 @@ pub struct NoteWithAttachments {
 
     /// All attachments linked to this note.
     pub attachments: Vec<Attachment>,
+
+    /// Password protected and not unlocked this session: `body` is empty
+    /// and attachment contents can't be read. Left out of JSON when false.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub locked: bool,
 }
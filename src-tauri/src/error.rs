@@ -0,0 +1,182 @@
+// src-tauri/src/error.rs
+//! One error type for services, commands, the API and the CLI.
+//!
+//! Reaches the webview as `{ code, message, details }`, so the frontend can
+//! branch on `code` ("not_found", "conflict", ...) instead of matching text.
+
+use std::io;
+
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+
+use crate::db::schema::Note;
+
+#[derive(Debug, thiserror::Error)]
+pub enum AppError {
+    /// What is missing, e.g. "Note 1234".
+    #[error("{0} not found")]
+    NotFound(String),
+
+    /// The note changed since the client read `expected_version`;
+    /// `current` is what is stored now, so the client can merge or retry.
+    #[error("Note {} was changed elsewhere (version {}, expected {})", current.id, current.version, expected_version)]
+    Conflict { expected_version: i64, current: Box<Note> },
+
+    #[error("{context}: {source}")]
+    Io {
+        context: String,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("{context}: {source}")]
+    Database {
+        context: String,
+        #[source]
+        source: sqlx::Error,
+    },
+
+    /// Bad input from the user or a client (also: unreadable import files).
+    #[error("{0}")]
+    Validation(String),
+
+    /// Bad input in a form (e.g. settings), one entry per offending field.
+    #[error("{}", .0.iter().map(|e| format!("{}: {}", e.field, e.message)).collect::<Vec<_>>().join("; "))]
+    InvalidFields(Vec<FieldError>),
+
+    #[error("{0}")]
+    PermissionDenied(String),
+
+    /// The vault is encrypted and its key isn't loaded; unlock it first.
+    #[error("The vault is locked")]
+    Locked,
+
+    /// The note is password protected and not unlocked this session.
+    #[error("Note {0} is locked")]
+    NoteLocked(String),
+
+    /// Anything else (third-party errors without a better home).
+    #[error("{0}")]
+    Internal(String),
+}
+
+pub type AppResult<T> = Result<T, AppError>;
+
+/// One rejected field; `field` is the settings key, e.g. `api.port`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self { field: field.into(), message: message.into() }
+    }
+}
+
+impl AppError {
+    /// `.map_err(AppError::io("Failed to write note file"))`.
+    /// OS permission errors become [`AppError::PermissionDenied`].
+    pub fn io(context: impl Into<String>) -> impl FnOnce(io::Error) -> AppError {
+        let context = context.into();
+        move |source| {
+            if source.kind() == io::ErrorKind::PermissionDenied {
+                AppError::PermissionDenied(format!("{}: {}", context, source))
+            } else {
+                AppError::Io { context, source }
+            }
+        }
+    }
+
+    /// `.map_err(AppError::db("Failed to create note"))`.
+    pub fn db(context: impl Into<String>) -> impl FnOnce(sqlx::Error) -> AppError {
+        let context = context.into();
+        move |source| AppError::Database { context, source }
+    }
+
+    /// `.map_err(AppError::invalid("Not a Simplenote export"))` for input
+    /// that could not be parsed.
+    pub fn invalid<E: std::fmt::Display>(context: impl Into<String>) -> impl FnOnce(E) -> AppError {
+        let context = context.into();
+        move |error| AppError::Validation(format!("{}: {}", context, error))
+    }
+
+    /// `.map_err(AppError::internal("Failed to save settings"))` for any
+    /// displayable error.
+    pub fn internal<E: std::fmt::Display>(context: impl Into<String>) -> impl FnOnce(E) -> AppError {
+        let context = context.into();
+        move |error| AppError::Internal(format!("{}: {}", context, error))
+    }
+
+    /// Stable identifier for the frontend and API clients.
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::NotFound(_) => "not_found",
+            AppError::Conflict { .. } => "conflict",
+            AppError::Io { .. } => "io",
+            AppError::Database { .. } => "database",
+            AppError::Validation(_) | AppError::InvalidFields(_) => "validation",
+            AppError::PermissionDenied(_) => "permission_denied",
+            AppError::Locked => "locked",
+            AppError::NoteLocked(_) => "note_locked",
+            AppError::Internal(_) => "internal",
+        }
+    }
+
+    /// Machine-readable extras (`null` when there are none).
+    pub fn details(&self) -> serde_json::Value {
+        match self {
+            AppError::Conflict { expected_version, current } => serde_json::json!({
+                "expected_version": expected_version,
+                "current": current,
+            }),
+            AppError::InvalidFields(fields) => serde_json::json!({ "fields": fields }),
+            _ => serde_json::Value::Null,
+        }
+    }
+}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("AppError", 3)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.serialize_field("details", &self.details())?;
+        state.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serializes_code_message_and_details() {
+        let error = AppError::NotFound("Note 42".to_string());
+        assert_eq!(
+            serde_json::to_value(&error).unwrap(),
+            serde_json::json!({ "code": "not_found", "message": "Note 42 not found", "details": null })
+        );
+    }
+
+    #[test]
+    fn io_permission_errors_are_permission_denied() {
+        let denied = AppError::io("Failed to write note file")(io::Error::from(io::ErrorKind::PermissionDenied));
+        assert_eq!(denied.code(), "permission_denied");
+
+        let full = AppError::io("Failed to write note file")(io::Error::other("disk full"));
+        assert_eq!(full.code(), "io");
+        assert_eq!(full.to_string(), "Failed to write note file: disk full");
+    }
+
+    #[test]
+    fn field_errors_are_listed_in_details() {
+        let error = AppError::InvalidFields(vec![FieldError::new("api.port", "Port must be between 1024 and 65535")]);
+        assert_eq!(error.to_string(), "api.port: Port must be between 1024 and 65535");
+        assert_eq!(
+            serde_json::to_value(&error).unwrap()["details"],
+            serde_json::json!({ "fields": [{ "field": "api.port", "message": "Port must be between 1024 and 65535" }] })
+        );
+    }
+}
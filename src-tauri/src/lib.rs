@@ -1,7 +1,12 @@
 // src-tauri/src/lib.rs
+pub mod error;
+pub mod crypto;
 pub mod settings;
 pub mod commands;
 pub mod db;
 pub mod services;
 pub mod models;
 pub mod shortcuts;
+pub mod tray;
+pub mod api;
+pub mod vaults;
@@ -1,23 +1,34 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod api;
 mod commands;
+mod crypto;
 mod db;
+mod error;
 mod models;
 mod services;
 mod settings;
 mod shortcuts;
+mod tray;
+mod vaults;
 
 use std::sync::Arc;
-use tauri::Manager;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::broadcast::error::RecvError;
 use tokio::sync::Mutex;
 
+use services::events::{AppEvent, EventBus, EventEnvelope};
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 fn main() {
     tauri::Builder::default()
+        .plugin(tauri_plugin_log::Builder::new().build())
         .plugin(tauri_plugin_persisted_scope::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_global_shortcut::Builder::new().build())
+        .plugin(tauri_plugin_clipboard_manager::init())
+        .on_window_event(tray::on_window_event)
         .setup(|app| {
             // Get app data directory
             let app_data_dir = app
@@ -28,39 +39,77 @@ fn main() {
             // Ensure directory exists
             std::fs::create_dir_all(&app_data_dir).expect("Failed to create app data directory");
 
-            // Load/init settings
-            let settings = crate::settings::load_or_init(
-                app.path()
-                    .app_config_dir()
-                    .expect("Failed to get app config dir"),
-            )
-            .expect("Failed to load settings");
-
-            // ✅ No manual scoping needed here either
-            println!("✓ Settings loaded: {}", settings.notes_folder);
-
-            // Register settings state (using tokio Mutex for async commands)
-            let settings_state = Arc::new(Mutex::new(settings));
-            app.manage(settings_state);
-
-            // Initialize database
-            let pool = tauri::async_runtime::block_on(async {
-                db::init_database(&app_data_dir)
-                    .await
-                    .expect("Failed to initialize database")
+            let config_dir = app.path().app_config_dir().expect("Failed to get app config dir");
+
+            // Open the vault used last (the default one on first start)
+            let registry = vaults::VaultRegistry::load(&config_dir, &app_data_dir).unwrap_or_else(|e| {
+                eprintln!("❌ Failed to read vaults, opening the default one: {}", e);
+                vaults::VaultRegistry::new(&app_data_dir)
             });
+            let bus = EventBus::new();
+            let loaded = tauri::async_runtime::block_on(async {
+                match vaults::load(registry.last_opened(), &config_dir, bus.clone()).await {
+                    Ok(loaded) => loaded,
+                    Err(e) => {
+                        eprintln!("❌ Failed to open vault {}, opening the default one: {}", registry.last_opened().name, e);
+                        let default = registry.get(vaults::DEFAULT_VAULT_ID).expect("default vault is always listed");
+                        vaults::load(default, &config_dir, bus.clone())
+                            .await
+                            .expect("Failed to initialize database")
+                    }
+                }
+            });
+            let data_dir = loaded.vault.data_dir.clone();
+            let shortcut_settings = loaded.settings.shortcuts.clone();
+            let open_vault = loaded.open_vault();
+
+            // Register state (tokio Mutex for async commands); opening another
+            // vault swaps the contents, the managed values stay
+            app.manage(tray::CloseToTray::new(loaded.settings.tray.close_to_tray));
+            app.manage(Arc::new(Mutex::new(loaded.settings)));
+            app.manage(vaults::StorageSlot::new(loaded.storage));
+            app.manage(std::sync::RwLock::new(open_vault));
+            app.manage(bus.clone());
 
-            // Create storage service
-            let storage = Arc::new(Mutex::new(services::db_storage::DbStorage::new(
-                pool,
-                app_data_dir,
-            )));
+            // Forward storage events to the webview as `app-event`
+            tauri::async_runtime::spawn(forward_events(app.handle().clone(), bus.clone()));
 
-            // Store in app state
-            app.manage(storage);
+            // Notice writes from other processes (CLI) sharing the DB
+            app.manage(commands::vaults::DbWatcherState::default());
+            let handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                commands::vaults::restart_db_watcher(&handle, &data_dir).await;
+            });
+
+            // Sync edits made to the notes folder by other tools
+            app.manage(commands::settings_commands::FolderWatcherState::default());
+            let handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = commands::settings_commands::restart_folder_watcher(&handle).await {
+                    eprintln!("❌ Notes folder watcher disabled: {}", e);
+                }
+            });
+
+            // Local HTTP API (opt-in via settings.api.enabled)
+            app.manage(api::ApiServerState::default());
+            let handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = api::start(&handle).await {
+                    eprintln!("❌ Failed to start API server: {}", e);
+                }
+            });
+
+            // Tray icon; recent notes follow the bus
+            if let Err(e) = tray::create(app.handle()) {
+                eprintln!("❌ Failed to create tray icon: {}", e);
+            }
+            tauri::async_runtime::spawn(tray::follow_notes(app.handle().clone(), bus.clone()));
+
+            // Encrypted vaults lock after settings.encryption.auto_lock_minutes idle
+            tauri::async_runtime::spawn(commands::encryption::auto_lock(app.handle().clone()));
 
             // ✅ Register all shortcuts
-            if let Err(e) = shortcuts::register_all(&app.handle()) {
+            if let Err(e) = shortcuts::register_all(app.handle(), &shortcut_settings) {
                 eprintln!("❌ Failed to register shortcuts: {}", e);
             }
 
@@ -74,12 +123,68 @@ fn main() {
             commands::notes::update_note,
             commands::notes::delete_note,
             commands::notes::search_notes,
+            commands::notes::protect_note,
+            commands::notes::unprotect_note,
+            commands::notes::unlock_note,
+            commands::notes::lock_note,
+            commands::notes::set_note_pinned,
+            commands::notes::set_note_favorite,
+            commands::notes::set_note_archived,
+            commands::notes::get_backlinks,
+            commands::notes::get_outgoing_links,
+            commands::notes::get_broken_links,
+            // import commands
+            commands::import::import_notes,
+            // export commands
+            commands::export::export_note,
+            commands::graph::get_graph,
+            commands::graph::export_graph,
+            commands::templates::list_templates,
+            commands::templates::create_note_from_template,
+            commands::daily_notes::open_daily_note,
             // settings commands
             commands::settings_commands::get_settings,
             commands::settings_commands::complete_onboarding,
             commands::settings_commands::update_settings,
+            commands::settings_commands::regenerate_api_token,
+            commands::settings_commands::move_notes_folder,
+            // vault commands
+            commands::vaults::list_vaults,
+            commands::vaults::create_vault,
+            commands::vaults::open_vault,
+            commands::vaults::rename_vault,
+            commands::vaults::close_vault,
+            // quick capture commands
+            commands::quick_capture::save_quick_capture,
+            commands::quick_capture::dismiss_quick_capture,
+            commands::quick_capture::quick_capture_prefill,
+            // encryption commands
+            commands::encryption::encryption_status,
+            commands::encryption::enable_encryption,
+            commands::encryption::unlock_vault,
+            commands::encryption::lock_vault,
+            commands::encryption::change_passphrase,
             // ✅ Remove restore_notes_directory_scope - not needed
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+/// Emits every bus event to the webview. If the webview falls behind, it
+/// gets a `resync` straight away (not via the bus, which would only lag more).
+async fn forward_events(app: AppHandle, bus: EventBus) {
+    let mut rx = bus.subscribe();
+    loop {
+        let envelope = match rx.recv().await {
+            Ok(envelope) => envelope,
+            Err(RecvError::Lagged(missed)) => {
+                eprintln!("⚠️ Dropped {} app events, asking the UI to resync", missed);
+                EventEnvelope { seq: bus.last_seq(), event: AppEvent::Resync { missed } }
+            }
+            Err(RecvError::Closed) => break,
+        };
+        if let Err(e) = app.emit("app-event", envelope) {
+            eprintln!("Failed to emit app-event: {e}");
+        }
+    }
+}
@@ -0,0 +1,125 @@
+// src-tauri/src/services/daily_notes.rs
+//! Daily notes: one note per date, titled, filed and started from a
+//! template as the daily note settings say. The date is kept on the note,
+//! so renaming it or changing the title format never makes a second one.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::Arc;
+
+use chrono::{Local, NaiveDate};
+use serde::Serialize;
+use tokio::sync::Mutex;
+
+use crate::db::schema::NoteWithAttachments;
+use crate::error::{AppError, AppResult};
+use crate::services::db_storage::DbStorage;
+use crate::services::note_service::NoteService;
+use crate::services::templates::Templates;
+use crate::settings::Settings;
+
+/// How dates are passed around and stored.
+pub const DATE_FORMAT: &str = "%Y-%m-%d";
+
+#[derive(Debug, Serialize)]
+pub struct DailyNote {
+    #[serde(flatten)]
+    pub note: NoteWithAttachments,
+    /// `YYYY-MM-DD`
+    pub date: String,
+    /// Closest earlier date with a daily note.
+    pub previous: Option<String>,
+    /// Closest later date with a daily note.
+    pub next: Option<String>,
+}
+
+pub struct DailyNotes {
+    storage: DbStorage,
+    settings: Arc<Mutex<Settings>>,
+    notes: NoteService,
+    templates: Templates,
+}
+
+impl DailyNotes {
+    pub fn new(storage: DbStorage, settings: Arc<Mutex<Settings>>) -> Self {
+        Self {
+            notes: NoteService::new(storage.clone(), settings.clone()),
+            templates: Templates::new(storage.clone(), settings.clone()),
+            storage,
+            settings,
+        }
+    }
+
+    /// The note for `date` (`YYYY-MM-DD`, today when `None`), created if
+    /// there is none yet.
+    pub async fn open(&self, date: Option<&str>) -> AppResult<DailyNote> {
+        let date = match date {
+            Some(text) => parse_date(text)?,
+            None => Local::now().date_naive(),
+        };
+        let key = date.format(DATE_FORMAT).to_string();
+
+        let note = {
+            // Two opens of a new day must not both create its note
+            let _writes = self.storage.lock_writes().await;
+            match self.storage.find_daily_note(&key).await? {
+                Some(note) => note,
+                None => self.create(date, &key).await?,
+            }
+        };
+        let (previous, next) = self.storage.daily_note_neighbors(&key).await?;
+        Ok(DailyNote { note, date: key, previous, next })
+    }
+
+    async fn create(&self, date: NaiveDate, key: &str) -> AppResult<NoteWithAttachments> {
+        let config = self.settings.lock().await.daily_notes.clone();
+        let title = title_for(date, &config.title_format)?;
+        let notebook = Some(config.notebook.trim().to_string()).filter(|notebook| !notebook.is_empty());
+
+        let template = config.template.trim();
+        let note = if template.is_empty() {
+            self.notes.create_in(title, String::new(), notebook).await?
+        } else {
+            // {{date}} is the note's day, not today
+            let vars = HashMap::from([("date".to_string(), key.to_string()), ("title".to_string(), title)]);
+            self.templates.create_note_in(template, vars, notebook).await?
+        };
+        self.storage.set_daily_date(&note.id, key).await?;
+
+        log::info!("✓ Created daily note {} for {}", note.id, key);
+        self.storage.get_note(&note.id).await
+    }
+}
+
+fn parse_date(text: &str) -> AppResult<NaiveDate> {
+    NaiveDate::parse_from_str(text.trim(), DATE_FORMAT)
+        .map_err(|_| AppError::Validation(format!("Not a date (YYYY-MM-DD): {}", text)))
+}
+
+/// `date` in the chrono `format`; a bad format is a validation error
+/// rather than a panic.
+fn title_for(date: NaiveDate, format: &str) -> AppResult<String> {
+    let mut title = String::new();
+    write!(title, "{}", date.format(format))
+        .map_err(|_| AppError::Validation(format!("Not a valid daily note title format: {}", format)))?;
+    Ok(title.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_titles_and_parses_dates() {
+        let date = parse_date(" 2026-03-04 ").unwrap();
+        assert_eq!(title_for(date, "%A, %B %-d %Y").unwrap(), "Wednesday, March 4 2026");
+        assert_eq!(title_for(date, DATE_FORMAT).unwrap(), "2026-03-04");
+        assert!(matches!(title_for(date, "%Q"), Err(AppError::Validation(_))));
+        // Only a date: there is no time to format
+        assert!(matches!(title_for(date, "%H:%M"), Err(AppError::Validation(_))));
+
+        for bad in ["2026-02-30", "04.03.2026", ""] {
+            assert!(matches!(parse_date(bad), Err(AppError::Validation(_))), "{}", bad);
+        }
+    }
+}
@@ -1,19 +1,25 @@
 use sqlx::{SqlitePool, Row};
+use sqlx::sqlite::SqliteRow;
 use crate::db::schema::Attachment;
+use crate::error::{AppError, AppResult};
+use crate::services::vault_root::VaultRoot;
 
 /// Manages attachment records only.
-/// Links attachments to notes via note_id.
+/// Links attachments to notes via note_id. `file_path` is stored relative
+/// to `root` and returned absolute.
+#[derive(Clone)]
 pub struct AttachmentStorage {
     pool: SqlitePool,
+    root: VaultRoot,
 }
 
 impl AttachmentStorage {
-    pub fn new(pool: SqlitePool) -> Self {
-        Self { pool }
+    pub fn new(pool: SqlitePool, root: VaultRoot) -> Self {
+        Self { pool, root }
     }
 
     /// Creates attachment record after saving file to disk.
-    pub async fn create(&self, attachment: Attachment) -> Result<(), String> {
+    pub async fn create(&self, attachment: Attachment) -> AppResult<()> {
         sqlx::query(
             "INSERT INTO attachments (id, note_id, attachment_type, file_name, file_path, mime_type, size_bytes, created_at)
              VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
@@ -22,19 +28,19 @@ impl AttachmentStorage {
         .bind(&attachment.note_id)
         .bind(&attachment.attachment_type)
         .bind(&attachment.file_name)
-        .bind(&attachment.file_path)
+        .bind(self.root.to_stored(&attachment.file_path))
         .bind(&attachment.mime_type.as_deref())
         .bind(attachment.size_bytes)
         .bind(attachment.created_at)
         .execute(&self.pool)
         .await
-        .map_err(|e| format!("Failed to create attachment: {}", e))?;
+        .map_err(AppError::db("Failed to create attachment"))?;
 
         Ok(())
     }
 
     /// Lists all attachments for a note.
-    pub async fn list_for_note(&self, note_id: &str) -> Result<Vec<Attachment>, String> {
+    pub async fn list_for_note(&self, note_id: &str) -> AppResult<Vec<Attachment>> {
         let rows = sqlx::query(
             "SELECT id, note_id, attachment_type, file_name, file_path, mime_type, size_bytes, created_at
              FROM attachments WHERE note_id = ?"
@@ -42,30 +48,73 @@ impl AttachmentStorage {
         .bind(note_id)
         .fetch_all(&self.pool)
         .await
-        .map_err(|e| format!("Failed to list attachments: {}", e))?;
-
-        let attachments = rows.iter().map(|row| Attachment {
-            id: row.get("id"),
-            note_id: row.get("note_id"),
-            attachment_type: row.get("attachment_type"),
-            file_name: row.get("file_name"),
-            file_path: row.get("file_path"),
-            mime_type: row.get("mime_type"),
-            size_bytes: row.get("size_bytes"),
-            created_at: row.get("created_at"),
-        }).collect();
+        .map_err(AppError::db("Failed to list attachments"))?;
+
+        let attachments = rows.iter().map(|row| attachment_from_row(row, &self.root)).collect();
 
         Ok(attachments)
     }
 
+    /// Every attachment record, of all notes.
+    pub async fn list_all(&self) -> AppResult<Vec<Attachment>> {
+        let rows = sqlx::query(
+            "SELECT id, note_id, attachment_type, file_name, file_path, mime_type, size_bytes, created_at
+             FROM attachments"
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::db("Failed to list attachments"))?;
+
+        Ok(rows.iter().map(|row| attachment_from_row(row, &self.root)).collect())
+    }
+
+    /// Single attachment record, `None` if missing.
+    pub async fn find(&self, id: &str) -> AppResult<Option<Attachment>> {
+        let row = sqlx::query(
+            "SELECT id, note_id, attachment_type, file_name, file_path, mime_type, size_bytes, created_at
+             FROM attachments WHERE id = ?"
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::db("Database error"))?;
+
+        Ok(row.as_ref().map(|row| attachment_from_row(row, &self.root)))
+    }
+
+    /// True if an attachment with this id exists (any note).
+    pub async fn exists(&self, id: &str) -> AppResult<bool> {
+        let row = sqlx::query("SELECT 1 FROM attachments WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::db("Database error"))?;
+
+        Ok(row.is_some())
+    }
+
     /// Deletes all attachments for a note (called before note delete).
-    pub async fn delete_for_note(&self, note_id: &str) -> Result<(), String> {
+    pub async fn delete_for_note(&self, note_id: &str) -> AppResult<()> {
         sqlx::query("DELETE FROM attachments WHERE note_id = ?")
             .bind(note_id)
             .execute(&self.pool)
             .await
-            .map_err(|e| format!("Failed to delete attachments: {}", e))?;
+            .map_err(AppError::db("Failed to delete attachments"))?;
 
         Ok(())
     }
 }
+
+/// Maps a `SELECT id, note_id, attachment_type, file_name, file_path, mime_type, size_bytes, created_at` row.
+fn attachment_from_row(row: &SqliteRow, root: &VaultRoot) -> Attachment {
+    Attachment {
+        id: row.get("id"),
+        note_id: row.get("note_id"),
+        attachment_type: row.get("attachment_type"),
+        file_name: row.get("file_name"),
+        file_path: root.resolve(row.get("file_path")),
+        mime_type: row.get("mime_type"),
+        size_bytes: row.get("size_bytes"),
+        created_at: row.get("created_at"),
+    }
+}
@@ -0,0 +1,52 @@
+use sqlx::{SqlitePool, Row};
+use crate::error::{AppError, AppResult};
+
+/// Remembers which external item (source + source id) produced which note,
+/// so re-running an import doesn't create duplicates.
+#[derive(Clone)]
+pub struct ImportSourceStorage {
+    pool: SqlitePool,
+}
+
+impl ImportSourceStorage {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Returns the note id previously created for this source item, if any.
+    pub async fn find(&self, source: &str, source_id: &str) -> AppResult<Option<String>> {
+        let row = sqlx::query(
+            "SELECT note_id FROM import_sources WHERE source = ? AND source_id = ?"
+        )
+        .bind(source)
+        .bind(source_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::db("Database error"))?;
+
+        Ok(row.map(|r| r.get("note_id")))
+    }
+
+    /// Records that `source_id` from `source` was imported as `note_id`.
+    pub async fn record(
+        &self,
+        source: &str,
+        source_id: &str,
+        note_id: &str,
+        imported_at: i64,
+    ) -> AppResult<()> {
+        sqlx::query(
+            "INSERT OR REPLACE INTO import_sources (source, source_id, note_id, imported_at)
+             VALUES (?, ?, ?, ?)"
+        )
+        .bind(source)
+        .bind(source_id)
+        .bind(note_id)
+        .bind(imported_at)
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::db("Failed to record import"))?;
+
+        Ok(())
+    }
+}
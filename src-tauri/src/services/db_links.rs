@@ -0,0 +1,127 @@
+use sqlx::{SqlitePool, Row};
+use crate::crypto::{is_sealed_text, Keyring};
+use crate::error::{AppError, AppResult};
+use crate::services::links::Link;
+
+/// Manages the links parsed out of note bodies only. Targets are stored
+/// as written (sealed in an encrypted vault) and resolved on read, so
+/// creating, renaming or trashing a target needs no update here.
+#[derive(Clone)]
+pub struct LinkStorage {
+    pool: SqlitePool,
+    keyring: Keyring,
+}
+
+impl LinkStorage {
+    pub fn new(pool: SqlitePool, keyring: Keyring) -> Self {
+        Self { pool, keyring }
+    }
+
+    /// Replaces all links of a note and marks it indexed.
+    pub async fn replace(&self, source_id: &str, links: &[Link]) -> AppResult<()> {
+        let mut tx = self.pool
+            .begin()
+            .await
+            .map_err(AppError::db("Failed to start transaction"))?;
+
+        sqlx::query("DELETE FROM note_links WHERE source_id = ?")
+            .bind(source_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::db("Failed to clear links"))?;
+
+        for (position, link) in links.iter().enumerate() {
+            let (target, by_id) = match link {
+                Link::Title(title) => (title, false),
+                Link::Id(id) => (id, true),
+            };
+            sqlx::query("INSERT INTO note_links (source_id, position, target, by_id) VALUES (?, ?, ?, ?)")
+                .bind(source_id)
+                .bind(position as i64)
+                .bind(self.keyring.seal_text(target)?)
+                .bind(by_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(AppError::db("Failed to add link"))?;
+        }
+
+        sqlx::query("UPDATE notes SET links_indexed = 1 WHERE id = ?")
+            .bind(source_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::db("Failed to update note"))?;
+
+        tx.commit()
+            .await
+            .map_err(AppError::db("Failed to save links"))
+    }
+
+    /// Links of a note, in order of appearance.
+    pub async fn list_for_note(&self, source_id: &str) -> AppResult<Vec<Link>> {
+        let rows = sqlx::query("SELECT target, by_id FROM note_links WHERE source_id = ? ORDER BY position")
+            .bind(source_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::db("Failed to list links"))?;
+
+        rows.iter().map(|row| self.link_from_row(row)).collect()
+    }
+
+    /// Every link as `(source_id, link)`.
+    pub async fn list_all(&self) -> AppResult<Vec<(String, Link)>> {
+        let rows = sqlx::query("SELECT source_id, target, by_id FROM note_links ORDER BY source_id, position")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::db("Failed to list links"))?;
+
+        rows.iter().map(|row| Ok((row.get("source_id"), self.link_from_row(row)?))).collect()
+    }
+
+    /// Seals every link target that isn't sealed yet (turning encryption on).
+    pub async fn seal_all(&self) -> AppResult<()> {
+        let mut tx = self.pool.begin().await.map_err(AppError::db("Failed to start transaction"))?;
+        let rows: Vec<(String, i64, String)> = sqlx::query_as("SELECT source_id, position, target FROM note_links")
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(AppError::db("Failed to read links"))?;
+
+        for (source_id, position, target) in rows {
+            if is_sealed_text(&target) {
+                continue;
+            }
+            sqlx::query("UPDATE note_links SET target = ? WHERE source_id = ? AND position = ?")
+                .bind(self.keyring.seal_text(&target)?)
+                .bind(&source_id)
+                .bind(position)
+                .execute(&mut *tx)
+                .await
+                .map_err(AppError::db("Failed to encrypt link"))?;
+        }
+
+        tx.commit().await.map_err(AppError::db("Failed to encrypt links"))
+    }
+
+    /// Removes the links of a deleted note.
+    pub async fn delete_for_note(&self, source_id: &str) -> AppResult<()> {
+        sqlx::query("DELETE FROM note_links WHERE source_id = ?")
+            .bind(source_id)
+            .execute(&self.pool)
+            .await
+            .map_err(AppError::db("Failed to delete links"))?;
+
+        Ok(())
+    }
+
+    /// Ids of notes written before links were indexed.
+    pub async fn unindexed(&self) -> AppResult<Vec<String>> {
+        sqlx::query_scalar("SELECT id FROM notes WHERE links_indexed = 0")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::db("Failed to list notes"))
+    }
+
+    fn link_from_row(&self, row: &sqlx::sqlite::SqliteRow) -> AppResult<Link> {
+        let target = self.keyring.open_text(row.get("target"))?;
+        Ok(if row.get::<bool, _>("by_id") { Link::Id(target) } else { Link::Title(target) })
+    }
+}
@@ -1,130 +1,522 @@
 use sqlx::{SqlitePool, Row};
-use crate::db::schema::Note;
+use sqlx::sqlite::SqliteRow;
+use crate::crypto::{is_sealed_text, Keyring, WrappedKey};
+use crate::db::schema::{Note, NoteFilter};
+use crate::error::{AppError, AppResult};
+use crate::services::vault_root::VaultRoot;
 
 /// Handles CRUD operations for notes table only.
+/// `file_path` is stored relative to `root` and returned absolute; in an
+/// encrypted vault `title` and `body` are stored sealed by `keyring`.
+/// The body of a password protected note is sealed with its own key; reads
+/// return it empty unless the note is unlocked this session.
+#[derive(Clone)]
 pub struct NoteStorage {
     pool: SqlitePool,
+    root: VaultRoot,
+    keyring: Keyring,
 }
 
 impl NoteStorage {
-    pub fn new(pool: SqlitePool) -> Self {
-        Self { pool }
+    pub fn new(pool: SqlitePool, root: VaultRoot, keyring: Keyring) -> Self {
+        Self { pool, root, keyring }
     }
 
-    /// Creates new note with file_path for filesystem ops.
-    pub async fn create(&self, note: Note) -> Result<Note, String> {
+    /// Creates new note with file_path for filesystem ops (version 1).
+    pub async fn create(&self, mut note: Note) -> AppResult<Note> {
+        note.version = 1;
         sqlx::query(
-            "INSERT INTO notes (id, title, body, created_at, updated_at, file_path) 
-             VALUES (?, ?, ?, ?, ?, ?)"
+            "INSERT INTO notes (id, title, body, created_at, updated_at, file_path, notebook, version, 
+                                is_pinned, is_favorite, is_archived) 
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
         )
         .bind(&note.id)
-        .bind(&note.title)
-        .bind(&note.body)
+        .bind(self.keyring.seal_text(&note.title)?)
+        .bind(self.keyring.seal_text(&note.body)?)
         .bind(note.created_at)
         .bind(note.updated_at)
-        .bind(&note.file_path)
+        .bind(self.root.to_stored(&note.file_path))
+        .bind(&note.notebook)
+        .bind(note.version)
+        .bind(note.pinned)
+        .bind(note.favorite)
+        .bind(note.archived)
         .execute(&self.pool)
         .await
-        .map_err(|e| format!("Failed to create note: {}", e))?;
+        .map_err(AppError::db("Failed to create note"))?;
 
         Ok(note)
     }
 
     /// Fetches single active note.
-    pub async fn get(&self, id: &str) -> Result<Note, String> {
+    pub async fn get(&self, id: &str) -> AppResult<Note> {
+        self.find(id).await?.ok_or_else(|| AppError::NotFound(format!("Note {}", id)))
+    }
+
+    /// Single active note, `None` if missing or deleted.
+    pub async fn find(&self, id: &str) -> AppResult<Option<Note>> {
         let row = sqlx::query(
-            "SELECT id, title, body, created_at, updated_at, file_path 
+            "SELECT id, title, body, created_at, updated_at, file_path, notebook, version, 
+                    is_pinned, is_favorite, is_archived, password_lock IS NOT NULL AS protected 
              FROM notes WHERE id = ? AND is_deleted = 0"
         )
         .bind(id)
         .fetch_optional(&self.pool)  // Use optional to distinguish not found
         .await
-        .map_err(|e| format!("Database error: {}", e))?;
+        .map_err(AppError::db("Database error"))?;
 
-        row.map(|r| Note {
-            id: r.get("id"),
-            title: r.get("title"),
-            body: r.get("body"),
-            created_at: r.get("created_at"),
-            updated_at: r.get("updated_at"),
-            file_path: r.get("file_path"),
-        }).ok_or_else(|| "Note not found".to_string())
+        row.as_ref().map(|row| self.revealed_from_row(row)).transpose()
     }
 
-    /// Lists all active notes, newest first.
-    pub async fn list(&self) -> Result<Vec<Note>, String> {
+    /// Like `find`, but a protected body stays sealed (for rewriting the
+    /// note without its key).
+    pub async fn find_stored(&self, id: &str) -> AppResult<Option<Note>> {
+        let row = sqlx::query(
+            "SELECT id, title, body, created_at, updated_at, file_path, notebook, version, 
+                    is_pinned, is_favorite, is_archived, password_lock IS NOT NULL AS protected 
+             FROM notes WHERE id = ? AND is_deleted = 0"
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::db("Database error"))?;
+
+        row.as_ref().map(|row| self.note_from_row(row)).transpose()
+    }
+
+    /// True if a note with this id exists, active or deleted.
+    pub async fn exists(&self, id: &str) -> AppResult<bool> {
+        let row = sqlx::query("SELECT 1 FROM notes WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::db("Database error"))?;
+
+        Ok(row.is_some())
+    }
+
+    /// `(id, file_path)` of every note, including soft-deleted ones.
+    pub async fn list_file_paths(&self) -> AppResult<Vec<(String, String)>> {
+        let rows = sqlx::query("SELECT id, file_path FROM notes")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::db("Failed to list notes"))?;
+
+        Ok(rows
+            .iter()
+            .map(|row| (row.get("id"), self.root.resolve(row.get("file_path"))))
+            .collect())
+    }
+
+    /// Lists the active notes matching `filter`, pinned ones first, then
+    /// newest first.
+    pub async fn list(&self, filter: &NoteFilter) -> AppResult<Vec<Note>> {
         let rows = sqlx::query(
-            "SELECT id, title, body, created_at, updated_at, file_path 
-             FROM notes WHERE is_deleted = 0 
-             ORDER BY updated_at DESC"
+            "SELECT id, title, body, created_at, updated_at, file_path, notebook, version, 
+                    is_pinned, is_favorite, is_archived, password_lock IS NOT NULL AS protected 
+             FROM notes 
+             WHERE is_deleted = 0 AND is_archived = ? AND (? = 0 OR is_favorite = 1) AND (? = 0 OR is_pinned = 1) 
+             ORDER BY is_pinned DESC, updated_at DESC"
         )
+        .bind(filter.archived)
+        .bind(filter.favorites)
+        .bind(filter.pinned)
         .fetch_all(&self.pool)
         .await
-        .map_err(|e| format!("Failed to list notes: {}", e))?;
+        .map_err(AppError::db("Failed to list notes"))?;
 
-        let notes = rows.iter().map(|row| Note {
-            id: row.get("id"),
-            title: row.get("title"),
-            body: row.get("body"),
-            created_at: row.get("created_at"),
-            updated_at: row.get("updated_at"),
-            file_path: row.get("file_path"),
-        }).collect();
+        rows.iter().map(|row| self.revealed_from_row(row)).collect()
+    }
 
-        Ok(notes)
+    /// Every note row, including deleted and archived ones. Protected
+    /// bodies stay sealed.
+    pub async fn list_all(&self) -> AppResult<Vec<Note>> {
+        let rows = sqlx::query(
+            "SELECT id, title, body, created_at, updated_at, file_path, notebook, version, 
+                    is_pinned, is_favorite, is_archived, password_lock IS NOT NULL AS protected FROM notes"
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::db("Failed to list notes"))?;
+
+        rows.iter().map(|row| self.note_from_row(row)).collect()
     }
 
-    /// Updates title/body/updated_at (preserves file_path).
-    pub async fn update(&self, note: Note) -> Result<Note, String> {
-        sqlx::query(
-            "UPDATE notes SET title = ?, body = ?, updated_at = ? WHERE id = ?"
+    /// `(id, title)` of every active note (archived ones too), newest first.
+    pub async fn titles(&self) -> AppResult<Vec<(String, String)>> {
+        let rows: Vec<(String, String)> = sqlx::query_as(
+            "SELECT id, title FROM notes WHERE is_deleted = 0 ORDER BY updated_at DESC"
         )
-        .bind(&note.title)
-        .bind(&note.body)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::db("Failed to list notes"))?;
+
+        rows.into_iter().map(|(id, title)| Ok((id, self.keyring.open_text(title)?))).collect()
+    }
+
+    /// Lists soft-deleted notes (the trash), newest first.
+    pub async fn list_deleted(&self) -> AppResult<Vec<Note>> {
+        let rows = sqlx::query(
+            "SELECT id, title, body, created_at, updated_at, file_path, notebook, version, 
+                    is_pinned, is_favorite, is_archived, password_lock IS NOT NULL AS protected 
+             FROM notes WHERE is_deleted = 1 
+             ORDER BY updated_at DESC"
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::db("Failed to list deleted notes"))?;
+
+        rows.iter().map(|row| self.revealed_from_row(row)).collect()
+    }
+
+    /// Updates title/body/updated_at (preserves file_path) and bumps `version`.
+    /// With `expected_version`, only writes if the stored version still
+    /// matches; `None` means no row was written (missing note or stale version).
+    /// A protected note must be unlocked (`NoteLocked` otherwise).
+    pub async fn update(&self, mut note: Note, expected_version: Option<i64>) -> AppResult<Option<Note>> {
+        if self.is_locked(&note.id).await? {
+            return Err(AppError::NoteLocked(note.id));
+        }
+        let body = self.keyring.seal_note_text(&note.id, &note.body)?;
+        let row: Option<(i64,)> = sqlx::query_as(
+            "UPDATE notes SET title = ?, body = ?, updated_at = ?, notebook = ?, version = version + 1 
+             WHERE id = ? AND (? IS NULL OR version = ?) RETURNING version"
+        )
+        .bind(self.keyring.seal_text(&note.title)?)
+        .bind(self.keyring.seal_text(&body)?)
         .bind(note.updated_at)
+        .bind(&note.notebook)
         .bind(&note.id)
-        .execute(&self.pool)
+        .bind(expected_version)
+        .bind(expected_version)
+        .fetch_optional(&self.pool)
         .await
-        .map_err(|e| format!("Failed to update note: {}", e))?;
+        .map_err(AppError::db("Failed to update note"))?;
 
-        Ok(note)
+        Ok(row.map(|(version,)| {
+            note.version = version;
+            note
+        }))
+    }
+
+    /// Bumps `version` for changes stored outside the row (tags, attachments).
+    pub async fn bump_version(&self, id: &str) -> AppResult<i64> {
+        let row: Option<(i64,)> = sqlx::query_as(
+            "UPDATE notes SET version = version + 1 WHERE id = ? RETURNING version"
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::db("Failed to update note"))?;
+
+        row.map(|(version,)| version).ok_or_else(|| AppError::NotFound(format!("Note {}", id)))
     }
 
     /// Hard deletes note (after filesystem ops).
-    pub async fn delete(&self, id: &str) -> Result<(), String> {
-        sqlx::query("DELETE FROM notes WHERE id = ?")
+    /// Returns the deleted row's version, `None` if there was no such note.
+    pub async fn delete(&self, id: &str) -> AppResult<Option<i64>> {
+        let row: Option<(i64,)> = sqlx::query_as("DELETE FROM notes WHERE id = ? RETURNING version")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::db("Failed to delete note"))?;
+
+        Ok(row.map(|(version,)| version))
+    }
+
+    /// Finds an active note with exactly this title and body (import dedup).
+    pub async fn find_by_content(&self, title: &str, body: &str) -> AppResult<Option<Note>> {
+        if self.keyring.is_encrypted() {
+            // Sealing uses a random nonce, so equal text never compares equal in SQL
+            let rows = sqlx::query(
+                "SELECT id, title, body, created_at, updated_at, file_path, notebook, version, 
+                    is_pinned, is_favorite, is_archived, password_lock IS NOT NULL AS protected 
+                 FROM notes WHERE is_deleted = 0"
+            )
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::db("Database error"))?;
+
+            for row in &rows {
+                let note = self.note_from_row(row)?;
+                if note.title == title && note.body == body {
+                    return Ok(Some(note));
+                }
+            }
+            return Ok(None);
+        }
+
+        let row = sqlx::query(
+            "SELECT id, title, body, created_at, updated_at, file_path, notebook, version, 
+                    is_pinned, is_favorite, is_archived, password_lock IS NOT NULL AS protected 
+             FROM notes WHERE is_deleted = 0 AND title = ? AND body = ? 
+             LIMIT 1"
+        )
+        .bind(title)
+        .bind(body)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::db("Database error"))?;
+
+        row.as_ref().map(|row| self.note_from_row(row)).transpose()
+    }
+
+    /// Seals the title and body of every note (trashed ones too) that isn't
+    /// sealed yet, in one transaction. Returns the number of notes changed.
+    pub async fn seal_all(&self) -> AppResult<usize> {
+        let mut tx = self.pool.begin().await.map_err(AppError::db("Failed to start transaction"))?;
+        let rows: Vec<(String, String, String)> = sqlx::query_as("SELECT id, title, body FROM notes")
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(AppError::db("Failed to read notes"))?;
+
+        let mut sealed = 0;
+        for (id, title, body) in rows {
+            if is_sealed_text(&title) && is_sealed_text(&body) {
+                continue;
+            }
+            let seal = |text: String| if is_sealed_text(&text) { Ok(text) } else { self.keyring.seal_text(&text) };
+            sqlx::query("UPDATE notes SET title = ?, body = ? WHERE id = ?")
+                .bind(seal(title)?)
+                .bind(seal(body)?)
+                .bind(&id)
+                .execute(&mut *tx)
+                .await
+                .map_err(AppError::db("Failed to encrypt note"))?;
+            sealed += 1;
+        }
+
+        tx.commit().await.map_err(AppError::db("Failed to encrypt notes"))?;
+        Ok(sealed)
+    }
+
+    /// Wrapped key of a password protected note, `None` for other notes.
+    pub async fn password_lock(&self, id: &str) -> AppResult<Option<WrappedKey>> {
+        let row: Option<(Option<String>,)> = sqlx::query_as("SELECT password_lock FROM notes WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::db("Database error"))?;
+
+        let Some((lock,)) = row else {
+            return Err(AppError::NotFound(format!("Note {}", id)));
+        };
+        lock.map(|lock| serde_json::from_str(&lock).map_err(AppError::internal("Invalid note password lock")))
+            .transpose()
+    }
+
+    /// Protected and not unlocked this session.
+    pub async fn is_locked(&self, id: &str) -> AppResult<bool> {
+        if self.keyring.note_key(id).is_some() {
+            return Ok(false);
+        }
+        let row = sqlx::query("SELECT 1 FROM notes WHERE id = ? AND password_lock IS NOT NULL")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::db("Database error"))?;
+        Ok(row.is_some())
+    }
+
+    /// Ids of all password protected notes.
+    async fn protected_ids(&self) -> AppResult<Vec<String>> {
+        sqlx::query_scalar("SELECT id FROM notes WHERE password_lock IS NOT NULL")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::db("Database error"))
+    }
+
+    /// Stores `body` as is (sealed with the note key or not) with the
+    /// note's password lock, or without one. Returns the new version.
+    pub async fn set_password_lock(&self, id: &str, body: &str, lock: Option<&WrappedKey>) -> AppResult<i64> {
+        let lock = lock
+            .map(|lock| serde_json::to_string(lock).map_err(AppError::internal("Failed to encode note password lock")))
+            .transpose()?;
+        let row: Option<(i64,)> = sqlx::query_as(
+            "UPDATE notes SET body = ?, password_lock = ?, version = version + 1 WHERE id = ? RETURNING version"
+        )
+        .bind(self.keyring.seal_text(body)?)
+        .bind(lock)
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::db("Failed to update note"))?;
+
+        row.map(|(version,)| version).ok_or_else(|| AppError::NotFound(format!("Note {}", id)))
+    }
+
+    /// Sets the archived flag (imported Keep notes can arrive archived).
+    /// Archived notes stay readable by id but are hidden from list and search.
+    /// Returns the new version.
+    pub async fn set_archived(&self, id: &str, archived: bool) -> AppResult<i64> {
+        self.set_flag(id, "is_archived", archived).await
+    }
+
+    /// Sets the pinned flag. Returns the new version.
+    pub async fn set_pinned(&self, id: &str, pinned: bool) -> AppResult<i64> {
+        self.set_flag(id, "is_pinned", pinned).await
+    }
+
+    /// Sets the favorite flag. Returns the new version.
+    pub async fn set_favorite(&self, id: &str, favorite: bool) -> AppResult<i64> {
+        self.set_flag(id, "is_favorite", favorite).await
+    }
+
+    /// Current archived flag.
+    pub async fn is_archived(&self, id: &str) -> AppResult<bool> {
+        let row = sqlx::query("SELECT is_archived FROM notes WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::db("Database error"))?;
+
+        row.map(|row| row.get::<bool, _>("is_archived"))
+            .ok_or_else(|| AppError::NotFound(format!("Note {}", id)))
+    }
+
+    /// Id of the active daily note for `date` (`YYYY-MM-DD`), the newest
+    /// if a restored one makes two.
+    pub async fn daily_note_id(&self, date: &str) -> AppResult<Option<String>> {
+        let row: Option<(String,)> = sqlx::query_as(
+            "SELECT id FROM notes WHERE is_deleted = 0 AND daily_date = ? ORDER BY created_at DESC LIMIT 1"
+        )
+        .bind(date)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::db("Database error"))?;
+
+        Ok(row.map(|(id,)| id))
+    }
+
+    /// Makes `id` the daily note for `date`.
+    pub async fn set_daily_date(&self, id: &str, date: &str) -> AppResult<()> {
+        let result = sqlx::query("UPDATE notes SET daily_date = ? WHERE id = ?")
+            .bind(date)
             .bind(id)
             .execute(&self.pool)
             .await
-            .map_err(|e| format!("Failed to delete note: {}", e))?;
+            .map_err(AppError::db("Failed to update note"))?;
 
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Note {}", id)));
+        }
         Ok(())
     }
 
-    /// Full-text search on active notes.
-    pub async fn search(&self, query: &str) -> Result<Vec<Note>, String> {
+    /// Closest dates before and after `date` that have an active daily note.
+    pub async fn daily_neighbors(&self, date: &str) -> AppResult<(Option<String>, Option<String>)> {
+        sqlx::query_as(
+            "SELECT (SELECT MAX(daily_date) FROM notes WHERE is_deleted = 0 AND daily_date < ?), 
+                    (SELECT MIN(daily_date) FROM notes WHERE is_deleted = 0 AND daily_date > ?)"
+        )
+        .bind(date)
+        .bind(date)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::db("Database error"))
+    }
+
+    /// Flips the soft-delete flag (trash / restore; imported notes can
+    /// arrive trashed). Only touches a note currently in the other state.
+    /// Returns the new version.
+    pub async fn set_deleted(&self, id: &str, deleted: bool) -> AppResult<i64> {
+        let row: Option<(i64,)> = sqlx::query_as(
+            "UPDATE notes SET is_deleted = ?, version = version + 1 
+             WHERE id = ? AND is_deleted != ? RETURNING version"
+        )
+        .bind(deleted)
+        .bind(id)
+        .bind(deleted)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::db("Failed to update note"))?;
+
+        row.map(|(version,)| version).ok_or_else(|| AppError::NotFound(format!("Note {}", id)))
+    }
+
+    /// Full-text search on the notes `list` returns for `filter`. Only the
+    /// title of a protected note is matched, even while it's unlocked.
+    pub async fn search(&self, query: &str, filter: &NoteFilter) -> AppResult<Vec<Note>> {
+        if self.keyring.is_encrypted() {
+            // Sealed columns can't be matched in SQL; search the opened notes
+            // in memory, so no plaintext index ever reaches the disk
+            let query = query.to_ascii_lowercase();
+            let protected = self.protected_ids().await?;
+            let mut notes = self.list(filter).await?;
+            notes.retain(|note| {
+                note.title.to_ascii_lowercase().contains(&query)
+                    || (!protected.contains(&note.id) && note.body.to_ascii_lowercase().contains(&query))
+            });
+            return Ok(notes);
+        }
+
         let pattern = format!("%{}%", query);
         let rows = sqlx::query(
-            "SELECT id, title, body, created_at, updated_at, file_path 
+            "SELECT id, title, body, created_at, updated_at, file_path, notebook, version, 
+                    is_pinned, is_favorite, is_archived, password_lock IS NOT NULL AS protected 
              FROM notes 
-             WHERE is_deleted = 0 AND (title LIKE ? OR body LIKE ?)
-             ORDER BY updated_at DESC"
+             WHERE is_deleted = 0 AND is_archived = ? AND (? = 0 OR is_favorite = 1) AND (? = 0 OR is_pinned = 1) 
+               AND (title LIKE ? OR (body LIKE ? AND password_lock IS NULL))
+             ORDER BY is_pinned DESC, updated_at DESC"
         )
+        .bind(filter.archived)
+        .bind(filter.favorites)
+        .bind(filter.pinned)
         .bind(&pattern)
         .bind(&pattern)
         .fetch_all(&self.pool)
         .await
-        .map_err(|e| format!("Search failed: {}", e))?;
+        .map_err(AppError::db("Search failed"))?;
+
+        rows.iter().map(|row| self.revealed_from_row(row)).collect()
+    }
+}
 
-        let notes = rows.iter().map(|row| Note {
+impl NoteStorage {
+    /// Maps a `SELECT id, title, body, created_at, updated_at, file_path, notebook, version,
+    /// is_pinned, is_favorite, is_archived, password_lock IS NOT NULL AS protected` row.
+    fn note_from_row(&self, row: &SqliteRow) -> AppResult<Note> {
+        Ok(Note {
             id: row.get("id"),
-            title: row.get("title"),
-            body: row.get("body"),
+            title: self.keyring.open_text(row.get("title"))?,
+            body: self.keyring.open_text(row.get("body"))?,
             created_at: row.get("created_at"),
             updated_at: row.get("updated_at"),
-            file_path: row.get("file_path"),
-        }).collect();
+            file_path: self.root.resolve(row.get("file_path")),
+            notebook: row.get("notebook"),
+            version: row.get("version"),
+            pinned: row.get("is_pinned"),
+            favorite: row.get("is_favorite"),
+            archived: row.get("is_archived"),
+        })
+    }
+
+    /// Sets a flag column (`is_pinned`, ...) and bumps `version`.
+    async fn set_flag(&self, id: &str, column: &str, value: bool) -> AppResult<i64> {
+        let row: Option<(i64,)> = sqlx::query_as(&format!(
+            "UPDATE notes SET {} = ?, version = version + 1 WHERE id = ? RETURNING version",
+            column
+        ))
+        .bind(value)
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::db("Failed to update note"))?;
+
+        row.map(|(version,)| version).ok_or_else(|| AppError::NotFound(format!("Note {}", id)))
+    }
 
-        Ok(notes)
+    /// Like `note_from_row`, with a protected body opened by the note's
+    /// session key, or emptied. Protection comes from the row, not from the
+    /// body, which may look sealed in a plain note.
+    fn revealed_from_row(&self, row: &SqliteRow) -> AppResult<Note> {
+        let mut note = self.note_from_row(row)?;
+        if row.get::<bool, _>("protected") {
+            note.body = match self.keyring.note_key(&note.id) {
+                Some(key) => key.open_note_text(&note.body)?,
+                None => String::new(),
+            };
+        }
+        Ok(note)
     }
 }
@@ -1,73 +1,580 @@
 use sqlx::SqlitePool;
-use std::path::PathBuf;
-use crate::services::{NoteStorage, AttachmentStorage};
-use crate::db::schema::{Note, NoteWithAttachments, Attachment};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use crate::crypto::{Keyring, WrappedKey};
+use crate::services::{NoteStorage, AttachmentStorage, TagStorage, ImportSourceStorage, LinkStorage};
+use crate::services::events::{AppEvent, EventBus};
+use crate::services::links::{self, BrokenLink, OutgoingLink};
+use crate::services::repository::WriteLock;
+use crate::services::vault_root::{self, VaultRoot};
+use crate::db::schema::{Note, NoteFilter, NoteWithAttachments, Attachment};
+use crate::error::{AppError, AppResult};
+
+/// New location of a note's file, plus its body if that changed too.
+#[derive(Debug, Clone)]
+pub struct RelocatedNote {
+    pub id: String,
+    pub file_path: String,
+    pub body: Option<String>,
+}
 
 /// Facade combining note + attachment storage.
 /// Used by Tauri commands. Delegates to specialized storage modules.
+/// Every mutation publishes an [`AppEvent`] on [`DbStorage::events`].
+///
+/// Note and attachment paths are stored relative to the notes folder (see
+/// [`VaultRoot`]); callers always get and pass absolute paths.
+///
+/// In an encrypted vault (a key file in the data dir) note titles and
+/// bodies are sealed by [`DbStorage::keyring`]; reading or writing them
+/// fails with [`AppError::Locked`] until it is unlocked.
+///
+/// Clones share the pool, so reads run in parallel; single statements need
+/// no locking (SQLite serializes them). Writes spanning several steps (DB
+/// row + note file) take [`DbStorage::lock_writes`].
+#[derive(Clone)]
 pub struct DbStorage {
+    pool: SqlitePool,
     notes: NoteStorage,
     attachments: AttachmentStorage,
+    tags: TagStorage,
+    imports: ImportSourceStorage,
+    links: LinkStorage,
     app_data_dir: PathBuf,  // For filesystem cleanup
+    root: VaultRoot,
+    keyring: Keyring,
+    events: EventBus,
+    writes: Arc<Mutex<()>>,
 }
 
 impl DbStorage {
-    /// Creates complete storage with note/attachment subsystems.
+    /// Creates complete storage with note/attachment subsystems, rooted at
+    /// the default notes folder (`<app data>/notes`) until
+    /// [`DbStorage::set_notes_folder`] says otherwise.
     pub fn new(pool: SqlitePool, app_data_dir: PathBuf) -> Self {
-        let notes = NoteStorage::new(pool.clone());
-        let attachments = AttachmentStorage::new(pool.clone());
+        Self::with_events(pool, app_data_dir, EventBus::new())
+    }
+
+    /// Like `new`, publishing on an existing bus (every vault the app
+    /// opens shares the one forwarded to the webview).
+    pub fn with_events(pool: SqlitePool, app_data_dir: PathBuf, events: EventBus) -> Self {
+        let root = VaultRoot::new(app_data_dir.join("notes"));
+        let keyring = Keyring::for_vault(&app_data_dir);
+        let notes = NoteStorage::new(pool.clone(), root.clone(), keyring.clone());
+        let attachments = AttachmentStorage::new(pool.clone(), root.clone());
+        let tags = TagStorage::new(pool.clone());
+        let imports = ImportSourceStorage::new(pool.clone());
+        let links = LinkStorage::new(pool.clone(), keyring.clone());
         
         Self {
+            pool,
             notes,
             attachments,
+            tags,
+            imports,
+            links,
             app_data_dir,
+            root,
+            keyring,
+            events,
+            writes: Arc::new(Mutex::new(())),
         }
     }
 
+    /// Resolves stored paths against `folder` from now on (all clones).
+    pub fn set_notes_folder(&self, folder: impl Into<PathBuf>) {
+        self.root.set(folder);
+    }
+
+    /// Rewrites absolute paths under the notes folder as relative ones
+    /// (rows written before paths were stored relative). Paths outside the
+    /// folder are left absolute. Returns the number of rows changed.
+    pub async fn store_paths_relative(&self) -> AppResult<usize> {
+        let _write = self.events.begin_write();
+        let root = self.root.get();
+        let mut tx = self.pool.begin().await.map_err(AppError::db("Failed to start transaction"))?;
+
+        let mut changed = 0;
+        for table in ["notes", "attachments"] {
+            let rows: Vec<(String, String)> = sqlx::query_as(&format!("SELECT id, file_path FROM {}", table))
+                .fetch_all(&mut *tx)
+                .await
+                .map_err(AppError::db("Failed to read file paths"))?;
+
+            for (id, file_path) in rows {
+                let stored = vault_root::to_stored(&root, &file_path);
+                if stored == file_path {
+                    continue;
+                }
+                sqlx::query(&format!("UPDATE {} SET file_path = ? WHERE id = ?", table))
+                    .bind(&stored)
+                    .bind(&id)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(AppError::db("Failed to update file path"))?;
+                changed += 1;
+            }
+        }
+
+        tx.commit().await.map_err(AppError::db("Failed to update file paths"))?;
+        Ok(changed)
+    }
+
+    /// Encryption state of this vault, shared by all clones.
+    pub fn keyring(&self) -> &Keyring {
+        &self.keyring
+    }
+
+    /// Seals every note title, body and link not sealed yet (turning
+    /// encryption on), then rebuilds the DB so no plaintext lingers in free pages or
+    /// the WAL. Returns the number of notes sealed.
+    pub async fn seal_notes(&self) -> AppResult<usize> {
+        let _write = self.events.begin_write();
+        let sealed = self.notes.seal_all().await?;
+        self.links.seal_all().await?;
+
+        sqlx::query("VACUUM")
+            .execute(&self.pool)
+            .await
+            .map_err(AppError::db("Failed to compact database"))?;
+        sqlx::query("PRAGMA wal_checkpoint(TRUNCATE)")
+            .execute(&self.pool)
+            .await
+            .map_err(AppError::db("Failed to checkpoint database"))?;
+        Ok(sealed)
+    }
+
+    /// Serializes multi-step writes (DB + files) across all clones, so e.g.
+    /// two autosaves of one note can't leave the file and row out of order.
+    /// Reads never take it. Also counts as a write in flight on [`EventBus`].
+    pub async fn lock_writes(&self) -> WriteLock<'_> {
+        let writes = self.writes.lock().await;
+        WriteLock::new(writes, self.events.begin_write())
+    }
+
+    /// Change events of this storage (subscribe, or publish non-DB events).
+    pub fn events(&self) -> &EventBus {
+        &self.events
+    }
+
     /// Full note + all its attachments (used by get_note command).
-    pub async fn get_note(&self, id: &str) -> Result<NoteWithAttachments, String> {
+    pub async fn get_note(&self, id: &str) -> AppResult<NoteWithAttachments> {
         let note = self.notes.get(id).await?;
         let attachments = self.attachments.list_for_note(id).await?;
-        Ok(NoteWithAttachments { note, attachments })
+        let locked = self.notes.is_locked(id).await?;
+        Ok(NoteWithAttachments { note, attachments, locked })
+    }
+
+    /// Like `get_note`, but `None` when the note is missing or deleted.
+    pub async fn find_note(&self, id: &str) -> AppResult<Option<NoteWithAttachments>> {
+        let Some(note) = self.notes.find(id).await? else {
+            return Ok(None);
+        };
+        let attachments = self.attachments.list_for_note(id).await?;
+        let locked = self.notes.is_locked(id).await?;
+        Ok(Some(NoteWithAttachments { note, attachments, locked }))
+    }
+
+    /// Active note with a protected body still sealed with the note key.
+    pub async fn find_stored_note(&self, id: &str) -> AppResult<Option<Note>> {
+        self.notes.find_stored(id).await
+    }
+
+    /// Password protected and not unlocked this session.
+    pub async fn is_note_locked(&self, id: &str) -> AppResult<bool> {
+        self.notes.is_locked(id).await
+    }
+
+    /// The note's wrapped key, `None` if it isn't password protected.
+    pub async fn note_password_lock(&self, id: &str) -> AppResult<Option<WrappedKey>> {
+        self.notes.password_lock(id).await
+    }
+
+    /// Protects the note (`lock` with `body` sealed by its key) or removes
+    /// the protection (no `lock`, plain `body`).
+    pub async fn set_note_password_lock(&self, id: &str, body: &str, lock: Option<&WrappedKey>) -> AppResult<()> {
+        let _write = self.events.begin_write();
+        let version = self.notes.set_password_lock(id, body, lock).await?;
+        self.index_links(id, body).await?;
+        self.events.publish(AppEvent::NoteUpdated { id: id.to_string(), version });
+        Ok(())
+    }
+
+    /// True if the id is taken (active or deleted).
+    pub async fn note_exists(&self, id: &str) -> AppResult<bool> {
+        self.notes.exists(id).await
     }
 
     /// Creates note (file_path populated by commands layer).
-    pub async fn create_note(&self, note: Note) -> Result<Note, String> {
-        self.notes.create(note).await
+    pub async fn create_note(&self, note: Note) -> AppResult<Note> {
+        let _write = self.events.begin_write();
+        let note = self.notes.create(note).await?;
+        self.index_links(&note.id, &note.body).await?;
+        self.events.publish(AppEvent::NoteCreated { id: note.id.clone(), version: note.version });
+        Ok(note)
     }
 
-    /// Lists all active notes.
-    pub async fn list_notes(&self) -> Result<Vec<Note>, String> {
-        self.notes.list().await
+    /// Lists the active notes matching `filter`, pinned first.
+    pub async fn list_notes(&self, filter: &NoteFilter) -> AppResult<Vec<Note>> {
+        self.notes.list(filter).await
     }
 
-    /// Updates note (preserves file_path).
-    pub async fn update_note(&self, note: Note) -> Result<Note, String> {
-        self.notes.update(note).await
+    /// Updates note (preserves file_path). With `expected_version`, a note
+    /// changed in the meantime is not overwritten ([`AppError::Conflict`]).
+    pub async fn update_note(&self, note: Note, expected_version: Option<i64>) -> AppResult<Note> {
+        let _write = self.events.begin_write();
+        let id = note.id.clone();
+        let Some(note) = self.notes.update(note, expected_version).await? else {
+            // No row written: missing note (NotFound from `get`) or stale version
+            let current = self.notes.get(&id).await?;
+            return Err(match expected_version {
+                Some(expected_version) => AppError::Conflict { expected_version, current: Box::new(current) },
+                None => AppError::NotFound(format!("Note {}", id)),
+            });
+        };
+        self.index_links(&id, &note.body).await?;
+        self.events.publish(AppEvent::NoteUpdated { id, version: note.version });
+        Ok(note)
     }
 
     /// Hard delete: attachments first, then note + filesystem cleanup.
-    pub async fn delete_note(&self, id: &str) -> Result<(), String> {
-        // Delete attachments DB records
+    pub async fn delete_note(&self, id: &str) -> AppResult<()> {
+        let _write = self.events.begin_write();
+
+        // Delete attachments and links DB records
         self.attachments.delete_for_note(id).await?;
+        self.links.delete_for_note(id).await?;
         
         // Delete note DB record
-        self.notes.delete(id).await?;
+        if let Some(version) = self.notes.delete(id).await? {
+            self.events.publish(AppEvent::NoteDeleted {
+                id: id.to_string(),
+                version: version + 1,
+                permanent: true,
+            });
+        }
         
-        // Cleanup filesystem (attachments dir + note file handled by DeleteService)
+        // Cleanup filesystem (attachments dir + note file handled by NoteService)
         let attachments_dir = self.app_data_dir.join("attachments").join(id);
         let _ = std::fs::remove_dir_all(attachments_dir);
         
         Ok(())
     }
 
-    /// Full-text search on notes.
-    pub async fn search_notes(&self, query: &str) -> Result<Vec<Note>, String> {
-        self.notes.search(query).await
+    /// Full-text search on the notes matching `filter`.
+    pub async fn search_notes(&self, query: &str, filter: &NoteFilter) -> AppResult<Vec<Note>> {
+        self.notes.search(query, filter).await
     }
 
     /// Creates attachment record (after file saved to disk).
-    pub async fn create_attachment(&self, attachment: Attachment) -> Result<(), String> {
-        self.attachments.create(attachment).await
+    pub async fn create_attachment(&self, attachment: Attachment) -> AppResult<()> {
+        let _write = self.events.begin_write();
+        self.attachments.create(attachment.clone()).await?;
+        let version = self.notes.bump_version(&attachment.note_id).await?;
+        self.events.publish(AppEvent::AttachmentAdded {
+            note_id: attachment.note_id,
+            attachment_id: attachment.id,
+            version,
+        });
+        Ok(())
+    }
+
+    /// Single attachment record, `None` if missing.
+    pub async fn find_attachment(&self, id: &str) -> AppResult<Option<Attachment>> {
+        self.attachments.find(id).await
+    }
+
+    /// True if the attachment id is taken.
+    pub async fn attachment_exists(&self, id: &str) -> AppResult<bool> {
+        self.attachments.exists(id).await
+    }
+
+    /// Lists attachments of a note.
+    pub async fn list_attachments(&self, note_id: &str) -> AppResult<Vec<Attachment>> {
+        self.attachments.list_for_note(note_id).await
+    }
+
+    /// Every note (deleted and archived too) and every attachment, for jobs
+    /// that rewrite the whole store (moving the notes folder).
+    pub async fn list_everything(&self) -> AppResult<(Vec<Note>, Vec<Attachment>)> {
+        Ok((self.notes.list_all().await?, self.attachments.list_all().await?))
+    }
+
+    /// Points notes and attachments at new files under `folder` in one
+    /// transaction and makes `folder` the notes folder; notes with a new
+    /// `body` get a new version. Publishes `ExternalChange`, as every open
+    /// note may be affected.
+    pub async fn relocate(
+        &self,
+        folder: &Path,
+        notes: &[RelocatedNote],
+        attachments: &[(String, String)],
+    ) -> AppResult<()> {
+        let _write = self.events.begin_write();
+        let mut tx = self.pool.begin().await.map_err(AppError::db("Failed to start transaction"))?;
+
+        for note in notes {
+            let body = note.body.as_deref().map(|body| self.keyring.seal_text(body)).transpose()?;
+            sqlx::query(
+                "UPDATE notes SET file_path = ?, body = COALESCE(?, body), version = version + (? IS NOT NULL)
+                 WHERE id = ?"
+            )
+            .bind(vault_root::to_stored(folder, &note.file_path))
+            .bind(&body)
+            .bind(&body)
+            .bind(&note.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::db("Failed to move note"))?;
+        }
+        for (id, file_path) in attachments {
+            sqlx::query("UPDATE attachments SET file_path = ? WHERE id = ?")
+                .bind(vault_root::to_stored(folder, file_path))
+                .bind(id)
+                .execute(&mut *tx)
+                .await
+                .map_err(AppError::db("Failed to move attachment"))?;
+        }
+
+        tx.commit().await.map_err(AppError::db("Failed to move notes"))?;
+        self.root.set(folder);
+        self.events.publish(AppEvent::ExternalChange);
+        Ok(())
+    }
+
+    /// `(id, file_path)` of every note, including soft-deleted ones.
+    pub async fn list_note_files(&self) -> AppResult<Vec<(String, String)>> {
+        self.notes.list_file_paths().await
+    }
+
+    /// Writes a consistent copy of the DB to `dest` (safe while other
+    /// connections are writing).
+    pub async fn backup_database(&self, dest: &Path) -> AppResult<()> {
+        sqlx::query("VACUUM INTO ?")
+            .bind(dest.to_string_lossy().to_string())
+            .execute(&self.pool)
+            .await
+            .map_err(AppError::db("Failed to back up database"))?;
+
+        Ok(())
+    }
+
+    /// Runs `PRAGMA integrity_check`; returns `["ok"]` when healthy.
+    pub async fn integrity_check(&self) -> AppResult<Vec<String>> {
+        let rows: Vec<(String,)> = sqlx::query_as("PRAGMA integrity_check")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::db("Integrity check failed"))?;
+
+        Ok(rows.into_iter().map(|(line,)| line).collect())
+    }
+
+    /// Replaces the tags of a note.
+    pub async fn set_note_tags(&self, note_id: &str, tags: &[String]) -> AppResult<()> {
+        let _write = self.events.begin_write();
+        self.tags.set_for_note(note_id, tags).await?;
+        let version = self.notes.bump_version(note_id).await?;
+        self.events.publish(AppEvent::NoteUpdated { id: note_id.to_string(), version });
+        Ok(())
+    }
+
+    /// Lists the tags of a note.
+    pub async fn list_note_tags(&self, note_id: &str) -> AppResult<Vec<String>> {
+        self.tags.list_for_note(note_id).await
+    }
+
+    /// Every `(note_id, tag)` pair, trashed notes included.
+    pub async fn list_all_tags(&self) -> AppResult<Vec<(String, String)>> {
+        self.tags.list_all().await
+    }
+
+    /// The active daily note for `date` (`YYYY-MM-DD`), if there is one.
+    pub async fn find_daily_note(&self, date: &str) -> AppResult<Option<NoteWithAttachments>> {
+        match self.notes.daily_note_id(date).await? {
+            Some(id) => self.find_note(&id).await,
+            None => Ok(None),
+        }
+    }
+
+    /// Makes a note the daily note for `date`.
+    pub async fn set_daily_date(&self, id: &str, date: &str) -> AppResult<()> {
+        let _write = self.events.begin_write();
+        self.notes.set_daily_date(id, date).await
+    }
+
+    /// Closest dates before and after `date` with a daily note.
+    pub async fn daily_note_neighbors(&self, date: &str) -> AppResult<(Option<String>, Option<String>)> {
+        self.notes.daily_neighbors(date).await
+    }
+
+    /// Marks a note archived / unarchived.
+    pub async fn set_note_archived(&self, id: &str, archived: bool) -> AppResult<()> {
+        let _write = self.events.begin_write();
+        let version = self.notes.set_archived(id, archived).await?;
+        self.events.publish(AppEvent::NoteUpdated { id: id.to_string(), version });
+        Ok(())
+    }
+
+    /// Pins a note to the top of the list, or unpins it.
+    pub async fn set_note_pinned(&self, id: &str, pinned: bool) -> AppResult<()> {
+        let _write = self.events.begin_write();
+        let version = self.notes.set_pinned(id, pinned).await?;
+        self.events.publish(AppEvent::NoteUpdated { id: id.to_string(), version });
+        Ok(())
+    }
+
+    /// Marks a note a favorite, or not.
+    pub async fn set_note_favorite(&self, id: &str, favorite: bool) -> AppResult<()> {
+        let _write = self.events.begin_write();
+        let version = self.notes.set_favorite(id, favorite).await?;
+        self.events.publish(AppEvent::NoteUpdated { id: id.to_string(), version });
+        Ok(())
+    }
+
+    /// Whether a note is archived.
+    pub async fn is_note_archived(&self, id: &str) -> AppResult<bool> {
+        self.notes.is_archived(id).await
+    }
+
+    /// Soft-deleted notes (the trash), newest first.
+    pub async fn list_deleted_notes(&self) -> AppResult<Vec<Note>> {
+        self.notes.list_deleted().await
+    }
+
+    /// Marks an active note soft-deleted, or a soft-deleted one active;
+    /// `NotFound` if the note isn't in the opposite state.
+    pub async fn set_note_deleted(&self, id: &str, deleted: bool) -> AppResult<()> {
+        let _write = self.events.begin_write();
+        let version = self.notes.set_deleted(id, deleted).await?;
+        let id = id.to_string();
+        self.events.publish(if deleted {
+            AppEvent::NoteDeleted { id, version, permanent: false }
+        } else {
+            AppEvent::NoteRestored { id, version }
+        });
+        Ok(())
+    }
+
+    /// Stores the links in `body` as note `id`'s; a password protected
+    /// note has none, so they can't give it away while it's locked.
+    async fn index_links(&self, id: &str, body: &str) -> AppResult<()> {
+        let links = if self.notes.password_lock(id).await?.is_some() { Vec::new() } else { links::parse(body) };
+        self.links.replace(id, &links).await
+    }
+
+    /// Indexes the links of notes stored before links were. Returns the
+    /// number of notes indexed.
+    pub async fn index_unindexed_links(&self) -> AppResult<usize> {
+        let unindexed = self.links.unindexed().await?;
+        if unindexed.is_empty() {
+            return Ok(0);
+        }
+        let _write = self.events.begin_write();
+        let mut indexed = 0;
+        for note in self.notes.list_all().await? {
+            if unindexed.contains(&note.id) {
+                self.index_links(&note.id, &note.body).await?;
+                indexed += 1;
+            }
+        }
+        Ok(indexed)
+    }
+
+    /// Active notes linking to note `id`, newest first.
+    pub async fn backlinks(&self, id: &str) -> AppResult<Vec<Note>> {
+        let titles = self.notes.titles().await?;
+        let sources: Vec<String> = self
+            .links
+            .list_all()
+            .await?
+            .into_iter()
+            .filter(|(source, link)| {
+                source != id && links::resolve(link, &titles).is_some_and(|(target, _)| target == id)
+            })
+            .map(|(source, _)| source)
+            .collect();
+
+        let mut notes = Vec::new();
+        for (source, _) in titles.iter().filter(|(source, _)| sources.contains(source)) {
+            if let Some(note) = self.notes.find(source).await? {
+                notes.push(note);
+            }
+        }
+        Ok(notes)
+    }
+
+    /// Links of note `id`, in order of appearance.
+    pub async fn outgoing_links(&self, id: &str) -> AppResult<Vec<OutgoingLink>> {
+        let titles = self.notes.titles().await?;
+        Ok(self
+            .links
+            .list_for_note(id)
+            .await?
+            .into_iter()
+            .map(|link| {
+                let note = links::resolve(&link, &titles).cloned();
+                OutgoingLink {
+                    target: link.into_target(),
+                    broken: note.is_none(),
+                    note_id: note.as_ref().map(|(id, _)| id.clone()),
+                    title: note.map(|(_, title)| title),
+                }
+            })
+            .collect())
+    }
+
+    /// `(source, target)` ids of every link between two active notes, each
+    /// pair once; a note linking to itself doesn't count.
+    pub async fn link_edges(&self) -> AppResult<Vec<(String, String)>> {
+        let titles = self.notes.titles().await?;
+        let mut edges: Vec<(String, String)> = Vec::new();
+        for (source, link) in self.links.list_all().await? {
+            let Some((target, _)) = links::resolve(&link, &titles) else { continue };
+            let edge = (source, target.clone());
+            if edge.0 != edge.1 && titles.iter().any(|(id, _)| *id == edge.0) && !edges.contains(&edge) {
+                edges.push(edge);
+            }
+        }
+        Ok(edges)
+    }
+
+    /// Links from active notes to notes that are missing or in the trash.
+    pub async fn broken_links(&self) -> AppResult<Vec<BrokenLink>> {
+        let titles = self.notes.titles().await?;
+        Ok(self
+            .links
+            .list_all()
+            .await?
+            .into_iter()
+            .filter(|(_, link)| links::resolve(link, &titles).is_none())
+            .filter_map(|(source_id, link)| {
+                let (_, source_title) = titles.iter().find(|(id, _)| *id == source_id)?;
+                Some(BrokenLink { source_title: source_title.clone(), source_id, target: link.into_target() })
+            })
+            .collect())
+    }
+
+    /// Active note with identical title + body, if any (import dedup).
+    pub async fn find_note_by_content(&self, title: &str, body: &str) -> AppResult<Option<Note>> {
+        self.notes.find_by_content(title, body).await
+    }
+
+    /// Note id previously imported from `source` / `source_id`, if any.
+    pub async fn find_imported(&self, source: &str, source_id: &str) -> AppResult<Option<String>> {
+        self.imports.find(source, source_id).await
+    }
+
+    /// Records the origin of an imported note.
+    pub async fn record_import(
+        &self,
+        source: &str,
+        source_id: &str,
+        note_id: &str,
+        imported_at: i64,
+    ) -> AppResult<()> {
+        // Bookkeeping only (no event), but still a local write
+        let _write = self.events.begin_write();
+        self.imports.record(source, source_id, note_id, imported_at).await
     }
 }
@@ -0,0 +1,60 @@
+use sqlx::{SqlitePool, Row};
+use crate::error::{AppError, AppResult};
+
+/// Manages note tags (labels) only.
+#[derive(Clone)]
+pub struct TagStorage {
+    pool: SqlitePool,
+}
+
+impl TagStorage {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Replaces all tags of a note.
+    pub async fn set_for_note(&self, note_id: &str, tags: &[String]) -> AppResult<()> {
+        let mut tx = self.pool
+            .begin()
+            .await
+            .map_err(AppError::db("Failed to start transaction"))?;
+
+        sqlx::query("DELETE FROM note_tags WHERE note_id = ?")
+            .bind(note_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::db("Failed to clear tags"))?;
+
+        for tag in tags {
+            sqlx::query("INSERT OR IGNORE INTO note_tags (note_id, tag) VALUES (?, ?)")
+                .bind(note_id)
+                .bind(tag)
+                .execute(&mut *tx)
+                .await
+                .map_err(AppError::db("Failed to add tag"))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(AppError::db("Failed to save tags"))
+    }
+
+    /// Lists tags of a note, alphabetically.
+    pub async fn list_for_note(&self, note_id: &str) -> AppResult<Vec<String>> {
+        let rows = sqlx::query("SELECT tag FROM note_tags WHERE note_id = ? ORDER BY tag")
+            .bind(note_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::db("Failed to list tags"))?;
+
+        Ok(rows.iter().map(|row| row.get("tag")).collect())
+    }
+
+    /// Every `(note_id, tag)`, by note and then tag.
+    pub async fn list_all(&self) -> AppResult<Vec<(String, String)>> {
+        sqlx::query_as("SELECT note_id, tag FROM note_tags ORDER BY note_id, tag")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::db("Failed to list tags"))
+    }
+}
@@ -0,0 +1,170 @@
+// src-tauri/src/services/encryption.rs
+//! Turning encryption at rest on for a vault, unlocking and locking it,
+//! and changing its passphrase (see [`crate::crypto`]).
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde::Serialize;
+use tokio::sync::Mutex;
+
+use crate::crypto::{is_sealed, KeyFile, Keyring};
+use crate::error::{AppError, AppResult};
+use crate::services::db_storage::DbStorage;
+use crate::services::AppEvent;
+use crate::settings::Settings;
+
+/// Shorter passphrases are refused when turning encryption on.
+pub const MIN_PASSPHRASE_CHARS: usize = 8;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EncryptionStatus {
+    pub encrypted: bool,
+    pub locked: bool,
+}
+
+/// What turning encryption on sealed (a resumed run only counts what it
+/// sealed itself).
+#[derive(Debug, Clone, Serialize)]
+pub struct EncryptionReport {
+    pub notes: usize,
+    pub files: usize,
+}
+
+pub struct VaultEncryption {
+    storage: DbStorage,
+    settings: Arc<Mutex<Settings>>,
+    data_dir: PathBuf,
+}
+
+impl VaultEncryption {
+    pub fn new(storage: DbStorage, settings: Arc<Mutex<Settings>>, data_dir: &Path) -> Self {
+        Self { storage, settings, data_dir: data_dir.to_path_buf() }
+    }
+
+    fn keyring(&self) -> &Keyring {
+        self.storage.keyring()
+    }
+
+    pub fn status(&self) -> EncryptionStatus {
+        EncryptionStatus { encrypted: self.keyring().is_encrypted(), locked: self.keyring().is_locked() }
+    }
+
+    /// Encrypts the vault with `passphrase`: note files, attachments, the
+    /// trash and the DB's titles and bodies. The key file is written first,
+    /// so an interrupted run is finished by calling this again with the
+    /// same passphrase.
+    pub async fn enable(&self, passphrase: &str) -> AppResult<EncryptionReport> {
+        let key = if KeyFile::exists(&self.data_dir) {
+            KeyFile::load(&self.data_dir)?.unlock(passphrase)?
+        } else {
+            if passphrase.chars().count() < MIN_PASSPHRASE_CHARS {
+                return Err(AppError::Validation(format!(
+                    "The passphrase needs at least {} characters",
+                    MIN_PASSPHRASE_CHARS
+                )));
+            }
+            let (key_file, key) = KeyFile::create(passphrase)?;
+            key_file.save(&self.data_dir)?;
+            key
+        };
+
+        let _writes = self.storage.lock_writes().await;
+        self.keyring().unlock(key);
+
+        let (notes, attachments) = self.storage.list_everything().await?;
+        let mut paths: Vec<PathBuf> = notes
+            .iter()
+            .map(|note| PathBuf::from(&note.file_path))
+            .chain(attachments.iter().map(|attachment| PathBuf::from(&attachment.file_path)))
+            .collect();
+        let notes_folder = self.settings.lock().await.notes_folder.clone();
+        collect_files(&Path::new(&notes_folder).join("trash"), &mut paths);
+
+        let mut files = 0;
+        for path in &paths {
+            if seal_file(self.keyring(), path)? {
+                files += 1;
+            }
+        }
+        let notes = self.storage.seal_notes().await?;
+
+        self.storage.events().publish(AppEvent::ExternalChange);
+        log::info!("🔒 Vault encrypted: {} notes, {} files sealed", notes, files);
+        Ok(EncryptionReport { notes, files })
+    }
+
+    pub fn unlock(&self, passphrase: &str) -> AppResult<()> {
+        if !self.keyring().is_encrypted() {
+            return Err(AppError::Validation("This vault is not encrypted".to_string()));
+        }
+        let key = KeyFile::load(&self.data_dir)?.unlock(passphrase)?;
+        self.keyring().unlock(key);
+        self.storage.events().publish(AppEvent::VaultUnlocked);
+        log::info!("🔓 Vault unlocked");
+        Ok(())
+    }
+
+    /// Forgets the key. Does nothing for a plain or already locked vault.
+    pub fn lock(&self) {
+        if self.keyring().is_locked() || !self.keyring().is_encrypted() {
+            return;
+        }
+        self.keyring().lock();
+        self.storage.events().publish(AppEvent::VaultLocked);
+        log::info!("🔒 Vault locked");
+    }
+
+    /// Rewraps the vault key under `new`; no note or attachment is rewritten.
+    pub fn change_passphrase(&self, old: &str, new: &str) -> AppResult<()> {
+        if new.chars().count() < MIN_PASSPHRASE_CHARS {
+            return Err(AppError::Validation(format!(
+                "The passphrase needs at least {} characters",
+                MIN_PASSPHRASE_CHARS
+            )));
+        }
+        KeyFile::load(&self.data_dir)?.rewrap(old, new)?.save(&self.data_dir)?;
+        log::info!("🔑 Vault passphrase changed");
+        Ok(())
+    }
+}
+
+/// Seals `path` in place unless it's missing or sealed already.
+fn seal_file(keyring: &Keyring, path: &Path) -> AppResult<bool> {
+    let data = match fs::read(path) {
+        Ok(data) => data,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(AppError::io(format!("Failed to read {}", path.display()))(e)),
+    };
+    if is_sealed(&data) {
+        return Ok(false);
+    }
+
+    replace_file(path, &keyring.seal(&data)?)?;
+    Ok(true)
+}
+
+/// Replaces `path` with `data` through a temporary file and a rename, so a
+/// crash never leaves half a file.
+pub(crate) fn replace_file(path: &Path, data: &[u8]) -> AppResult<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".sealing");
+    let tmp = PathBuf::from(tmp);
+    fs::write(&tmp, data).map_err(AppError::io(format!("Failed to write {}", path.display())))?;
+    fs::rename(&tmp, path).map_err(AppError::io(format!("Failed to write {}", path.display())))
+}
+
+/// Every file below `dir` (none if it doesn't exist).
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.is_dir() {
+            collect_files(&path, files);
+        } else {
+            files.push(path);
+        }
+    }
+}
@@ -0,0 +1,224 @@
+// src-tauri/src/services/events.rs
+//! Typed change events published by storage mutations.
+//!
+//! `DbStorage` owns an [`EventBus`]; every mutation publishes an
+//! [`AppEvent`]. `main.rs` forwards the bus to the webview as `app-event`,
+//! so the frontend store can reconcile changes made by imports, the API,
+//! background jobs or (via [`watch_external_changes`]) other processes.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Serialize;
+use sqlx::SqliteConnection;
+use tokio::sync::broadcast;
+
+/// What changed. `version` is the note's `version` column after the change
+/// (deletes report the deleted row's version + 1), so per note it only grows.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AppEvent {
+    NoteCreated { id: String, version: i64 },
+    NoteUpdated { id: String, version: i64 },
+    /// `permanent: false` means only flagged deleted (e.g. imported trashed).
+    NoteDeleted { id: String, version: i64, permanent: bool },
+    NoteRestored { id: String, version: i64 },
+    AttachmentAdded { note_id: String, attachment_id: String, version: i64 },
+    SettingsChanged,
+    /// Another vault was opened; everything shown belongs to the old one.
+    VaultOpened { id: String },
+    /// Written outside the per-note events (another process such as the
+    /// CLI, or a notes folder move); reload everything.
+    ExternalChange,
+    /// The encrypted vault was locked (by command or after idling); note
+    /// reads fail with `Locked` until it is unlocked.
+    VaultLocked,
+    VaultUnlocked,
+    /// The webview missed `missed` events; reload everything.
+    /// Sent by the forwarder only, never published on the bus.
+    Resync { missed: u64 },
+}
+
+/// Event plus a per-process sequence number, so the frontend can detect gaps.
+#[derive(Debug, Clone, Serialize)]
+pub struct EventEnvelope {
+    pub seq: u64,
+    #[serde(flatten)]
+    pub event: AppEvent,
+}
+
+/// Cheap-to-clone handle on the broadcast channel.
+#[derive(Clone)]
+pub struct EventBus {
+    tx: broadcast::Sender<EventEnvelope>,
+    seq: Arc<AtomicU64>,
+    writes_started: Arc<AtomicU64>,
+    writes_finished: Arc<AtomicU64>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        let (tx, _) = broadcast::channel(256);
+        Self {
+            tx,
+            seq: Arc::new(AtomicU64::new(0)),
+            writes_started: Arc::new(AtomicU64::new(0)),
+            writes_finished: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Publishes an event. Having no subscribers (CLI, tests) is fine.
+    pub fn publish(&self, event: AppEvent) {
+        let seq = self.seq.fetch_add(1, Ordering::SeqCst) + 1;
+        let _ = self.tx.send(EventEnvelope { seq, event });
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<EventEnvelope> {
+        self.tx.subscribe()
+    }
+
+    /// Sequence number of the last published event (0 if none yet).
+    pub fn last_seq(&self) -> u64 {
+        self.seq.load(Ordering::SeqCst)
+    }
+
+    /// Marks a DB write by this process until the guard is dropped.
+    /// Hold it across the write *and* the publish.
+    pub fn begin_write(&self) -> WriteGuard {
+        self.writes_started.fetch_add(1, Ordering::SeqCst);
+        WriteGuard(self.writes_finished.clone())
+    }
+
+    /// True while some local write holds a [`WriteGuard`].
+    pub fn writes_in_flight(&self) -> bool {
+        self.settled_writes().is_none()
+    }
+
+    /// Number of local writes so far, or `None` while one is in flight.
+    fn settled_writes(&self) -> Option<u64> {
+        let finished = self.writes_finished.load(Ordering::SeqCst);
+        let started = self.writes_started.load(Ordering::SeqCst);
+        (started == finished).then_some(started)
+    }
+}
+
+/// See [`EventBus::begin_write`].
+pub struct WriteGuard(Arc<AtomicU64>);
+
+impl Drop for WriteGuard {
+    fn drop(&mut self) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+/// Polls `PRAGMA data_version` and publishes `ExternalChange` when the DB
+/// changed while this process made no writes of its own.
+///
+/// `conn` must not come from the app's pool: `data_version` only moves for
+/// commits made by *other* connections. Changes from another process that
+/// land in the same interval as a local write are not reported; the local
+/// write's own event already makes the UI refresh that note.
+pub async fn watch_external_changes(mut conn: SqliteConnection, bus: EventBus, interval: Duration) {
+    let mut last: Option<(i64, Option<u64>)> = None;
+
+    loop {
+        tokio::time::sleep(interval).await;
+
+        let before = bus.settled_writes();
+        let version = match sqlx::query_as::<_, (i64,)>("PRAGMA data_version")
+            .fetch_one(&mut conn)
+            .await
+        {
+            Ok((version,)) => version,
+            Err(e) => {
+                log::warn!("data_version poll failed: {}", e);
+                continue;
+            }
+        };
+        let after = bus.settled_writes();
+        let writes = if before == after { after } else { None };
+
+        if let Some((last_version, last_writes)) = last {
+            let idle = last_writes.is_some() && writes == last_writes;
+            if version != last_version && idle {
+                bus.publish(AppEvent::ExternalChange);
+            }
+        }
+        last = Some((version, writes));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn created(id: &str) -> AppEvent {
+        AppEvent::NoteCreated { id: id.to_string(), version: 1 }
+    }
+
+    #[tokio::test]
+    async fn sequence_numbers_start_at_one_and_increase() {
+        let bus = EventBus::new();
+        let mut rx = bus.subscribe();
+        assert_eq!(bus.last_seq(), 0);
+
+        bus.publish(created("a"));
+        bus.publish(AppEvent::SettingsChanged);
+
+        let first = rx.recv().await.unwrap();
+        let second = rx.recv().await.unwrap();
+        assert_eq!((first.seq, first.event), (1, created("a")));
+        assert_eq!((second.seq, second.event), (2, AppEvent::SettingsChanged));
+        assert_eq!(bus.last_seq(), 2);
+    }
+
+    #[test]
+    fn publishing_without_subscribers_still_counts() {
+        let bus = EventBus::new();
+        bus.publish(created("a"));
+        assert_eq!(bus.last_seq(), 1);
+    }
+
+    #[tokio::test]
+    async fn clones_share_one_sequence() {
+        let bus = EventBus::new();
+        let clone = bus.clone();
+        let mut rx = bus.subscribe();
+
+        bus.publish(created("a"));
+        clone.publish(created("b"));
+
+        assert_eq!(rx.recv().await.unwrap().seq, 1);
+        assert_eq!(rx.recv().await.unwrap().seq, 2);
+    }
+
+    #[test]
+    fn envelope_serializes_flat_with_type_tag() {
+        let envelope = EventEnvelope {
+            seq: 7,
+            event: AppEvent::NoteDeleted { id: "n".to_string(), version: 3, permanent: true },
+        };
+        assert_eq!(
+            serde_json::to_value(&envelope).unwrap(),
+            serde_json::json!({ "seq": 7, "type": "note_deleted", "id": "n", "version": 3, "permanent": true })
+        );
+    }
+
+    #[test]
+    fn write_guards_track_in_flight_writes() {
+        let bus = EventBus::new();
+        assert_eq!(bus.settled_writes(), Some(0));
+
+        let guard = bus.begin_write();
+        assert_eq!(bus.settled_writes(), None);
+        drop(guard);
+        assert_eq!(bus.settled_writes(), Some(1));
+    }
+}
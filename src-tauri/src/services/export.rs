@@ -0,0 +1,349 @@
+// src-tauri/src/services/export.rs
+//! Single-note export for sharing outside the app.
+//!
+//! - `Html`: standalone page, attachments inlined as data URIs.
+//! - `Text`: plain text rendering of the body.
+//! - `Json`: lossless document; re-importable via `ImportFormat::Json`.
+
+use std::collections::HashMap;
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+
+use crate::crypto::Keyring;
+use crate::db::schema::{Attachment, NoteWithAttachments};
+use crate::services::db_storage::DbStorage;
+use crate::services::import::html::{escape, unescape};
+use crate::services::note_files::reference_forms;
+use crate::error::{AppError, AppResult};
+
+/// Marker stored in `NoteExport::format` so importers can recognise the file.
+pub const NOTE_EXPORT_FORMAT: &str = "rusty-notes/note";
+pub const NOTE_EXPORT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Html,
+    Text,
+    Json,
+}
+
+/// Export result handed to the frontend / CLI, which decide where to save it.
+#[derive(Debug, Serialize)]
+pub struct ExportedNote {
+    /// Suggested file name, e.g. `Meeting notes.html`.
+    pub file_name: String,
+    pub mime_type: String,
+    pub contents: String,
+}
+
+/// Lossless JSON document: the `NoteWithAttachments` projection (flags such
+/// as `archived` included) plus the note's tags and every attachment's bytes
+/// (base64, keyed by attachment id).
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NoteExport {
+    pub format: String,
+    pub version: u32,
+    #[serde(flatten)]
+    pub note: NoteWithAttachments,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub attachment_contents: HashMap<String, String>,
+}
+
+pub struct ExportService {
+    storage: DbStorage,
+}
+
+impl ExportService {
+    pub fn new(storage: DbStorage) -> Self {
+        Self { storage }
+    }
+
+    /// Renders note `id` in the requested format.
+    pub async fn export_note(&self, id: &str, format: ExportFormat) -> AppResult<ExportedNote> {
+        let note = self.storage.get_note(id).await?;
+        if note.locked {
+            return Err(AppError::NoteLocked(id.to_string()));
+        }
+        let tags = self.storage.list_note_tags(id).await?;
+        let protected = self.storage.note_password_lock(id).await?.is_some();
+        let base_name = file_stem_for(&note.note.title);
+
+        let exported = match format {
+            ExportFormat::Html => {
+                let contents = read_attachments(self.storage.keyring(), protected, &note.attachments).await?;
+                ExportedNote {
+                    file_name: format!("{}.html", base_name),
+                    mime_type: "text/html".to_string(),
+                    contents: render_html(&note, &contents),
+                }
+            }
+            ExportFormat::Text => ExportedNote {
+                file_name: format!("{}.txt", base_name),
+                mime_type: "text/plain".to_string(),
+                contents: format!("{}\n\n{}\n", note.note.title, html_to_text(&note.note.body)),
+            },
+            ExportFormat::Json => {
+                // Versions are local to a DB; an import starts over at 1.
+                // Also keeps `version` free for the export format version.
+                let mut note = note;
+                note.note.version = 0;
+                let attachment_contents = read_attachments(self.storage.keyring(), protected, &note.attachments)
+                    .await?
+                    .into_iter()
+                    .map(|(id, data)| (id, BASE64.encode(data)))
+                    .collect();
+                let document = NoteExport {
+                    format: NOTE_EXPORT_FORMAT.to_string(),
+                    version: NOTE_EXPORT_VERSION,
+                    note,
+                    tags,
+                    attachment_contents,
+                };
+                ExportedNote {
+                    file_name: format!("{}.json", base_name),
+                    mime_type: "application/json".to_string(),
+                    contents: serde_json::to_string_pretty(&document)
+                        .map_err(AppError::internal("Failed to serialize note"))?,
+                }
+            }
+        };
+
+        log::info!("✅ Exported note: {} as {:?}", id, format);
+        Ok(exported)
+    }
+}
+
+/// Reads (and opens) every attachment file; missing files are skipped,
+/// not fatal.
+async fn read_attachments(
+    keyring: &Keyring,
+    protected: bool,
+    attachments: &[Attachment],
+) -> AppResult<HashMap<String, Vec<u8>>> {
+    let mut contents = HashMap::new();
+    for attachment in attachments {
+        match fs::read(&attachment.file_path).await {
+            Ok(data) => {
+                let data = keyring.open_note_data(&attachment.note_id, protected, keyring.open(&data)?)?;
+                contents.insert(attachment.id.clone(), data);
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                log::warn!("⚠️ Attachment missing on disk: {}", attachment.file_path);
+            }
+            Err(e) => return Err(AppError::io(format!("Failed to read attachment {}", attachment.file_name))(e)),
+        }
+    }
+    Ok(contents)
+}
+
+const STYLESHEET: &str = "\
+body{font-family:system-ui,-apple-system,'Segoe UI',sans-serif;line-height:1.6;max-width:46rem;margin:2rem auto;padding:0 1rem;color:#1f2328}\
+h1,h2,h3{line-height:1.25}\
+img{max-width:100%}\
+pre,code{background:#f6f8fa;border-radius:4px}\
+pre{padding:.75rem;overflow:auto}\
+ul[data-type=taskList]{list-style:none;padding-left:0}\
+li[data-type=taskItem][data-checked=true]>p{text-decoration:line-through;color:#57606a}\
+li[data-type=taskItem]>p::before{content:'\\2610\\00a0'}\
+li[data-type=taskItem][data-checked=true]>p::before{content:'\\2611\\00a0'}\
+.attachments{border-top:1px solid #d0d7de;margin-top:2rem;padding-top:1rem}\
+time{color:#57606a;font-size:.875rem}";
+
+fn render_html(note: &NoteWithAttachments, contents: &HashMap<String, Vec<u8>>) -> String {
+    let mut body = note.note.body.clone();
+    let mut listed = String::new();
+
+    for attachment in &note.attachments {
+        let Some(data) = contents.get(&attachment.id) else { continue };
+        let mime = attachment.mime_type.as_deref().unwrap_or("application/octet-stream");
+        let data_uri = format!("data:{};base64,{}", mime, BASE64.encode(data));
+
+        // Inline references the editor made to the file on disk (any URL form)
+        let mut referenced = false;
+        for form in reference_forms(&attachment.file_path) {
+            if body.contains(&form) {
+                body = body.replace(&form, &data_uri);
+                referenced = true;
+            }
+        }
+
+        if referenced {
+            continue;
+        } else if mime.starts_with("image/") {
+            listed.push_str(&format!(
+                "<figure><img src=\"{}\" alt=\"{}\"><figcaption>{}</figcaption></figure>",
+                data_uri,
+                escape(&attachment.file_name),
+                escape(&attachment.file_name)
+            ));
+        } else {
+            listed.push_str(&format!(
+                "<p><a download=\"{0}\" href=\"{1}\">{0}</a></p>",
+                escape(&attachment.file_name),
+                data_uri
+            ));
+        }
+    }
+
+    let updated = chrono::DateTime::from_timestamp(note.note.updated_at, 0)
+        .map(|dt| dt.to_rfc3339())
+        .unwrap_or_default();
+    let attachments_section = if listed.is_empty() {
+        String::new()
+    } else {
+        format!("<section class=\"attachments\">{}</section>", listed)
+    };
+
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>{style}</style>\n</head>\n<body>\n<article>\n<h1>{title}</h1>\n<time datetime=\"{updated}\">{updated}</time>\n{body}\n{attachments}\n</article>\n</body>\n</html>\n",
+        title = escape(&note.note.title),
+        style = STYLESHEET,
+        updated = updated,
+        body = body,
+        attachments = attachments_section,
+    )
+}
+
+/// Very small HTML → text conversion for the editor's markup:
+/// block elements become line breaks, task items get `[ ]` / `[x]`.
+pub fn html_to_text(html: &str) -> String {
+    let mut out = String::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        out.push_str(&unescape(&rest[..start]));
+        let Some(end) = rest[start..].find('>') else {
+            rest = "";
+            break;
+        };
+        let tag = &rest[start + 1..start + end];
+        let name: String = tag
+            .trim_start_matches('/')
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_ascii_lowercase();
+        let closing = tag.starts_with('/');
+
+        match (name.as_str(), closing) {
+            ("li", false) if tag.contains("data-checked=\"true\"") => out.push_str("[x] "),
+            ("li", false) if tag.contains("data-type=\"taskItem\"") => out.push_str("[ ] "),
+            ("li", false) => out.push_str("- "),
+            ("br", _) => out.push('\n'),
+            ("p" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "blockquote" | "pre" | "div", true) => {
+                out.push('\n')
+            }
+            _ => {}
+        }
+
+        rest = &rest[start + end + 1..];
+    }
+    out.push_str(&unescape(rest));
+
+    // Collapse the blank lines nested blocks produce (e.g. `<li><p>`)
+    let mut text = String::new();
+    let mut blank_run = 0;
+    for line in out.lines() {
+        if line.trim().is_empty() {
+            blank_run += 1;
+            if blank_run > 1 {
+                continue;
+            }
+        } else {
+            blank_run = 0;
+        }
+        text.push_str(line.trim_end());
+        text.push('\n');
+    }
+    text.trim().to_string()
+}
+
+/// File-system friendly version of a note title.
+fn file_stem_for(title: &str) -> String {
+    let cleaned: String = title
+        .chars()
+        .map(|c| if matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|') { '_' } else { c })
+        .collect();
+    let cleaned = cleaned.trim();
+    if cleaned.is_empty() { "note".to_string() } else { cleaned.to_string() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::schema::Note;
+
+    #[test]
+    fn html_to_text_keeps_structure() {
+        let html = "<h1>Plan</h1><p>a &amp; b</p><ul><li><p>one</p></li></ul>\
+                    <ul data-type=\"taskList\"><li data-type=\"taskItem\" data-checked=\"true\"><p>done</p></li>\
+                    <li data-type=\"taskItem\" data-checked=\"false\"><p>todo</p></li></ul><p>x<br>y</p>";
+        assert_eq!(html_to_text(html), "Plan\na & b\n- one\n[x] done\n[ ] todo\nx\ny");
+    }
+
+    #[test]
+    fn html_to_text_collapses_blank_lines() {
+        assert_eq!(html_to_text("<p>a</p><p></p><p></p><p></p><p>b</p>"), "a\n\nb");
+        assert_eq!(html_to_text("plain &lt;text&gt;"), "plain <text>");
+    }
+
+    #[test]
+    fn file_stem_replaces_reserved_characters() {
+        assert_eq!(file_stem_for("a/b: c?"), "a_b_ c_");
+        assert_eq!(file_stem_for("  "), "note");
+    }
+
+    fn note_with_image(body: &str) -> NoteWithAttachments {
+        NoteWithAttachments {
+            note: Note {
+                id: "n1".to_string(),
+                title: "T".to_string(),
+                body: body.to_string(),
+                created_at: 0,
+                updated_at: 0,
+                file_path: "/notes/n1.html".to_string(),
+                notebook: None,
+                version: 1,
+                pinned: false,
+                favorite: false,
+                archived: false,
+            },
+            attachments: vec![Attachment {
+                id: "a1".to_string(),
+                note_id: "n1".to_string(),
+                attachment_type: "image".to_string(),
+                file_name: "my pic.png".to_string(),
+                file_path: "/notes/attachments/n1/my pic.png".to_string(),
+                mime_type: Some("image/png".to_string()),
+                size_bytes: Some(3),
+                created_at: 0,
+            }],
+            locked: false,
+        }
+    }
+
+    #[test]
+    fn render_html_inlines_asset_urls() {
+        let note = note_with_image("<img src=\"asset://localhost/%2Fnotes%2Fattachments%2Fn1%2Fmy%20pic.png\">");
+        let contents = HashMap::from([("a1".to_string(), b"png".to_vec())]);
+        let html = render_html(&note, &contents);
+
+        assert!(html.contains("<img src=\"data:image/png;base64,cG5n\">"));
+        assert!(!html.contains("class=\"attachments\""));
+    }
+
+    #[test]
+    fn render_html_lists_unreferenced_attachments() {
+        let note = note_with_image("<p>no image here</p>");
+        let contents = HashMap::from([("a1".to_string(), b"png".to_vec())]);
+        let html = render_html(&note, &contents);
+
+        assert!(html.contains("<section class=\"attachments\"><figure><img src=\"data:image/png;base64,cG5n\" alt=\"my pic.png\">"));
+    }
+}
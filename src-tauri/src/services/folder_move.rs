@@ -0,0 +1,359 @@
+// src-tauri/src/services/folder_move.rs
+//! Moves the notes folder: copies every note file, attachment and trashed
+//! file to the new folder, verifies the copies, points the DB rows at them
+//! in one transaction, and only then switches `notes_folder` and removes the
+//! originals.
+//!
+//! Progress is kept in `folder-move.json` in the app data dir, so a move
+//! that was interrupted (crash, full disk) continues where it stopped when
+//! it is started again, or when the app starts.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::sync::Mutex;
+
+use crate::error::{AppError, AppResult};
+use crate::services::db_storage::{DbStorage, RelocatedNote};
+use crate::services::note_files::rewrite_references;
+use crate::settings::validation::ensure_writable;
+use crate::settings::{Settings, SettingsFiles};
+
+const JOURNAL_FILE: &str = "folder-move.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Stage {
+    /// Copying files; DB and settings still use `from`.
+    Copying,
+    /// Rewriting DB paths; the transaction may or may not have committed.
+    Switching,
+    /// DB and settings use `to`; removing the originals.
+    Cleanup,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Journal {
+    from: PathBuf,
+    to: PathBuf,
+    stage: Stage,
+}
+
+/// Outcome of a finished move.
+#[derive(Debug, Clone, Serialize)]
+pub struct FolderMoveReport {
+    pub from: String,
+    pub to: String,
+    /// Files copied (a resumed move only counts what it copied itself)
+    pub files: usize,
+    pub notes: usize,
+    pub attachments: usize,
+}
+
+pub struct FolderMove {
+    storage: DbStorage,
+    settings: Arc<Mutex<Settings>>,
+    settings_files: SettingsFiles,
+    journal_path: PathBuf,
+}
+
+impl FolderMove {
+    pub fn new(
+        storage: DbStorage,
+        settings: Arc<Mutex<Settings>>,
+        settings_files: SettingsFiles,
+        app_data_dir: &Path,
+    ) -> Self {
+        Self { storage, settings, settings_files, journal_path: app_data_dir.join(JOURNAL_FILE) }
+    }
+
+    /// Moves the notes folder to `to` (absolute). Starting the same move
+    /// again resumes it; a different target is refused until it's finished.
+    pub async fn run(&self, to: PathBuf) -> AppResult<FolderMoveReport> {
+        let mut journal = match self.read_journal()? {
+            Some(journal) if journal.to == to => journal,
+            Some(journal) => {
+                return Err(AppError::Validation(format!(
+                    "The move of the notes folder to {} is not finished yet",
+                    journal.to.display()
+                )))
+            }
+            None => {
+                let from = PathBuf::from(&self.settings.lock().await.notes_folder);
+                check_target(&from, &to)?;
+                Journal { from, to, stage: Stage::Copying }
+            }
+        };
+        self.write_journal(&journal)?;
+        log::info!("📦 Moving notes folder {} -> {}", journal.from.display(), journal.to.display());
+
+        let mut files = 0;
+        if journal.stage == Stage::Copying {
+            files += copy_tree(journal.from.clone(), journal.to.clone(), Verify::Checksum).await?;
+            journal.stage = Stage::Switching;
+            self.write_journal(&journal)?;
+        }
+
+        let (mut notes, mut attachments) = (0, 0);
+        if journal.stage == Stage::Switching {
+            let switched = self.switch(&journal).await?;
+            files += switched.0;
+            (notes, attachments) = (switched.1, switched.2);
+            journal.stage = Stage::Cleanup;
+            self.write_journal(&journal)?;
+        }
+
+        let (from, to) = (journal.from.clone(), journal.to.clone());
+        tokio::task::spawn_blocking(move || remove_originals(&from, &to))
+            .await
+            .map_err(AppError::internal("Cleanup task failed"))?
+            .map_err(AppError::io("Failed to remove the old notes folder"))?;
+        fs::remove_file(&self.journal_path).map_err(AppError::io("Failed to remove folder move journal"))?;
+
+        log::info!("✅ Notes folder moved to {}", journal.to.display());
+        Ok(FolderMoveReport {
+            from: journal.from.to_string_lossy().to_string(),
+            to: journal.to.to_string_lossy().to_string(),
+            files,
+            notes,
+            attachments,
+        })
+    }
+
+    /// Finishes a move the app was interrupted in once the DB switch had
+    /// started; the DB may already point at the new folder. A move still
+    /// copying is left alone (nothing depends on it yet).
+    pub async fn resume_interrupted(&self) -> AppResult<Option<FolderMoveReport>> {
+        match self.read_journal()? {
+            Some(journal) if journal.stage != Stage::Copying => self.run(journal.to).await.map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// Copies what changed since the first pass, rewrites the DB and saves
+    /// the setting, all under the write lock so no note changes midway.
+    /// Returns `(files copied, notes moved, attachments moved)`.
+    async fn switch(&self, journal: &Journal) -> AppResult<(usize, usize, usize)> {
+        let _writes = self.storage.lock_writes().await;
+        let files = copy_tree(journal.from.clone(), journal.to.clone(), Verify::Modified).await?;
+
+        let (all_notes, all_attachments) = self.storage.list_everything().await?;
+        let moved_attachments: Vec<(String, String, String)> = all_attachments
+            .into_iter()
+            .filter_map(|a| {
+                let new_path = relocated(&a.file_path, &journal.from, &journal.to)?;
+                Some((a.id, a.file_path, new_path))
+            })
+            .collect();
+
+        let mut moved_notes = Vec::new();
+        for note in all_notes {
+            let Some(file_path) = relocated(&note.file_path, &journal.from, &journal.to) else {
+                continue;
+            };
+            let body = moved_attachments
+                .iter()
+                .fold(note.body.clone(), |body, (_, old, new)| rewrite_references(&body, old, new));
+
+            // The DB has the latest body (an edit may have landed after the
+            // first copy), references included
+            if Path::new(&note.file_path).exists() || Path::new(&file_path).exists() {
+                let sealed = self.storage.keyring().seal(body.as_bytes())?;
+                fs::write(&file_path, sealed).map_err(AppError::io("Failed to write moved note"))?;
+            }
+            moved_notes.push(RelocatedNote {
+                id: note.id,
+                file_path,
+                body: (body != note.body).then_some(body),
+            });
+        }
+
+        let attachment_paths: Vec<(String, String)> =
+            moved_attachments.into_iter().map(|(id, _, new)| (id, new)).collect();
+        self.storage.relocate(&journal.to, &moved_notes, &attachment_paths).await?;
+
+        let mut settings = self.settings.lock().await;
+        let mut updated = settings.clone();
+        updated.notes_folder = journal.to.to_string_lossy().to_string();
+        self.settings_files.save(&updated).map_err(AppError::internal("Failed to save settings"))?;
+        *settings = updated;
+
+        Ok((files, moved_notes.len(), attachment_paths.len()))
+    }
+
+    fn read_journal(&self) -> AppResult<Option<Journal>> {
+        match fs::read_to_string(&self.journal_path) {
+            Ok(json) => serde_json::from_str(&json)
+                .map(Some)
+                .map_err(AppError::internal("Corrupt folder move journal")),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(AppError::io("Failed to read folder move journal")(e)),
+        }
+    }
+
+    fn write_journal(&self, journal: &Journal) -> AppResult<()> {
+        let json = serde_json::to_string_pretty(journal).map_err(AppError::internal("Failed to encode journal"))?;
+        // Written aside and renamed, so a crash never leaves half a journal
+        let tmp = self.journal_path.with_extension("json.tmp");
+        fs::write(&tmp, json).map_err(AppError::io("Failed to write folder move journal"))?;
+        fs::rename(&tmp, &self.journal_path).map_err(AppError::io("Failed to write folder move journal"))
+    }
+}
+
+/// `path` under `to` if it is under `from`.
+fn relocated(path: &str, from: &Path, to: &Path) -> Option<String> {
+    let rest = Path::new(path).strip_prefix(from).ok()?;
+    Some(to.join(rest).to_string_lossy().to_string())
+}
+
+/// The target must be a new or empty, writable folder outside the current one.
+fn check_target(from: &Path, to: &Path) -> AppResult<()> {
+    let invalid = |message: &str| Err(AppError::Validation(message.to_string()));
+    if !to.is_absolute() {
+        return invalid("The new notes folder must be an absolute path");
+    }
+    let from = from.canonicalize().unwrap_or_else(|_| from.to_path_buf());
+    let to_resolved = to.canonicalize().unwrap_or_else(|_| to.to_path_buf());
+    if to_resolved == from {
+        return invalid("The notes are already in that folder");
+    }
+    if to_resolved.starts_with(&from) {
+        return invalid("The new notes folder can't be inside the current one");
+    }
+    if to.exists() {
+        let empty = fs::read_dir(to).map(|mut entries| entries.next().is_none()).unwrap_or(false);
+        if !empty {
+            return invalid("The new notes folder must be empty");
+        }
+    }
+    ensure_writable(to).map_err(AppError::Validation)
+}
+
+/// When a file already at the destination counts as copied.
+#[derive(Clone, Copy)]
+enum Verify {
+    /// Same SHA-256 (resuming: the copy may be cut short)
+    Checksum,
+    /// Same size and not older than the source (files verified before)
+    Modified,
+}
+
+/// Copies every file under `from` to the same place under `to`, checking
+/// each copy's SHA-256. Returns the number of files copied.
+async fn copy_tree(from: PathBuf, to: PathBuf, verify: Verify) -> AppResult<usize> {
+    tokio::task::spawn_blocking(move || {
+        let mut copied = 0;
+        for src in list_files(&from)? {
+            let dest = to.join(src.strip_prefix(&from).expect("listed under from"));
+            if dest.exists() && up_to_date(&src, &dest, verify)? {
+                continue;
+            }
+            if let Some(parent) = dest.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::copy(&src, &dest)?;
+            if sha256(&src)? != sha256(&dest)? {
+                return Err(io::Error::other(format!("Copy of {} doesn't match the original", src.display())));
+            }
+            copied += 1;
+        }
+        Ok(copied)
+    })
+    .await
+    .map_err(AppError::internal("Copy task failed"))?
+    .map_err(AppError::io("Failed to copy notes"))
+}
+
+fn up_to_date(src: &Path, dest: &Path, verify: Verify) -> io::Result<bool> {
+    match verify {
+        Verify::Checksum => Ok(sha256(src)? == sha256(dest)?),
+        Verify::Modified => {
+            let (src, dest) = (fs::metadata(src)?, fs::metadata(dest)?);
+            Ok(src.len() == dest.len() && dest.modified()? >= src.modified()?)
+        }
+    }
+}
+
+fn sha256(path: &Path) -> io::Result<Vec<u8>> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut fs::File::open(path)?, &mut hasher)?;
+    Ok(hasher.finalize().to_vec())
+}
+
+/// All files under `dir` (recursively); empty if `dir` doesn't exist.
+fn list_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e),
+        };
+        for entry in entries {
+            let path = entry?.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else {
+                files.push(path);
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// Deletes originals that have a copy under `to`, then empty folders.
+/// Anything without a copy (written after the switch) is left in place.
+fn remove_originals(from: &Path, to: &Path) -> io::Result<()> {
+    for src in list_files(from)? {
+        if to.join(src.strip_prefix(from).expect("listed under from")).exists() {
+            fs::remove_file(&src)?;
+        }
+    }
+    remove_empty_dirs(from)
+}
+
+fn remove_empty_dirs(dir: &Path) -> io::Result<()> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Ok(());
+    };
+    for entry in entries {
+        let path = entry?.path();
+        if path.is_dir() {
+            remove_empty_dirs(&path)?;
+        }
+    }
+    if fs::read_dir(dir)?.next().is_none() {
+        fs::remove_dir(dir)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relocates_only_paths_under_the_old_folder() {
+        let (from, to) = (Path::new("/data/notes"), Path::new("/mnt/notes"));
+        assert_eq!(relocated("/data/notes/a/b.html", from, to).as_deref(), Some("/mnt/notes/a/b.html"));
+        assert_eq!(relocated("/data/notes-old/b.html", from, to), None);
+        assert_eq!(relocated("/elsewhere/b.html", from, to), None);
+    }
+
+    #[test]
+    fn refuses_a_target_inside_the_notes_folder() {
+        let from = std::env::temp_dir().join(format!("rusty-notes-move-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&from).unwrap();
+
+        let inside = check_target(&from, &from.join("sub"));
+        let same = check_target(&from, &from);
+        fs::remove_dir_all(&from).unwrap();
+        assert!(matches!(inside, Err(AppError::Validation(_))));
+        assert!(matches!(same, Err(AppError::Validation(_))));
+    }
+}
@@ -0,0 +1,266 @@
+// src-tauri/src/services/folder_sync.rs
+//! Keeps the DB in line with `{notes_folder}/*.html` edited by other tools
+//! (external editors, Syncthing, ...).
+//!
+//! [`watch`] debounces `notify` events and hands each changed path to
+//! [`FolderSync::sync_path`]. The app's own writes are ignored: a file whose
+//! content equals the stored body is a no-op, and paths are only processed
+//! once no local write (see `EventBus::begin_write`) is in flight. Changes go
+//! through `DbStorage`, so the UI hears about them on the event bus.
+
+use std::collections::HashSet;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::sync::{mpsc, Mutex};
+use tokio::task::JoinHandle;
+
+use crate::crypto::is_note_sealed_text;
+use crate::db::schema::Note;
+use crate::services::events::EventBus;
+use crate::services::{DbStorage, PermanentDelete, TrashManager};
+use crate::settings::model::{DeleteBehavior, Settings};
+use crate::error::{AppError, AppResult};
+
+/// What [`FolderSync::sync_path`] did with a path.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyncAction {
+    /// Not a note file, or a file the DB doesn't track (e.g. trashed note).
+    Ignored,
+    /// File and DB already agree (typically the app's own write).
+    Unchanged,
+    Updated(String),
+    Created(String),
+    Deleted(String),
+}
+
+pub struct FolderSync {
+    storage: DbStorage,
+    settings: Arc<Mutex<Settings>>,
+}
+
+impl FolderSync {
+    pub fn new(storage: DbStorage, settings: Arc<Mutex<Settings>>) -> Self {
+        Self { storage, settings }
+    }
+
+    /// Applies the current state of `path` to the DB:
+    /// changed file → body updated, unknown file → new note,
+    /// missing file → note deleted according to `delete_behavior`.
+    pub async fn sync_path(&self, path: &Path) -> AppResult<SyncAction> {
+        let settings = self.settings.lock().await.clone();
+        let Some(id) = note_id_for(path, Path::new(&settings.notes_folder)) else {
+            return Ok(SyncAction::Ignored);
+        };
+
+        // Held throughout, so no app write (e.g. an import) interleaves
+        let _writes = self.storage.lock_writes().await;
+        let storage = &self.storage;
+
+        let body = match tokio::fs::read(path).await {
+            Ok(bytes) => String::from_utf8_lossy(&storage.keyring().open(&bytes)?).into_owned(),
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                let Some(note) = storage.find_note(&id).await? else {
+                    return Ok(SyncAction::Ignored);
+                };
+                // Only if the note actually lives in this file
+                let tracked = note_id_for(Path::new(&note.note.file_path), Path::new(&settings.notes_folder));
+                if tracked.as_deref() != Some(id.as_str()) {
+                    return Ok(SyncAction::Ignored);
+                }
+
+                match settings.delete_behavior {
+                    DeleteBehavior::MoveToTrash => {
+                        // The file is gone; keep the last known body instead
+                        // (as stored: a protected body stays sealed)
+                        let stored = storage.find_stored_note(&id).await?;
+                        let last_body = stored.map_or_else(|| note.note.body.clone(), |stored| stored.body);
+                        let trash = TrashManager::folder_for_today(&settings.notes_folder);
+                        tokio::fs::create_dir_all(&trash)
+                            .await
+                            .map_err(AppError::io("Failed to create trash folder"))?;
+                        let body = storage.keyring().seal(last_body.as_bytes())?;
+                        tokio::fs::write(trash.join(format!("{}.html", id)), body)
+                            .await
+                            .map_err(AppError::io("Failed to write note to trash"))?;
+                        TrashManager::move_to_trash(&settings.notes_folder, &note).await?;
+                    }
+                    DeleteBehavior::Permanent => PermanentDelete::delete(&note).await?,
+                }
+                storage.delete_note(&id).await?;
+
+                log::info!("🗑️ Note file removed externally: {}", id);
+                return Ok(SyncAction::Deleted(id));
+            }
+            Err(e) => return Err(AppError::io(format!("Failed to read {}", path.display()))(e)),
+        };
+
+        // A password protected note's file: other tools can't have edited
+        // it, and it's sealed apart from the row, so there's nothing to compare
+        if is_note_sealed_text(&body) {
+            let tracked = storage.note_exists(&id).await?;
+            return Ok(if tracked { SyncAction::Unchanged } else { SyncAction::Ignored });
+        }
+
+        match storage.find_note(&id).await? {
+            Some(existing) if existing.note.body == body => Ok(SyncAction::Unchanged),
+            Some(existing) => {
+                let mut note = existing.note;
+                note.body = body;
+                note.updated_at = Utc::now().timestamp();
+                storage.update_note(note, None).await?;
+
+                log::info!("✏️ Note file changed externally: {}", id);
+                Ok(SyncAction::Updated(id))
+            }
+            // Soft-deleted note whose file reappeared: leave it alone
+            None if storage.note_exists(&id).await? => Ok(SyncAction::Ignored),
+            None => {
+                let now = Utc::now().timestamp();
+                storage
+                    .create_note(Note {
+                        id: id.clone(),
+                        title: id.clone(),
+                        body,
+                        created_at: now,
+                        updated_at: now,
+                        file_path: path.to_string_lossy().to_string(),
+                        notebook: None,
+                        version: 0,
+                        pinned: false,
+                        favorite: false,
+                        archived: false,
+                    })
+                    .await?;
+
+                log::info!("📄 New note file picked up: {}", id);
+                Ok(SyncAction::Created(id))
+            }
+        }
+    }
+
+    /// Syncs every note file currently in the folder (edits made while the
+    /// app was closed). Missing files are not treated as deletions here: an
+    /// unmounted or half-synced folder must not wipe the DB.
+    pub async fn scan(&self) -> AppResult<Vec<SyncAction>> {
+        let folder = PathBuf::from(&self.settings.lock().await.notes_folder);
+        let mut entries = match tokio::fs::read_dir(&folder).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(AppError::io("Failed to read notes folder")(e)),
+        };
+
+        let mut actions = Vec::new();
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .map_err(AppError::io("Failed to read notes folder"))?
+        {
+            let action = self.sync_path(&entry.path()).await?;
+            if action != SyncAction::Ignored && action != SyncAction::Unchanged {
+                actions.push(action);
+            }
+        }
+        Ok(actions)
+    }
+}
+
+/// Running watcher; dropping it stops watching.
+pub struct FolderWatcher {
+    _watcher: RecommendedWatcher,
+    task: JoinHandle<()>,
+}
+
+impl Drop for FolderWatcher {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Watches `folder` (not recursively: `trash/` and `attachments/` are the
+/// app's own) and syncs paths once they were quiet for `debounce`.
+pub fn watch(sync: FolderSync, folder: &Path, bus: EventBus, debounce: Duration) -> AppResult<FolderWatcher> {
+    std::fs::create_dir_all(folder).map_err(AppError::io("Failed to create notes folder"))?;
+
+    let (tx, rx) = mpsc::unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |result: notify::Result<notify::Event>| match result {
+        Ok(event) => {
+            for path in event.paths {
+                let _ = tx.send(path);
+            }
+        }
+        Err(e) => log::warn!("Notes folder watcher error: {}", e),
+    })
+    .map_err(AppError::internal("Failed to create folder watcher"))?;
+    watcher
+        .watch(folder, RecursiveMode::NonRecursive)
+        .map_err(AppError::internal(format!("Failed to watch {}", folder.display())))?;
+
+    let task = tokio::spawn(process_changes(sync, rx, bus, debounce));
+    Ok(FolderWatcher { _watcher: watcher, task })
+}
+
+async fn process_changes(
+    sync: FolderSync,
+    mut rx: mpsc::UnboundedReceiver<PathBuf>,
+    bus: EventBus,
+    debounce: Duration,
+) {
+    while let Some(first) = rx.recv().await {
+        let mut pending = HashSet::from([first]);
+
+        // Editors save in several steps; wait until the folder is quiet
+        // and the app itself isn't halfway through a write
+        loop {
+            match tokio::time::timeout(debounce, rx.recv()).await {
+                Ok(Some(path)) => {
+                    pending.insert(path);
+                }
+                Ok(None) => return,
+                Err(_) if bus.writes_in_flight() => continue,
+                Err(_) => break,
+            }
+        }
+
+        for path in pending {
+            if let Err(e) = sync.sync_path(&path).await {
+                log::warn!("Failed to sync {}: {}", path.display(), e);
+            }
+        }
+    }
+}
+
+/// `{notes_folder}/{id}.html` → `id`. Anything else (subfolders, hidden or
+/// temporary editor files, other extensions) is not a note file.
+fn note_id_for(path: &Path, notes_folder: &Path) -> Option<String> {
+    if !same_dir(path.parent()?, notes_folder) || path.extension()? != "html" {
+        return None;
+    }
+    let id = path.file_stem()?.to_str()?;
+    (!id.is_empty() && !id.starts_with('.')).then(|| id.to_string())
+}
+
+/// Watchers may report canonical paths (e.g. `/private/var` on macOS).
+fn same_dir(a: &Path, b: &Path) -> bool {
+    a == b || matches!((a.canonicalize(), b.canonicalize()), (Ok(a), Ok(b)) if a == b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_top_level_html_files_are_notes() {
+        let folder = Path::new("/notes");
+        assert_eq!(note_id_for(Path::new("/notes/abc.html"), folder), Some("abc".to_string()));
+        assert_eq!(note_id_for(Path::new("/notes/trash/2024-01-01/abc.html"), folder), None);
+        assert_eq!(note_id_for(Path::new("/notes/attachments/abc/a.html"), folder), None);
+        assert_eq!(note_id_for(Path::new("/notes/.abc.html"), folder), None);
+        assert_eq!(note_id_for(Path::new("/notes/abc.html.swp"), folder), None);
+        assert_eq!(note_id_for(Path::new("/notes/abc.txt"), folder), None);
+    }
+}
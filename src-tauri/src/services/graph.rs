@@ -0,0 +1,308 @@
+// src-tauri/src/services/graph.rs
+//! The note graph: active notes as nodes (title, tags, notebook) and the
+//! links between them as edges (see [`links`](crate::services::links)),
+//! optionally narrowed down, for the UI or as a file for external tools.
+//!
+//! - `Dot`: Graphviz; tags and notebook as extra node attributes.
+//! - `Graphml`: yEd, Gephi, networkx.
+//! - `Json`: the [`Graph`] as returned by `get_graph`.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::db::schema::NoteFilter;
+use crate::error::{AppError, AppResult};
+use crate::services::db_storage::DbStorage;
+use crate::services::export::ExportedNote;
+use crate::services::import::html::escape;
+
+/// Hops around `GraphFilter::around` when none are given.
+pub const DEFAULT_HOPS: u32 = 1;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphFormat {
+    Dot,
+    Graphml,
+    Json,
+}
+
+/// Narrows the graph down; a note has to match every filter given.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct GraphFilter {
+    #[serde(default)]
+    pub tag: Option<String>,
+    #[serde(default)]
+    pub notebook: Option<String>,
+    /// Only notes at most `hops` links away from this one, either direction.
+    #[serde(default)]
+    pub around: Option<String>,
+    #[serde(default)]
+    pub hops: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GraphNode {
+    pub id: String,
+    pub title: String,
+    pub tags: Vec<String>,
+    pub notebook: Option<String>,
+}
+
+/// A link from `source` to `target` (note ids).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GraphEdge {
+    pub source: String,
+    pub target: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Graph {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+}
+
+pub struct NoteGraph {
+    storage: DbStorage,
+}
+
+impl NoteGraph {
+    pub fn new(storage: DbStorage) -> Self {
+        Self { storage }
+    }
+
+    /// Active, non-archived notes matching `filter` (newest first) and the
+    /// links among them. `NotFound` if `filter.around` isn't such a note.
+    pub async fn build(&self, filter: &GraphFilter) -> AppResult<Graph> {
+        let mut tags: HashMap<String, Vec<String>> = HashMap::new();
+        for (note_id, tag) in self.storage.list_all_tags().await? {
+            tags.entry(note_id).or_default().push(tag);
+        }
+        let nodes: Vec<GraphNode> = self
+            .storage
+            .list_notes(&NoteFilter::default())
+            .await?
+            .into_iter()
+            .map(|note| GraphNode {
+                tags: tags.remove(&note.id).unwrap_or_default(),
+                id: note.id,
+                title: note.title,
+                notebook: note.notebook,
+            })
+            .collect();
+        let ids: HashSet<&str> = nodes.iter().map(|node| node.id.as_str()).collect();
+        let edges: Vec<GraphEdge> = self
+            .storage
+            .link_edges()
+            .await?
+            .into_iter()
+            .filter(|(source, target)| ids.contains(source.as_str()) && ids.contains(target.as_str()))
+            .map(|(source, target)| GraphEdge { source, target })
+            .collect();
+
+        filter_graph(Graph { nodes, edges }, filter)
+    }
+
+    /// The filtered graph as a file in `format`.
+    pub async fn export(&self, filter: &GraphFilter, format: GraphFormat) -> AppResult<ExportedNote> {
+        let graph = self.build(filter).await?;
+        let (extension, mime_type, contents) = match format {
+            GraphFormat::Dot => ("dot", "text/vnd.graphviz", render_dot(&graph)),
+            GraphFormat::Graphml => ("graphml", "application/graphml+xml", render_graphml(&graph)),
+            GraphFormat::Json => (
+                "json",
+                "application/json",
+                serde_json::to_string_pretty(&graph).map_err(AppError::internal("Failed to serialize graph"))?,
+            ),
+        };
+
+        log::info!("✅ Exported note graph as {:?}: {} notes, {} links", format, graph.nodes.len(), graph.edges.len());
+        Ok(ExportedNote {
+            file_name: format!("Notes graph.{}", extension),
+            mime_type: mime_type.to_string(),
+            contents,
+        })
+    }
+}
+
+/// Keeps the nodes matching `filter` and the edges between them.
+fn filter_graph(graph: Graph, filter: &GraphFilter) -> AppResult<Graph> {
+    let nearby = match &filter.around {
+        Some(center) => Some(neighborhood(&graph, center, filter.hops.unwrap_or(DEFAULT_HOPS))?),
+        None => None,
+    };
+    let nodes: Vec<GraphNode> = graph
+        .nodes
+        .into_iter()
+        .filter(|node| filter.tag.iter().all(|tag| node.tags.contains(tag)))
+        .filter(|node| filter.notebook.is_none() || node.notebook == filter.notebook)
+        .filter(|node| nearby.iter().all(|nearby| nearby.contains(&node.id)))
+        .collect();
+    let ids: HashSet<&str> = nodes.iter().map(|node| node.id.as_str()).collect();
+    let edges = graph
+        .edges
+        .into_iter()
+        .filter(|edge| ids.contains(edge.source.as_str()) && ids.contains(edge.target.as_str()))
+        .collect();
+    Ok(Graph { nodes, edges })
+}
+
+/// Ids of the notes at most `hops` links away from `center`, itself included.
+fn neighborhood(graph: &Graph, center: &str, hops: u32) -> AppResult<HashSet<String>> {
+    if !graph.nodes.iter().any(|node| node.id == center) {
+        return Err(AppError::NotFound(format!("Note {}", center)));
+    }
+    let mut reached = HashSet::from([center.to_string()]);
+    let mut frontier = reached.clone();
+    for _ in 0..hops {
+        let mut next = HashSet::new();
+        for edge in &graph.edges {
+            for (from, to) in [(&edge.source, &edge.target), (&edge.target, &edge.source)] {
+                if frontier.contains(from) && reached.insert(to.clone()) {
+                    next.insert(to.clone());
+                }
+            }
+        }
+        if next.is_empty() {
+            break;
+        }
+        frontier = next;
+    }
+    Ok(reached)
+}
+
+fn render_dot(graph: &Graph) -> String {
+    let quote = |text: &str| format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n"));
+    let mut out = String::from("digraph notes {\n");
+    for node in &graph.nodes {
+        out.push_str(&format!("  {} [label={}", quote(&node.id), quote(&node.title)));
+        if !node.tags.is_empty() {
+            out.push_str(&format!(", tags={}", quote(&node.tags.join(", "))));
+        }
+        if let Some(notebook) = &node.notebook {
+            out.push_str(&format!(", notebook={}", quote(notebook)));
+        }
+        out.push_str("];\n");
+    }
+    for edge in &graph.edges {
+        out.push_str(&format!("  {} -> {};\n", quote(&edge.source), quote(&edge.target)));
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn render_graphml(graph: &Graph) -> String {
+    let mut out = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n  \
+         <key id=\"title\" for=\"node\" attr.name=\"title\" attr.type=\"string\"/>\n  \
+         <key id=\"tags\" for=\"node\" attr.name=\"tags\" attr.type=\"string\"/>\n  \
+         <key id=\"notebook\" for=\"node\" attr.name=\"notebook\" attr.type=\"string\"/>\n  \
+         <graph id=\"notes\" edgedefault=\"directed\">\n",
+    );
+    for node in &graph.nodes {
+        out.push_str(&format!("    <node id=\"{}\">\n", escape(&node.id)));
+        out.push_str(&format!("      <data key=\"title\">{}</data>\n", escape(&node.title)));
+        if !node.tags.is_empty() {
+            out.push_str(&format!("      <data key=\"tags\">{}</data>\n", escape(&node.tags.join(", "))));
+        }
+        if let Some(notebook) = &node.notebook {
+            out.push_str(&format!("      <data key=\"notebook\">{}</data>\n", escape(notebook)));
+        }
+        out.push_str("    </node>\n");
+    }
+    for edge in &graph.edges {
+        out.push_str(&format!(
+            "    <edge source=\"{}\" target=\"{}\"/>\n",
+            escape(&edge.source),
+            escape(&edge.target)
+        ));
+    }
+    out.push_str("  </graph>\n</graphml>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(id: &str, title: &str, tags: &[&str], notebook: Option<&str>) -> GraphNode {
+        GraphNode {
+            id: id.to_string(),
+            title: title.to_string(),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            notebook: notebook.map(str::to_string),
+        }
+    }
+
+    fn edge(source: &str, target: &str) -> GraphEdge {
+        GraphEdge { source: source.to_string(), target: target.to_string() }
+    }
+
+    /// a -> b -> c -> d, and e on its own.
+    fn chain() -> Graph {
+        Graph {
+            nodes: vec![
+                node("a", "A", &["work"], Some("Projects")),
+                node("b", "B", &[], Some("Projects")),
+                node("c", "C", &["work"], None),
+                node("d", "D", &[], None),
+                node("e", "E", &["work"], None),
+            ],
+            edges: vec![edge("a", "b"), edge("b", "c"), edge("c", "d")],
+        }
+    }
+
+    fn ids(graph: &Graph) -> Vec<&str> {
+        graph.nodes.iter().map(|node| node.id.as_str()).collect()
+    }
+
+    #[test]
+    fn neighborhood_follows_links_both_ways() {
+        let filter = GraphFilter { around: Some("c".to_string()), ..GraphFilter::default() };
+        let graph = filter_graph(chain(), &filter).unwrap();
+        assert_eq!(ids(&graph), ["b", "c", "d"]);
+        assert_eq!(graph.edges, [edge("b", "c"), edge("c", "d")]);
+
+        let filter = GraphFilter { around: Some("a".to_string()), hops: Some(2), ..GraphFilter::default() };
+        assert_eq!(ids(&filter_graph(chain(), &filter).unwrap()), ["a", "b", "c"]);
+
+        let filter = GraphFilter { around: Some("x".to_string()), ..GraphFilter::default() };
+        assert!(matches!(filter_graph(chain(), &filter), Err(AppError::NotFound(_))));
+    }
+
+    #[test]
+    fn filters_combine() {
+        let filter = GraphFilter { tag: Some("work".to_string()), ..GraphFilter::default() };
+        let graph = filter_graph(chain(), &filter).unwrap();
+        assert_eq!(ids(&graph), ["a", "c", "e"]);
+        assert!(graph.edges.is_empty());
+
+        let filter = GraphFilter {
+            tag: Some("work".to_string()),
+            notebook: Some("Projects".to_string()),
+            ..GraphFilter::default()
+        };
+        assert_eq!(ids(&filter_graph(chain(), &filter).unwrap()), ["a"]);
+    }
+
+    #[test]
+    fn renders_escaped_dot_and_graphml() {
+        let graph = Graph {
+            nodes: vec![node("a", "Say \"hi\" <now>", &["x", "y"], Some("R&D")), node("b", "B", &[], None)],
+            edges: vec![edge("a", "b")],
+        };
+
+        let dot = render_dot(&graph);
+        assert!(dot.starts_with("digraph notes {\n"));
+        assert!(dot.contains("  \"a\" [label=\"Say \\\"hi\\\" <now>\", tags=\"x, y\", notebook=\"R&D\"];\n"));
+        assert!(dot.contains("  \"b\" [label=\"B\"];\n  \"a\" -> \"b\";\n}"));
+
+        let graphml = render_graphml(&graph);
+        assert!(graphml.contains("<data key=\"title\">Say &quot;hi&quot; &lt;now&gt;</data>"));
+        assert!(graphml.contains("<data key=\"notebook\">R&amp;D</data>"));
+        assert!(graphml.contains("<edge source=\"a\" target=\"b\"/>"));
+        assert!(graphml.ends_with("</graph>\n</graphml>\n"));
+    }
+}
@@ -0,0 +1,252 @@
+// src-tauri/src/services/import/html.rs
+//! Minimal text → HTML conversion for imported bodies.
+//! Produces the same markup the editor uses (paragraphs, headings,
+//! bullet lists and TipTap task lists), nothing more.
+
+/// Escapes text for use inside HTML elements and attributes.
+pub fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Reverses [`escape`] (and turns `&nbsp;` into a space).
+pub fn unescape(text: &str) -> String {
+    text.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Renders checklist items as a TipTap task list.
+pub fn checklist(items: &[(String, bool)]) -> String {
+    let items: Vec<(String, bool)> = items
+        .iter()
+        .map(|(text, checked)| (escape(text), *checked))
+        .collect();
+    task_list(&items)
+}
+
+/// Same as [`checklist`] for items that are already HTML.
+fn task_list(items: &[(String, bool)]) -> String {
+    let mut out = String::from("<ul data-type=\"taskList\">");
+    for (html, checked) in items {
+        out.push_str(&format!(
+            "<li data-type=\"taskItem\" data-checked=\"{}\"><p>{}</p></li>",
+            checked, html
+        ));
+    }
+    out.push_str("</ul>");
+    out
+}
+
+/// Converts plain text to paragraphs. `- [ ]` / `- [x]` lines become task items.
+pub fn text_to_html(text: &str) -> String {
+    convert(text, false)
+}
+
+/// Converts the common Markdown subset (headings, bullets, task items) to HTML.
+/// Links and images become `<a>` / `<img>`; other inline formatting is kept
+/// as literal text.
+pub fn markdown_to_html(markdown: &str) -> String {
+    convert(markdown, true)
+}
+
+/// Pending list; items are already rendered inline HTML.
+enum Block {
+    None,
+    Tasks(Vec<(String, bool)>),
+    Bullets(Vec<String>),
+}
+
+fn convert(text: &str, markdown: bool) -> String {
+    let mut out = String::new();
+    let mut block = Block::None;
+
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+
+        if let Some((item, checked)) = task_item(trimmed) {
+            if !matches!(block, Block::Tasks(_)) {
+                flush(&mut out, &mut block);
+                block = Block::Tasks(Vec::new());
+            }
+            if let Block::Tasks(items) = &mut block {
+                items.push((inline(item, markdown), checked));
+            }
+            continue;
+        }
+
+        if markdown {
+            if let Some(item) = trimmed.strip_prefix("- ").or_else(|| trimmed.strip_prefix("* ")) {
+                if !matches!(block, Block::Bullets(_)) {
+                    flush(&mut out, &mut block);
+                    block = Block::Bullets(Vec::new());
+                }
+                if let Block::Bullets(items) = &mut block {
+                    items.push(inline(item, markdown));
+                }
+                continue;
+            }
+        }
+
+        flush(&mut out, &mut block);
+
+        if markdown {
+            let level = trimmed.chars().take_while(|c| *c == '#').count();
+            if (1..=6).contains(&level) && trimmed[level..].starts_with(' ') {
+                out.push_str(&format!("<h{0}>{1}</h{0}>", level, inline(trimmed[level..].trim(), markdown)));
+                continue;
+            }
+        }
+
+        if line.trim().is_empty() {
+            out.push_str("<p></p>");
+        } else {
+            out.push_str(&format!("<p>{}</p>", inline(line, markdown)));
+        }
+    }
+
+    flush(&mut out, &mut block);
+    out
+}
+
+/// Escapes a line; in Markdown mode `![alt](url)` and `[text](url)` become
+/// `<img>` / `<a>` (the URL is kept verbatim apart from escaping).
+fn inline(text: &str, markdown: bool) -> String {
+    if !markdown {
+        return escape(text);
+    }
+
+    let mut out = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('[') {
+        let image = start > 0 && rest[..start].ends_with('!');
+        let Some((label, url, len)) = link_at(&rest[start..]) else {
+            out.push_str(&escape(&rest[..=start]));
+            rest = &rest[start + 1..];
+            continue;
+        };
+
+        let before = if image { &rest[..start - 1] } else { &rest[..start] };
+        out.push_str(&escape(before));
+        if image {
+            out.push_str(&format!("<img src=\"{}\" alt=\"{}\">", escape(url), escape(label)));
+        } else {
+            out.push_str(&format!("<a href=\"{}\">{}</a>", escape(url), escape(label)));
+        }
+        rest = &rest[start + len..];
+    }
+    out.push_str(&escape(rest));
+    out
+}
+
+/// Parses `[label](url)` at the start of `text`; returns label, url and length.
+fn link_at(text: &str) -> Option<(&str, &str, usize)> {
+    let close = text.find(']')?;
+    let after = &text[close + 1..];
+    if !after.starts_with('(') {
+        return None;
+    }
+    let end = after.find(')')?;
+    let url = after[1..end].trim();
+    // Drop an optional title: [a](url "title")
+    let url = url.split_once(' ').map_or(url, |(u, _)| u);
+    if url.is_empty() {
+        return None;
+    }
+    Some((&text[1..close], url, close + 1 + end + 1))
+}
+
+fn task_item(line: &str) -> Option<(&str, bool)> {
+    let rest = line.strip_prefix("- ").or_else(|| line.strip_prefix("* "))?;
+    if let Some(item) = rest.strip_prefix("[ ] ") {
+        Some((item, false))
+    } else if let Some(item) = rest.strip_prefix("[x] ").or_else(|| rest.strip_prefix("[X] ")) {
+        Some((item, true))
+    } else {
+        None
+    }
+}
+
+fn flush(out: &mut String, block: &mut Block) {
+    match std::mem::replace(block, Block::None) {
+        Block::None => {}
+        Block::Tasks(items) => out.push_str(&task_list(&items)),
+        Block::Bullets(items) => {
+            out.push_str("<ul>");
+            for item in items {
+                out.push_str(&format!("<li><p>{}</p></li>", item));
+            }
+            out.push_str("</ul>");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_becomes_escaped_paragraphs() {
+        assert_eq!(text_to_html("a < b\n\nc"), "<p>a &lt; b</p><p></p><p>c</p>");
+    }
+
+    #[test]
+    fn text_keeps_markdown_syntax_literal() {
+        assert_eq!(text_to_html("# not a heading"), "<p># not a heading</p>");
+        assert_eq!(text_to_html("[a](b)"), "<p>[a](b)</p>");
+    }
+
+    #[test]
+    fn task_items_become_a_task_list() {
+        assert_eq!(
+            text_to_html("- [ ] milk\n- [x] eggs"),
+            "<ul data-type=\"taskList\">\
+             <li data-type=\"taskItem\" data-checked=\"false\"><p>milk</p></li>\
+             <li data-type=\"taskItem\" data-checked=\"true\"><p>eggs</p></li></ul>"
+        );
+    }
+
+    #[test]
+    fn markdown_headings_and_bullets() {
+        assert_eq!(
+            markdown_to_html("## Title\n- one\n- two\ntext"),
+            "<h2>Title</h2><ul><li><p>one</p></li><li><p>two</p></li></ul><p>text</p>"
+        );
+        assert_eq!(markdown_to_html("#hashtag"), "<p>#hashtag</p>");
+    }
+
+    #[test]
+    fn markdown_links_and_images() {
+        assert_eq!(
+            markdown_to_html("see [docs](https://x.test/?a=1&b=2) and ![cat](:/0123 \"Cat\")"),
+            "<p>see <a href=\"https://x.test/?a=1&amp;b=2\">docs</a> and <img src=\":/0123\" alt=\"cat\"></p>"
+        );
+        assert_eq!(markdown_to_html("- [ ] read [it](u)"), checklist_html("read <a href=\"u\">it</a>"));
+    }
+
+    #[test]
+    fn unmatched_brackets_stay_text() {
+        assert_eq!(markdown_to_html("a [b] c ! [d"), "<p>a [b] c ! [d</p>");
+        assert_eq!(markdown_to_html("[x]()"), "<p>[x]()</p>");
+    }
+
+    fn checklist_html(item: &str) -> String {
+        format!(
+            "<ul data-type=\"taskList\"><li data-type=\"taskItem\" data-checked=\"false\"><p>{}</p></li></ul>",
+            item
+        )
+    }
+}
@@ -0,0 +1,366 @@
+// src-tauri/src/services/import/joplin.rs
+//! Joplin exports: JEX (a tar archive) or RAW (the same files in a folder).
+//!
+//! Every item is a `{id}.md` file: the title, a blank line, the body, then a
+//! block of `key: value` metadata lines. `type_` tells items apart
+//! (1 = note, 2 = notebook, 4 = resource, 5 = tag, 6 = note ↔ tag link).
+//! Resource files live in `resources/{id}.{ext}`.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+use chrono::Utc;
+
+use super::{html, mime_from_extension, parse_iso, ImportBatch, ImportIssue, ImportedAttachment, ImportedNote, Importer};
+use crate::error::{AppError, AppResult};
+
+pub struct JoplinImporter;
+
+const TYPE_NOTE: &str = "1";
+const TYPE_FOLDER: &str = "2";
+const TYPE_RESOURCE: &str = "4";
+const TYPE_TAG: &str = "5";
+const TYPE_NOTE_TAG: &str = "6";
+
+/// One parsed `{id}.md` item.
+struct Item {
+    title: String,
+    body: String,
+    props: HashMap<String, String>,
+}
+
+impl Item {
+    fn prop(&self, key: &str) -> Option<&str> {
+        self.props.get(key).map(|s| s.as_str()).filter(|s| !s.is_empty())
+    }
+}
+
+impl Importer for JoplinImporter {
+    fn source(&self) -> &'static str {
+        "joplin"
+    }
+
+    fn read(&self, path: &Path) -> AppResult<ImportBatch> {
+        let files = if path.is_dir() {
+            read_raw_dir(path)?
+        } else {
+            read_jex(path)?
+        };
+
+        let mut batch = ImportBatch::default();
+        let mut items = Vec::new();
+        for (name, data) in &files {
+            if name.contains('/') || !name.ends_with(".md") {
+                continue;
+            }
+            match String::from_utf8(data.clone()) {
+                Ok(text) => items.push(parse_item(&text)),
+                Err(_) => batch.errors.push(ImportIssue {
+                    item: name.clone(),
+                    message: "File is not valid UTF-8".to_string(),
+                }),
+            }
+        }
+
+        let by_type = |t: &'static str| items.iter().filter(move |i| i.prop("type_") == Some(t));
+
+        let folders: HashMap<&str, &Item> = by_type(TYPE_FOLDER)
+            .filter_map(|i| Some((i.prop("id")?, i)))
+            .collect();
+        let tags: HashMap<&str, &str> = by_type(TYPE_TAG)
+            .filter_map(|i| Some((i.prop("id")?, i.title.as_str())))
+            .collect();
+        let resources: HashMap<&str, &Item> = by_type(TYPE_RESOURCE)
+            .filter_map(|i| Some((i.prop("id")?, i)))
+            .collect();
+
+        let mut note_tags: HashMap<&str, Vec<String>> = HashMap::new();
+        for link in by_type(TYPE_NOTE_TAG) {
+            if let (Some(note_id), Some(tag)) = (link.prop("note_id"), link.prop("tag_id").and_then(|t| tags.get(t))) {
+                note_tags.entry(note_id).or_default().push(tag.to_string());
+            }
+        }
+
+        let now = Utc::now().timestamp();
+        for item in by_type(TYPE_NOTE) {
+            let Some(id) = item.prop("id") else {
+                batch.errors.push(ImportIssue {
+                    item: item.title.clone(),
+                    message: "Note has no id".to_string(),
+                });
+                continue;
+            };
+
+            let mut attachments = Vec::new();
+            for resource_id in resource_ids(&item.body) {
+                match resources.get(resource_id.as_str()).and_then(|r| load_resource(&files, &resource_id, r)) {
+                    Some(attachment) => attachments.push(attachment),
+                    None => batch.errors.push(ImportIssue {
+                        item: item.title.clone(),
+                        message: format!("Resource {} missing from export", resource_id),
+                    }),
+                }
+            }
+
+            let created_at = item.prop("created_time").and_then(parse_iso).unwrap_or(now);
+            let updated_at = item.prop("updated_time").and_then(parse_iso).unwrap_or(created_at);
+            let trashed = item.prop("deleted_time").is_some_and(|t| t != "0");
+
+            batch.notes.push(ImportedNote {
+                source_id: id.to_string(),
+                id: None,
+                title: item.title.clone(),
+                body: html::markdown_to_html(&item.body),
+                created_at,
+                updated_at,
+                tags: note_tags.remove(id).unwrap_or_default(),
+                notebook: item.prop("parent_id").and_then(|p| folder_path(&folders, p)),
+                archived: false,
+                trashed,
+                attachments,
+            });
+        }
+
+        Ok(batch)
+    }
+}
+
+/// RAW export: `*.md` items plus a `resources/` folder.
+fn read_raw_dir(dir: &Path) -> AppResult<HashMap<String, Vec<u8>>> {
+    let mut files = HashMap::new();
+
+    let mut collect = |folder: &Path, prefix: &str| -> AppResult<()> {
+        let entries = fs::read_dir(folder)
+            .map_err(AppError::io(format!("Failed to read {}", folder.display())))?;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_file() {
+                let name = format!("{}{}", prefix, entry.file_name().to_string_lossy());
+                let data = fs::read(&path)
+                    .map_err(AppError::io(format!("Failed to read {}", path.display())))?;
+                files.insert(name, data);
+            }
+        }
+        Ok(())
+    };
+
+    collect(dir, "")?;
+    let resources = dir.join("resources");
+    if resources.is_dir() {
+        collect(&resources, "resources/")?;
+    }
+
+    Ok(files)
+}
+
+/// JEX export: a plain tar archive with the RAW layout inside.
+fn read_jex(file: &Path) -> AppResult<HashMap<String, Vec<u8>>> {
+    let archive = fs::File::open(file)
+        .map_err(AppError::io(format!("Failed to open {}", file.display())))?;
+    let mut archive = tar::Archive::new(archive);
+    let entries = archive
+        .entries()
+        .map_err(AppError::io("Not a JEX archive"))?;
+
+    let mut files = HashMap::new();
+    for entry in entries {
+        let mut entry = entry.map_err(AppError::io("Corrupt JEX archive"))?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let name = entry
+            .path()
+            .map_err(AppError::io("Corrupt JEX archive"))?
+            .to_string_lossy()
+            .trim_start_matches("./")
+            .to_string();
+        let mut data = Vec::new();
+        entry
+            .read_to_end(&mut data)
+            .map_err(AppError::io(format!("Failed to read {} from archive", name)))?;
+        files.insert(name, data);
+    }
+
+    Ok(files)
+}
+
+/// Splits an item into title, body and the trailing `key: value` block.
+fn parse_item(text: &str) -> Item {
+    let text = text.replace("\r\n", "\n");
+    let lines: Vec<&str> = text.trim_end().lines().collect();
+
+    let mut props = HashMap::new();
+    let mut end = lines.len();
+    while end > 0 {
+        let line = lines[end - 1];
+        match line.split_once(": ").or_else(|| line.strip_suffix(':').map(|k| (k, ""))) {
+            Some((key, value)) if !key.is_empty() && key.chars().all(|c| c.is_ascii_lowercase() || c == '_') => {
+                props.insert(key.to_string(), value.to_string());
+                end -= 1;
+            }
+            _ => break,
+        }
+    }
+
+    let content = &lines[..end];
+    let title = content.first().map(|l| l.trim().to_string()).unwrap_or_default();
+    let body = content
+        .iter()
+        .skip(1)
+        .copied()
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_matches('\n')
+        .to_string();
+
+    Item { title, body, props }
+}
+
+/// Resource ids referenced as `:/<32 hex chars>` in a Markdown body.
+fn resource_ids(body: &str) -> Vec<String> {
+    let mut ids = Vec::new();
+    for (pos, _) in body.match_indices(":/") {
+        let candidate: String = body[pos + 2..].chars().take(32).collect();
+        if candidate.len() == 32 && candidate.chars().all(|c| c.is_ascii_hexdigit()) && !ids.contains(&candidate) {
+            ids.push(candidate);
+        }
+    }
+    ids
+}
+
+fn load_resource(files: &HashMap<String, Vec<u8>>, id: &str, resource: &Item) -> Option<ImportedAttachment> {
+    let ext = resource.prop("file_extension").unwrap_or("");
+    let stored = if ext.is_empty() {
+        files.get(&format!("resources/{}", id))
+    } else {
+        files.get(&format!("resources/{}.{}", id, ext))
+    }?;
+
+    let file_name = match (resource.title.is_empty(), ext.is_empty()) {
+        (false, _) => resource.title.clone(),
+        (true, true) => id.to_string(),
+        (true, false) => format!("{}.{}", id, ext),
+    };
+
+    Some(ImportedAttachment {
+        mime_type: resource.prop("mime").map(str::to_string).or_else(|| mime_from_extension(&file_name)),
+        file_name,
+        data: stored.clone(),
+        references: vec![format!(":/{}", id)],
+        original_path: None,
+        id: None,
+        created_at: None,
+    })
+}
+
+/// Full notebook path (`Work/Projects`), following `parent_id` upwards.
+fn folder_path(folders: &HashMap<&str, &Item>, id: &str) -> Option<String> {
+    let mut parts = Vec::new();
+    let mut current = Some(id);
+    while let Some(folder) = current.and_then(|id| folders.get(id)) {
+        parts.push(folder.title.clone());
+        current = folder.prop("parent_id");
+        if parts.len() > 32 {
+            break; // guard against cyclic parents in broken exports
+        }
+    }
+
+    if parts.is_empty() {
+        return None;
+    }
+    parts.reverse();
+    Some(parts.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOTE_ID: &str = "0123456789abcdef0123456789abcdef";
+    const FOLDER_ID: &str = "11111111111111111111111111111111";
+    const RESOURCE_ID: &str = "22222222222222222222222222222222";
+
+    fn scratch_dir() -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("rusty-notes-joplin-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(dir.join("resources")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn parse_item_splits_title_body_and_props() {
+        let item = parse_item("Title\r\n\r\nLine one\n\nkey: not a prop\n\nid: abc\nparent_id:\ntype_: 1\n");
+        assert_eq!(item.title, "Title");
+        assert_eq!(item.body, "Line one\n\nkey: not a prop");
+        assert_eq!(item.prop("id"), Some("abc"));
+        assert_eq!(item.prop("type_"), Some("1"));
+        assert_eq!(item.prop("parent_id"), None);
+    }
+
+    #[test]
+    fn resource_ids_are_deduplicated() {
+        let body = format!("![a](:/{0}) [b](:/{0}) :/tooShort", RESOURCE_ID);
+        assert_eq!(resource_ids(&body), vec![RESOURCE_ID.to_string()]);
+    }
+
+    #[test]
+    fn reads_raw_export_with_notebook_tags_and_resources() {
+        let dir = scratch_dir();
+        fs::write(
+            dir.join(format!("{}.md", NOTE_ID)),
+            format!(
+                "Shopping\n\n# List\n- [x] milk\n![photo](:/{}) \n\nid: {}\nparent_id: {}\ncreated_time: 2024-01-02T03:04:05.000Z\ndeleted_time: 0\ntype_: 1",
+                RESOURCE_ID, NOTE_ID, FOLDER_ID
+            ),
+        )
+        .unwrap();
+        fs::write(dir.join(format!("{}.md", FOLDER_ID)), format!("Home\n\nid: {}\ntype_: 2", FOLDER_ID)).unwrap();
+        fs::write(
+            dir.join(format!("{}.md", RESOURCE_ID)),
+            format!("photo.png\n\nid: {}\nmime: image/png\nfile_extension: png\ntype_: 4", RESOURCE_ID),
+        )
+        .unwrap();
+        fs::write(dir.join("resources").join(format!("{}.png", RESOURCE_ID)), b"png").unwrap();
+        fs::write(dir.join("t.md"), "errands\n\nid: tag1\ntype_: 5").unwrap();
+        fs::write(dir.join("nt.md"), format!("\n\nid: nt1\nnote_id: {}\ntag_id: tag1\ntype_: 6", NOTE_ID)).unwrap();
+
+        let batch = JoplinImporter.read(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(batch.errors.is_empty());
+        assert_eq!(batch.notes.len(), 1);
+        let note = &batch.notes[0];
+        assert_eq!(note.source_id, NOTE_ID);
+        assert_eq!(note.title, "Shopping");
+        assert_eq!(note.notebook.as_deref(), Some("Home"));
+        assert_eq!(note.tags, vec!["errands".to_string()]);
+        assert_eq!(note.created_at, 1_704_164_645);
+        assert!(!note.trashed);
+        assert!(note.body.starts_with("<h1>List</h1><ul data-type=\"taskList\">"));
+        assert!(note.body.contains(&format!("<img src=\":/{}\" alt=\"photo\">", RESOURCE_ID)));
+
+        let attachment = &note.attachments[0];
+        assert_eq!(attachment.file_name, "photo.png");
+        assert_eq!(attachment.mime_type.as_deref(), Some("image/png"));
+        assert_eq!(attachment.data, b"png");
+        assert_eq!(attachment.references, vec![format!(":/{}", RESOURCE_ID)]);
+    }
+
+    #[test]
+    fn missing_resource_is_reported() {
+        let dir = scratch_dir();
+        fs::write(
+            dir.join(format!("{}.md", NOTE_ID)),
+            format!("Note\n\n![x](:/{})\n\nid: {}\ntype_: 1", RESOURCE_ID, NOTE_ID),
+        )
+        .unwrap();
+
+        let batch = JoplinImporter.read(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(batch.notes.len(), 1);
+        assert_eq!(batch.errors.len(), 1);
+        assert!(batch.errors[0].message.contains(RESOURCE_ID));
+    }
+}
@@ -0,0 +1,93 @@
+// src-tauri/src/services/import/json.rs
+//! Re-imports notes written by `export_note(.., ExportFormat::Json)`.
+//! Accepts a single `.json` file or a folder of them.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+
+use super::{ImportBatch, ImportIssue, ImportedAttachment, ImportedNote, Importer};
+use crate::services::export::{NoteExport, NOTE_EXPORT_FORMAT, NOTE_EXPORT_VERSION};
+use crate::error::{AppError, AppResult};
+
+pub struct NoteJsonImporter;
+
+impl Importer for NoteJsonImporter {
+    fn source(&self) -> &'static str {
+        "rusty-notes"
+    }
+
+    fn read(&self, path: &Path) -> AppResult<ImportBatch> {
+        let files: Vec<PathBuf> = if path.is_dir() {
+            let mut files: Vec<PathBuf> = fs::read_dir(path)
+                .map_err(AppError::io(format!("Failed to read {}", path.display())))?
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("json"))
+                .collect();
+            files.sort();
+            files
+        } else {
+            vec![path.to_path_buf()]
+        };
+
+        let mut batch = ImportBatch::default();
+        for file in files {
+            let name = file.file_name().unwrap_or_default().to_string_lossy().to_string();
+            match read_export(&file) {
+                Ok(note) => batch.notes.push(note),
+                Err(e) => batch.errors.push(ImportIssue { item: name, message: e.to_string() }),
+            }
+        }
+
+        Ok(batch)
+    }
+}
+
+fn read_export(file: &Path) -> AppResult<ImportedNote> {
+    let contents = fs::read_to_string(file).map_err(AppError::io("Failed to read file"))?;
+    let mut export: NoteExport = serde_json::from_str(&contents)
+        .map_err(AppError::invalid("Not a rusty-notes export"))?;
+
+    if export.format != NOTE_EXPORT_FORMAT {
+        return Err(AppError::Validation(format!("Unknown export format: {}", export.format)));
+    }
+    if export.version > NOTE_EXPORT_VERSION {
+        return Err(AppError::Validation(format!("Export version {} is newer than this app supports", export.version)));
+    }
+
+    let mut attachments = Vec::new();
+    for attachment in export.note.attachments {
+        let Some(encoded) = export.attachment_contents.remove(&attachment.id) else {
+            return Err(AppError::Validation(format!("Missing contents for attachment {}", attachment.file_name)));
+        };
+        let data = BASE64
+            .decode(encoded)
+            .map_err(AppError::invalid(format!("Invalid attachment data for {}", attachment.file_name)))?;
+        attachments.push(ImportedAttachment {
+            file_name: attachment.file_name,
+            mime_type: attachment.mime_type,
+            data,
+            references: Vec::new(),
+            original_path: Some(attachment.file_path),
+            id: Some(attachment.id),
+            created_at: Some(attachment.created_at),
+        });
+    }
+
+    let note = export.note.note;
+    Ok(ImportedNote {
+        source_id: note.id.clone(),
+        id: Some(note.id),
+        title: note.title,
+        body: note.body,
+        created_at: note.created_at,
+        updated_at: note.updated_at,
+        tags: export.tags,
+        notebook: note.notebook,
+        archived: note.archived,
+        trashed: false,
+        attachments,
+    })
+}
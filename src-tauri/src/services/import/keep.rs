@@ -0,0 +1,262 @@
+// src-tauri/src/services/import/keep.rs
+//! Google Keep from Google Takeout: a `Keep/` folder with one JSON file
+//! per note, plus the attachment files those JSON files reference.
+
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use serde::Deserialize;
+
+use super::{html, mime_from_extension, ImportBatch, ImportIssue, ImportedAttachment, ImportedNote, Importer};
+use crate::error::{AppError, AppResult};
+
+pub struct KeepImporter;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct KeepNote {
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    text_content: String,
+    #[serde(default)]
+    list_content: Vec<KeepListItem>,
+    #[serde(default)]
+    labels: Vec<KeepLabel>,
+    #[serde(default)]
+    attachments: Vec<KeepAttachment>,
+    #[serde(default)]
+    is_trashed: bool,
+    #[serde(default)]
+    is_archived: bool,
+    #[serde(default)]
+    created_timestamp_usec: i64,
+    #[serde(default)]
+    user_edited_timestamp_usec: i64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct KeepListItem {
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    is_checked: bool,
+}
+
+#[derive(Deserialize)]
+struct KeepLabel {
+    name: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct KeepAttachment {
+    file_path: String,
+    mimetype: Option<String>,
+}
+
+impl Importer for KeepImporter {
+    fn source(&self) -> &'static str {
+        "keep"
+    }
+
+    fn read(&self, path: &Path) -> AppResult<ImportBatch> {
+        if !path.is_dir() {
+            return Err(AppError::Validation(format!("Expected the Takeout Keep folder, got {}", path.display())));
+        }
+
+        let mut files: Vec<PathBuf> = fs::read_dir(path)
+            .map_err(AppError::io(format!("Failed to read {}", path.display())))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("json"))
+            .collect();
+        files.sort();
+
+        let mut batch = ImportBatch::default();
+        for file in files {
+            let name = file.file_name().unwrap_or_default().to_string_lossy().to_string();
+            match read_note(path, &file) {
+                Ok(Some((note, issues))) => {
+                    batch.notes.push(note);
+                    batch.errors.extend(issues);
+                }
+                Ok(None) => {}
+                Err(e) => batch.errors.push(ImportIssue { item: name, message: e.to_string() }),
+            }
+        }
+
+        Ok(batch)
+    }
+}
+
+/// Returns `None` for empty notes; missing attachments are reported as issues.
+fn read_note(dir: &Path, file: &Path) -> AppResult<Option<(ImportedNote, Vec<ImportIssue>)>> {
+    let contents = fs::read_to_string(file).map_err(AppError::io("Failed to read file"))?;
+    let keep: KeepNote = serde_json::from_str(&contents).map_err(AppError::invalid("Invalid Keep note"))?;
+
+    if keep.title.is_empty() && keep.text_content.is_empty() && keep.list_content.is_empty() && keep.attachments.is_empty() {
+        return Ok(None);
+    }
+
+    let body = if keep.list_content.is_empty() {
+        html::text_to_html(&keep.text_content)
+    } else {
+        let items: Vec<(String, bool)> = keep.list_content
+            .into_iter()
+            .map(|item| (item.text, item.is_checked))
+            .collect();
+        html::checklist(&items)
+    };
+
+    let mut issues = Vec::new();
+    let mut attachments = Vec::new();
+    for attachment in keep.attachments {
+        match read_attachment(dir, &attachment.file_path) {
+            Ok((file_name, data)) => attachments.push(ImportedAttachment {
+                mime_type: attachment.mimetype.or_else(|| mime_from_extension(&file_name)),
+                file_name,
+                data,
+                references: Vec::new(),
+                original_path: None,
+                id: None,
+                created_at: None,
+            }),
+            Err(e) => issues.push(ImportIssue {
+                item: attachment.file_path,
+                message: e.to_string(),
+            }),
+        }
+    }
+
+    let created_at = keep.created_timestamp_usec / 1_000_000;
+    let updated_at = match keep.user_edited_timestamp_usec {
+        0 => created_at,
+        usec => usec / 1_000_000,
+    };
+
+    let note = ImportedNote {
+        source_id: file.file_stem().unwrap_or_default().to_string_lossy().to_string(),
+        id: None,
+        title: keep.title,
+        body,
+        created_at,
+        updated_at,
+        tags: keep.labels.into_iter().map(|l| l.name).collect(),
+        notebook: None,
+        archived: keep.is_archived,
+        trashed: keep.is_trashed,
+        attachments,
+    };
+
+    Ok(Some((note, issues)))
+}
+
+/// Takeout sometimes names a file `.jpg` while the JSON says `.jpeg` (or the reverse).
+/// Only plain file names next to the JSON are accepted, never paths.
+fn read_attachment(dir: &Path, file_path: &str) -> AppResult<(String, Vec<u8>)> {
+    if !is_plain_file_name(file_path) {
+        return Err(AppError::Validation("Attachment path is not a plain file name".to_string()));
+    }
+
+    let direct = dir.join(file_path);
+    let swapped = if let Some(stem) = file_path.strip_suffix(".jpeg") {
+        Some(dir.join(format!("{}.jpg", stem)))
+    } else {
+        file_path.strip_suffix(".jpg").map(|stem| dir.join(format!("{}.jpeg", stem)))
+    };
+
+    let path = [Some(direct), swapped]
+        .into_iter()
+        .flatten()
+        .find(|p| p.is_file())
+        .ok_or_else(|| AppError::NotFound("Attachment file in export".to_string()))?;
+    let data = fs::read(&path).map_err(AppError::io("Failed to read attachment"))?;
+    let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+    Ok((name, data))
+}
+
+/// A single normal path component: no separators, `..`, roots or drive prefixes.
+fn is_plain_file_name(name: &str) -> bool {
+    let mut components = Path::new(name).components();
+    matches!(components.next(), Some(Component::Normal(_)))
+        && components.next().is_none()
+        && !name.contains(['/', '\\', ':'])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("rusty-notes-keep-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn reads_checklist_labels_and_attachments() {
+        let dir = scratch_dir();
+        fs::write(
+            dir.join("Groceries.json"),
+            r#"{
+                "title": "Groceries",
+                "listContent": [{"text": "milk", "isChecked": true}, {"text": "eggs", "isChecked": false}],
+                "labels": [{"name": "home"}],
+                "attachments": [{"filePath": "photo.jpeg", "mimetype": "image/jpeg"}],
+                "isArchived": true,
+                "createdTimestampUsec": 1704164645000000
+            }"#,
+        )
+        .unwrap();
+        // Takeout wrote .jpg although the JSON says .jpeg
+        fs::write(dir.join("photo.jpg"), b"jpg").unwrap();
+
+        let batch = KeepImporter.read(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(batch.errors.is_empty());
+        let note = &batch.notes[0];
+        assert_eq!(note.source_id, "Groceries");
+        assert_eq!(note.tags, vec!["home".to_string()]);
+        assert!(note.archived);
+        assert_eq!(note.created_at, 1_704_164_645);
+        assert_eq!(note.updated_at, note.created_at);
+        assert_eq!(note.body, html::checklist(&[("milk".to_string(), true), ("eggs".to_string(), false)]));
+        assert_eq!(note.attachments[0].file_name, "photo.jpg");
+        assert_eq!(note.attachments[0].data, b"jpg");
+    }
+
+    #[test]
+    fn skips_empty_notes_and_reports_bad_json() {
+        let dir = scratch_dir();
+        fs::write(dir.join("empty.json"), "{}").unwrap();
+        fs::write(dir.join("broken.json"), "{").unwrap();
+
+        let batch = KeepImporter.read(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(batch.notes.is_empty());
+        assert_eq!(batch.errors.len(), 1);
+        assert_eq!(batch.errors[0].item, "broken.json");
+    }
+
+    #[test]
+    fn attachment_paths_outside_the_export_are_rejected() {
+        let dir = scratch_dir();
+        let outside = dir.join("secret.txt");
+        fs::write(&outside, b"secret").unwrap();
+        let keep_dir = dir.join("Keep");
+        fs::create_dir_all(&keep_dir).unwrap();
+
+        let rejected = [
+            "../secret.txt".to_string(),
+            outside.to_string_lossy().to_string(),
+            "sub/../../secret.txt".to_string(),
+        ];
+        for path in &rejected {
+            assert!(read_attachment(&keep_dir, path).is_err(), "{} was accepted", path);
+        }
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,363 @@
+// src-tauri/src/services/import/mod.rs
+//! Importers for other note apps.
+//!
+//! Each format only knows how to *read* its export into [`ImportedNote`]s.
+//! [`ImportService`] does the rest (dedup, DB + file writes, attachments,
+//! progress and error reporting) so all formats behave the same.
+
+pub mod html;
+pub mod joplin;
+pub mod json;
+pub mod keep;
+pub mod simplenote;
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::db::schema::{Attachment, Note};
+use crate::services::db_storage::DbStorage;
+use crate::services::note_files::{attachment_type_for, rewrite_references, NoteFileStore};
+use crate::settings::Settings;
+use crate::error::{AppError, AppResult};
+
+pub use joplin::JoplinImporter;
+pub use json::NoteJsonImporter;
+pub use keep::KeepImporter;
+pub use simplenote::SimplenoteImporter;
+
+/// A note read from an external export, before it touches the DB.
+#[derive(Debug, Clone, Default)]
+pub struct ImportedNote {
+    /// Id of the item in the source app (or file name when it has none).
+    pub source_id: String,
+    /// Note id to keep, if still free (lossless re-import of our own exports).
+    pub id: Option<String>,
+    pub title: String,
+    /// HTML body, same format the editor writes to `{id}.html`.
+    pub body: String,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub tags: Vec<String>,
+    pub notebook: Option<String>,
+    pub archived: bool,
+    pub trashed: bool,
+    pub attachments: Vec<ImportedAttachment>,
+}
+
+/// Attachment bytes read from an export.
+#[derive(Debug, Clone)]
+pub struct ImportedAttachment {
+    /// Name from the export; reduced to its last path component on write.
+    pub file_name: String,
+    pub mime_type: Option<String>,
+    pub data: Vec<u8>,
+    /// Strings in the body that point at this attachment (e.g. Joplin's
+    /// `:/<id>`); replaced with the stored file path.
+    pub references: Vec<String>,
+    /// Path the attachment had in the exporting app (our JSON export);
+    /// body references to it in any form are rewritten to the new path.
+    pub original_path: Option<String>,
+    /// Attachment id to keep, if still free.
+    pub id: Option<String>,
+    /// Defaults to the note's `created_at`.
+    pub created_at: Option<i64>,
+}
+
+/// Result of reading an export: the notes that parsed plus per-item problems.
+#[derive(Debug, Default)]
+pub struct ImportBatch {
+    pub notes: Vec<ImportedNote>,
+    pub errors: Vec<ImportIssue>,
+}
+
+/// One item that could not be read or written.
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportIssue {
+    /// Title, file name or source id of the failing item.
+    pub item: String,
+    pub message: String,
+}
+
+/// Progress emitted after every processed item.
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportProgress {
+    pub source: String,
+    pub processed: usize,
+    pub total: usize,
+    pub current: Option<String>,
+}
+
+/// Summary returned to the frontend when an import finishes.
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportReport {
+    pub source: String,
+    pub total: usize,
+    pub imported: usize,
+    pub skipped_duplicates: usize,
+    pub errors: Vec<ImportIssue>,
+}
+
+/// A source format that can be read into [`ImportedNote`]s.
+pub trait Importer {
+    /// Stable identifier stored in `import_sources.source` (e.g. `keep`).
+    fn source(&self) -> &'static str;
+
+    /// Reads the export at `path` (file or extracted folder).
+    /// Returns `Err` only when nothing can be read at all.
+    fn read(&self, path: &Path) -> AppResult<ImportBatch>;
+}
+
+/// Formats selectable from the frontend.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportFormat {
+    Joplin,
+    Simplenote,
+    Keep,
+    /// Our own lossless JSON export (`export_note` with `ExportFormat::Json`).
+    Json,
+}
+
+impl ImportFormat {
+    pub fn importer(self) -> Box<dyn Importer + Send + Sync> {
+        match self {
+            ImportFormat::Joplin => Box::new(JoplinImporter),
+            ImportFormat::Simplenote => Box::new(SimplenoteImporter),
+            ImportFormat::Keep => Box::new(KeepImporter),
+            ImportFormat::Json => Box::new(NoteJsonImporter),
+        }
+    }
+}
+
+/// Writes imported notes through the same storage as the editor.
+pub struct ImportService {
+    storage: DbStorage,
+    settings: Arc<Mutex<Settings>>,
+}
+
+impl ImportService {
+    pub fn new(storage: DbStorage, settings: Arc<Mutex<Settings>>) -> Self {
+        Self { storage, settings }
+    }
+
+    /// Imports everything `importer` reads from `path`.
+    /// Duplicates (already imported, or same title + body) are skipped;
+    /// failing items are collected in the report instead of aborting.
+    pub async fn run<F>(
+        &self,
+        importer: Box<dyn Importer + Send + Sync>,
+        path: PathBuf,
+        mut on_progress: F,
+    ) -> AppResult<ImportReport>
+    where
+        F: FnMut(&ImportProgress),
+    {
+        let source = importer.source();
+        // Parsing is blocking file I/O (large archives); keep it off the async runtime
+        let batch = tokio::task::spawn_blocking(move || importer.read(&path))
+            .await
+            .map_err(AppError::internal("Import task failed"))??;
+
+        let notes_folder = self.settings.lock().await.notes_folder.clone();
+        let file_store = NoteFileStore::new(notes_folder, self.storage.keyring().clone());
+        file_store.ensure_folder_exists().await?;
+
+        let mut report = ImportReport {
+            source: source.to_string(),
+            total: batch.notes.len(),
+            imported: 0,
+            skipped_duplicates: 0,
+            errors: batch.errors,
+        };
+
+        for (index, item) in batch.notes.into_iter().enumerate() {
+            let label = if item.title.is_empty() { item.source_id.clone() } else { item.title.clone() };
+
+            match self.import_one(source, &file_store, item).await {
+                Ok(true) => report.imported += 1,
+                Ok(false) => report.skipped_duplicates += 1,
+                Err(e) => report.errors.push(ImportIssue { item: label.clone(), message: e.to_string() }),
+            }
+
+            on_progress(&ImportProgress {
+                source: source.to_string(),
+                processed: index + 1,
+                total: report.total,
+                current: Some(label),
+            });
+        }
+
+        log::info!(
+            "✅ Imported {} notes from {} ({} duplicates, {} errors)",
+            report.imported, source, report.skipped_duplicates, report.errors.len()
+        );
+        Ok(report)
+    }
+
+    /// Returns `Ok(false)` when the item was a duplicate.
+    async fn import_one(
+        &self,
+        source: &str,
+        file_store: &NoteFileStore,
+        item: ImportedNote,
+    ) -> AppResult<bool> {
+        let _writes = self.storage.lock_writes().await;
+        let storage = &self.storage;
+
+        if storage.find_imported(source, &item.source_id).await?.is_some() {
+            return Ok(false);
+        }
+        if let Some(existing) = storage.find_note_by_content(&item.title, &item.body).await? {
+            let now = Utc::now().timestamp();
+            storage.record_import(source, &item.source_id, &existing.id, now).await?;
+            return Ok(false);
+        }
+
+        let id = match item.id.clone() {
+            Some(id) if !storage.note_exists(&id).await? => id,
+            _ => Uuid::new_v4().to_string(),
+        };
+
+        if let Err(e) = write_imported(storage, file_store, source, &id, item).await {
+            // Undo the partial import so a retry starts from a clean slate
+            let _ = storage.delete_note(&id).await;
+            let _ = tokio::fs::remove_file(file_store.path_for_id(&id)).await;
+            let _ = tokio::fs::remove_dir_all(file_store.attachments_dir(&id)).await;
+            return Err(e);
+        }
+        Ok(true)
+    }
+}
+
+/// Files first, then rows: a failure never leaves a row without its file.
+async fn write_imported(
+    storage: &DbStorage,
+    file_store: &NoteFileStore,
+    source: &str,
+    id: &str,
+    item: ImportedNote,
+) -> AppResult<()> {
+    let mut body = item.body;
+    let mut attachments = Vec::new();
+    for attachment in item.attachments {
+        let file_name = sanitize_file_name(&attachment.file_name);
+        let path = file_store.write_attachment(id, &file_name, &attachment.data).await?;
+        for reference in &attachment.references {
+            body = body.replace(reference.as_str(), &html::escape(&path));
+        }
+        if let Some(original) = &attachment.original_path {
+            body = rewrite_references(&body, original, &path);
+        }
+
+        let attachment_id = match attachment.id {
+            Some(existing) if !storage.attachment_exists(&existing).await? => existing,
+            _ => Uuid::new_v4().to_string(),
+        };
+        attachments.push(Attachment {
+            id: attachment_id,
+            note_id: id.to_string(),
+            attachment_type: attachment_type_for(attachment.mime_type.as_deref()).to_string(),
+            file_name,
+            file_path: path,
+            mime_type: attachment.mime_type,
+            size_bytes: Some(attachment.data.len() as i64),
+            created_at: attachment.created_at.unwrap_or(item.created_at),
+        });
+    }
+
+    let note = Note {
+        id: id.to_string(),
+        title: item.title,
+        body,
+        created_at: item.created_at,
+        updated_at: item.updated_at,
+        file_path: file_store.path_for_id(id),
+        notebook: item.notebook,
+        version: 0,
+        pinned: false,
+        favorite: false,
+        archived: false,
+    };
+    file_store.write_note_file(&note.file_path, &note.body).await?;
+    storage.create_note(note).await?;
+
+    for attachment in attachments {
+        storage.create_attachment(attachment).await?;
+    }
+    if !item.tags.is_empty() {
+        storage.set_note_tags(id, &item.tags).await?;
+    }
+    if item.archived {
+        storage.set_note_archived(id, true).await?;
+    }
+    if item.trashed {
+        storage.set_note_deleted(id, true).await?;
+    }
+
+    storage.record_import(source, &item.source_id, id, Utc::now().timestamp()).await
+}
+
+/// Last path component of a name taken from an export, so it can never
+/// point outside the note's attachments folder.
+pub(crate) fn sanitize_file_name(name: &str) -> String {
+    let name = name.rsplit(['/', '\\']).next().unwrap_or_default().trim();
+    match name {
+        "" | "." | ".." => "attachment".to_string(),
+        name => name.replace(':', "_"),
+    }
+}
+
+/// Parses an RFC 3339 / ISO 8601 timestamp into Unix seconds.
+pub(crate) fn parse_iso(value: &str) -> Option<i64> {
+    DateTime::parse_from_rfc3339(value.trim())
+        .ok()
+        .map(|dt| dt.timestamp())
+}
+
+/// Best-effort MIME type from a file extension.
+pub(crate) fn mime_from_extension(file_name: &str) -> Option<String> {
+    let ext = Path::new(file_name).extension()?.to_str()?.to_ascii_lowercase();
+    let mime = match ext.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "pdf" => "application/pdf",
+        "mp3" => "audio/mpeg",
+        "m4a" => "audio/mp4",
+        "ogg" => "audio/ogg",
+        "wav" => "audio/wav",
+        "3gp" => "audio/3gpp",
+        "mp4" => "video/mp4",
+        "txt" => "text/plain",
+        _ => return None,
+    };
+    Some(mime.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitize_file_name_keeps_only_the_last_component() {
+        assert_eq!(sanitize_file_name("photo.png"), "photo.png");
+        assert_eq!(sanitize_file_name("../../etc/passwd"), "passwd");
+        assert_eq!(sanitize_file_name("C:\\Users\\me\\a.txt"), "a.txt");
+        assert_eq!(sanitize_file_name("a:b.txt"), "a_b.txt");
+        assert_eq!(sanitize_file_name(".."), "attachment");
+        assert_eq!(sanitize_file_name("dir/"), "attachment");
+    }
+
+    #[test]
+    fn parse_iso_accepts_rfc3339() {
+        assert_eq!(parse_iso(" 2024-01-02T03:04:05Z "), Some(1_704_164_645));
+        assert_eq!(parse_iso("yesterday"), None);
+    }
+}
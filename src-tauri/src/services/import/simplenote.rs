@@ -0,0 +1,163 @@
+// src-tauri/src/services/import/simplenote.rs
+//! Simplenote JSON export (`notes.json`, from "Export Notes" → `source/notes.json`).
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+use serde::Deserialize;
+
+use super::{html, parse_iso, ImportBatch, ImportedNote, Importer};
+use crate::error::{AppError, AppResult};
+
+pub struct SimplenoteImporter;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Export {
+    #[serde(default)]
+    active_notes: Vec<SimplenoteNote>,
+    #[serde(default)]
+    trashed_notes: Vec<SimplenoteNote>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SimplenoteNote {
+    id: String,
+    #[serde(default)]
+    content: String,
+    creation_date: Option<String>,
+    last_modified: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    markdown: bool,
+}
+
+impl Importer for SimplenoteImporter {
+    fn source(&self) -> &'static str {
+        "simplenote"
+    }
+
+    fn read(&self, path: &Path) -> AppResult<ImportBatch> {
+        let file = locate_export(path)?;
+        let contents = fs::read_to_string(&file)
+            .map_err(AppError::io(format!("Failed to read {}", file.display())))?;
+        let export: Export = serde_json::from_str(&contents)
+            .map_err(AppError::invalid("Not a Simplenote export"))?;
+
+        let active = export.active_notes.into_iter().map(|n| (n, false));
+        let trashed = export.trashed_notes.into_iter().map(|n| (n, true));
+
+        let notes = active
+            .chain(trashed)
+            .map(|(note, trashed)| convert(note, trashed))
+            .collect();
+
+        Ok(ImportBatch { notes, errors: Vec::new() })
+    }
+}
+
+/// Accepts the JSON file itself or the extracted export folder.
+fn locate_export(path: &Path) -> AppResult<PathBuf> {
+    if path.is_file() {
+        return Ok(path.to_path_buf());
+    }
+    [path.join("notes.json"), path.join("source").join("notes.json")]
+        .into_iter()
+        .find(|p| p.is_file())
+        .ok_or_else(|| AppError::NotFound(format!("notes.json in {}", path.display())))
+}
+
+/// Simplenote has no title field: the first non-empty line is the title.
+fn convert(note: SimplenoteNote, trashed: bool) -> ImportedNote {
+    let content = note.content.replace("\r\n", "\n");
+    let mut lines = content.lines().skip_while(|l| l.trim().is_empty());
+    let title = lines
+        .next()
+        .map(|l| l.trim().trim_start_matches('#').trim().to_string())
+        .unwrap_or_default();
+    let rest = lines.collect::<Vec<_>>().join("\n");
+    let rest = rest.trim_start_matches('\n');
+
+    let body = if note.markdown {
+        html::markdown_to_html(rest)
+    } else {
+        html::text_to_html(rest)
+    };
+
+    let now = Utc::now().timestamp();
+    let created_at = note.creation_date.as_deref().and_then(parse_iso).unwrap_or(now);
+    let updated_at = note.last_modified.as_deref().and_then(parse_iso).unwrap_or(created_at);
+
+    ImportedNote {
+        source_id: note.id,
+        title,
+        body,
+        created_at,
+        updated_at,
+        tags: note.tags,
+        trashed,
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(content: &str, markdown: bool) -> SimplenoteNote {
+        SimplenoteNote {
+            id: "sn-1".to_string(),
+            content: content.to_string(),
+            creation_date: Some("2024-01-02T03:04:05.000Z".to_string()),
+            last_modified: None,
+            tags: vec!["work".to_string()],
+            markdown,
+        }
+    }
+
+    #[test]
+    fn first_line_is_the_title() {
+        let imported = convert(note("\n# Plan\r\n\r\nfirst\nsecond", false), false);
+        assert_eq!(imported.title, "Plan");
+        assert_eq!(imported.body, "<p>first</p><p>second</p>");
+        assert_eq!(imported.created_at, 1_704_164_645);
+        assert_eq!(imported.updated_at, imported.created_at);
+        assert_eq!(imported.tags, vec!["work".to_string()]);
+    }
+
+    #[test]
+    fn markdown_notes_are_converted() {
+        let imported = convert(note("Title\n## Sub\n- item", true), true);
+        assert_eq!(imported.body, "<h2>Sub</h2><ul><li><p>item</p></li></ul>");
+        assert!(imported.trashed);
+    }
+
+    #[test]
+    fn reads_active_and_trashed_notes_from_folder() {
+        let dir = std::env::temp_dir().join(format!("rusty-notes-simplenote-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(dir.join("source")).unwrap();
+        fs::write(
+            dir.join("source").join("notes.json"),
+            r#"{"activeNotes":[{"id":"a","content":"A\nbody"}],"trashedNotes":[{"id":"b","content":"B"}]}"#,
+        )
+        .unwrap();
+
+        let batch = SimplenoteImporter.read(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        let summary: Vec<(&str, bool)> = batch.notes.iter().map(|n| (n.title.as_str(), n.trashed)).collect();
+        assert_eq!(summary, vec![("A", false), ("B", true)]);
+    }
+
+    #[test]
+    fn rejects_other_json() {
+        let file = std::env::temp_dir().join(format!("rusty-notes-simplenote-{}.json", uuid::Uuid::new_v4()));
+        fs::write(&file, "[1, 2]").unwrap();
+        let result = SimplenoteImporter.read(&file);
+        fs::remove_file(&file).unwrap();
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,153 @@
+// src-tauri/src/services/links.rs
+//! Wiki-style links between notes: `[[Title]]` in the text, or an
+//! `href="note://<id>"`. Bodies are parsed on every write (see
+//! [`DbStorage`](crate::services::DbStorage)) into the `note_links` table.
+
+use serde::Serialize;
+
+use crate::services::import::html::{escape, unescape};
+
+const NOTE_HREF: &str = "href=\"note://";
+
+/// A link as written in a body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Link {
+    Title(String),
+    Id(String),
+}
+
+impl Link {
+    /// The title or id as written.
+    pub fn into_target(self) -> String {
+        match self {
+            Link::Title(target) | Link::Id(target) => target,
+        }
+    }
+}
+
+/// A link from a note, resolved against the current notes.
+#[derive(Debug, Clone, Serialize)]
+pub struct OutgoingLink {
+    /// Title or id as written.
+    pub target: String,
+    /// The linked note, `None` when the link is broken.
+    pub note_id: Option<String>,
+    pub title: Option<String>,
+    pub broken: bool,
+}
+
+/// A link to a note that doesn't exist (or is in the trash).
+#[derive(Debug, Clone, Serialize)]
+pub struct BrokenLink {
+    pub source_id: String,
+    pub source_title: String,
+    pub target: String,
+}
+
+/// Links in `body`, in order of appearance, each once.
+pub fn parse(body: &str) -> Vec<Link> {
+    let mut found = Vec::new();
+    for_each_title_link(body, |title, range| found.push((range.start, Link::Title(title.to_string()))));
+
+    let mut offset = 0;
+    while let Some(start) = body[offset..].find(NOTE_HREF).map(|i| offset + i) {
+        let id_start = start + NOTE_HREF.len();
+        let Some(end) = body[id_start..].find('"').map(|i| id_start + i) else { break };
+        let id = body[id_start..end].trim();
+        if !id.is_empty() {
+            found.push((start, Link::Id(id.to_string())));
+        }
+        offset = end;
+    }
+    found.sort_by_key(|(start, _)| *start);
+
+    let mut seen = Vec::new();
+    let mut links: Vec<Link> = found.into_iter().map(|(_, link)| link).collect();
+    links.retain(|link| {
+        let key = match link {
+            Link::Title(title) => Link::Title(title.to_lowercase()),
+            Link::Id(_) => link.clone(),
+        };
+        !seen.contains(&key) && {
+            seen.push(key);
+            true
+        }
+    });
+    links
+}
+
+/// Same title as far as links go: trimmed, any case.
+pub fn same_title(a: &str, b: &str) -> bool {
+    a.trim().to_lowercase() == b.trim().to_lowercase()
+}
+
+/// The note `link` points to among `notes` (`(id, title)` of the active
+/// notes, most recently updated first, which wins a shared title).
+pub fn resolve<'a>(link: &Link, notes: &'a [(String, String)]) -> Option<&'a (String, String)> {
+    match link {
+        Link::Id(id) => notes.iter().find(|(note_id, _)| note_id == id),
+        Link::Title(title) => notes.iter().find(|(_, note_title)| same_title(note_title, title)),
+    }
+}
+
+/// `body` with every `[[old]]` turned into `[[new]]`.
+pub fn rename(body: &str, old: &str, new: &str) -> String {
+    let mut out = String::with_capacity(body.len());
+    let mut copied = 0;
+    for_each_title_link(body, |title, range| {
+        if same_title(title, old) {
+            out.push_str(&body[copied..range.start]);
+            out.push_str(&format!("[[{}]]", escape(new.trim())));
+            copied = range.end;
+        }
+    });
+    out.push_str(&body[copied..]);
+    out
+}
+
+/// Calls `f` with the title and byte range of every `[[Title]]`. Text
+/// with markup or brackets inside isn't a link.
+fn for_each_title_link(body: &str, mut f: impl FnMut(&str, std::ops::Range<usize>)) {
+    let mut offset = 0;
+    while let Some(start) = body[offset..].find("[[").map(|i| offset + i) {
+        let inner_start = start + 2;
+        let Some(end) = body[inner_start..].find("]]").map(|i| inner_start + i) else { break };
+        let inner = &body[inner_start..end];
+        if inner.contains(['[', ']', '<', '>']) {
+            offset = inner_start;
+            continue;
+        }
+        let title = unescape(inner);
+        if !title.trim().is_empty() {
+            f(title.trim(), start..end + 2);
+        }
+        offset = end + 2;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_title_and_id_links_once() {
+        let body = "<p>See [[Tom &amp; Jerry]] and [[tom & jerry]], [[ ]], [[a<b>x</b>]]</p>\
+                    <p><a href=\"note://n-2\">two</a> [not [[Plan]]</p>";
+        assert_eq!(
+            parse(body),
+            [
+                Link::Title("Tom & Jerry".to_string()),
+                Link::Id("n-2".to_string()),
+                Link::Title("Plan".to_string()),
+            ]
+        );
+        assert!(parse("<p>[[unclosed</p>").is_empty());
+    }
+
+    #[test]
+    fn renames_matching_title_links_only() {
+        let body = "<p>[[Plan]] vs [[plan ]] vs [[Planning]]</p>";
+        assert_eq!(rename(body, "Plan", "Q3 <Plan>"), "<p>[[Q3 &lt;Plan&gt;]] vs [[Q3 &lt;Plan&gt;]] vs [[Planning]]</p>");
+        assert_eq!(rename(body, "Other", "X"), body);
+    }
+}
@@ -0,0 +1,111 @@
+// src-tauri/src/services/maintenance.rs
+//! Backup and consistency checks (used by the CLI).
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::Local;
+use serde::Serialize;
+
+use crate::services::db_storage::DbStorage;
+use crate::error::{AppError, AppResult};
+
+/// Result of `check`: empty lists + `database == ["ok"]` means healthy.
+#[derive(Debug, Serialize)]
+pub struct CheckReport {
+    /// Output of `PRAGMA integrity_check`.
+    pub database: Vec<String>,
+    /// Note ids whose `file_path` doesn't exist.
+    pub missing_note_files: Vec<String>,
+    /// Attachment paths referenced in the DB but missing on disk.
+    pub missing_attachments: Vec<String>,
+    /// `.html` files in the notes folder that no note points to.
+    pub orphan_files: Vec<String>,
+}
+
+impl CheckReport {
+    pub fn is_ok(&self) -> bool {
+        self.database == ["ok"]
+            && self.missing_note_files.is_empty()
+            && self.missing_attachments.is_empty()
+            && self.orphan_files.is_empty()
+    }
+}
+
+/// Creates `{dest_dir}/rusty-notes-backup-{timestamp}/` containing a
+/// snapshot of the DB and a copy of the notes folder. Returns that folder.
+pub async fn backup(storage: &DbStorage, notes_folder: &str, dest_dir: &Path) -> AppResult<PathBuf> {
+    let stamp = Local::now().format("%Y%m%d-%H%M%S").to_string();
+    let target = dest_dir.join(format!("rusty-notes-backup-{}", stamp));
+    fs::create_dir_all(&target).map_err(AppError::io("Failed to create backup folder"))?;
+
+    storage.backup_database(&target.join("rusty-notes.db")).await?;
+
+    let notes = Path::new(notes_folder);
+    if notes.is_dir() {
+        copy_dir(notes, &target.join("notes"))?;
+    }
+
+    Ok(target)
+}
+
+/// Compares DB rows with the files on disk.
+pub async fn check(storage: &DbStorage, notes_folder: &str) -> AppResult<CheckReport> {
+    let database = storage.integrity_check().await?;
+
+    let note_files = storage.list_note_files().await?;
+    let mut missing_note_files = Vec::new();
+    let mut missing_attachments = Vec::new();
+
+    for (id, file_path) in &note_files {
+        if !Path::new(file_path).exists() {
+            missing_note_files.push(id.clone());
+        }
+        for attachment in storage.list_attachments(id).await? {
+            if !Path::new(&attachment.file_path).exists() {
+                missing_attachments.push(attachment.file_path);
+            }
+        }
+    }
+
+    let known: HashSet<PathBuf> = note_files
+        .iter()
+        .map(|(_, path)| PathBuf::from(path))
+        .collect();
+    let mut orphan_files = Vec::new();
+    if let Ok(entries) = fs::read_dir(notes_folder) {
+        for path in entries.flatten().map(|e| e.path()) {
+            let is_note_file = path.extension().and_then(|e| e.to_str()) == Some("html");
+            if is_note_file && !known.contains(&path) {
+                orphan_files.push(path.to_string_lossy().to_string());
+            }
+        }
+    }
+    orphan_files.sort();
+
+    Ok(CheckReport {
+        database,
+        missing_note_files,
+        missing_attachments,
+        orphan_files,
+    })
+}
+
+fn copy_dir(from: &Path, to: &Path) -> AppResult<()> {
+    fs::create_dir_all(to).map_err(AppError::io(format!("Failed to create {}", to.display())))?;
+    let entries = fs::read_dir(from).map_err(AppError::io(format!("Failed to read {}", from.display())))?;
+
+    for entry in entries.flatten() {
+        let source = entry.path();
+        let dest = to.join(entry.file_name());
+        if source.is_dir() {
+            copy_dir(&source, &dest)?;
+        } else {
+            fs::copy(&source, &dest)
+                .map_err(AppError::io(format!("Failed to copy {}", source.display())))?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,270 @@
+// src-tauri/src/services/memory.rs
+//! In-memory [`NoteRepository`], [`AttachmentRepository`] and [`FileStore`],
+//! so `NoteService` flows can be tested without a database or notes folder.
+//! They mirror the SQLite / disk behaviour the service relies on (versions,
+//! conflicts, trash), not every query detail.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex as StdMutex};
+
+use tokio::sync::Mutex;
+
+use crate::db::schema::{Attachment, Note, NoteFilter, NoteWithAttachments};
+use crate::error::{AppError, AppResult};
+use crate::services::events::EventBus;
+use crate::services::links;
+use crate::services::repository::{AttachmentRepository, FileStore, NoteRepository, WriteLock};
+
+/// Notes and attachments; clones share the same data.
+#[derive(Clone, Default)]
+pub struct InMemoryStorage {
+    data: Arc<StdMutex<Records>>,
+    writes: Arc<Mutex<()>>,
+    events: EventBus,
+}
+
+#[derive(Default)]
+struct Records {
+    notes: BTreeMap<String, StoredNote>,
+    attachments: Vec<Attachment>,
+}
+
+struct StoredNote {
+    note: Note,
+    trashed: bool,
+}
+
+impl InMemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn records(&self) -> std::sync::MutexGuard<'_, Records> {
+        self.data.lock().expect("in-memory storage poisoned")
+    }
+
+    /// Notes matching `filter`, pinned first, then newest first.
+    fn notes_where(&self, filter: impl Fn(&StoredNote) -> bool) -> Vec<Note> {
+        let mut notes: Vec<Note> = self
+            .records()
+            .notes
+            .values()
+            .filter(|stored| filter(stored))
+            .map(|stored| stored.note.clone())
+            .collect();
+        notes.sort_by_key(|note| std::cmp::Reverse((note.pinned, note.updated_at)));
+        notes
+    }
+}
+
+/// Active and matching `filter`, like the SQL `WHERE`.
+fn matches(stored: &StoredNote, filter: &NoteFilter) -> bool {
+    let note = &stored.note;
+    !stored.trashed
+        && note.archived == filter.archived
+        && (!filter.favorites || note.favorite)
+        && (!filter.pinned || note.pinned)
+}
+
+impl NoteRepository for InMemoryStorage {
+    async fn get(&self, id: &str) -> AppResult<Note> {
+        match self.records().notes.get(id) {
+            Some(stored) if !stored.trashed => Ok(stored.note.clone()),
+            _ => Err(AppError::NotFound(format!("Note {}", id))),
+        }
+    }
+
+    async fn list(&self, filter: &NoteFilter) -> AppResult<Vec<Note>> {
+        Ok(self.notes_where(|stored| matches(stored, filter)))
+    }
+
+    async fn search(&self, query: &str, filter: &NoteFilter) -> AppResult<Vec<Note>> {
+        // Same as SQLite's LIKE: case-insensitive for ASCII
+        let query = query.to_ascii_lowercase();
+        Ok(self.notes_where(|stored| {
+            matches(stored, filter)
+                && (stored.note.title.to_ascii_lowercase().contains(&query)
+                    || stored.note.body.to_ascii_lowercase().contains(&query))
+        }))
+    }
+
+    async fn list_trashed(&self) -> AppResult<Vec<Note>> {
+        Ok(self.notes_where(|stored| stored.trashed))
+    }
+
+    async fn is_locked(&self, _id: &str) -> AppResult<bool> {
+        // No password protection in memory
+        Ok(false)
+    }
+
+    async fn backlinks(&self, id: &str) -> AppResult<Vec<Note>> {
+        // Parsed on the fly instead of kept in a table
+        let notes = self.notes_where(|stored| !stored.trashed);
+        let titles: Vec<(String, String)> = notes.iter().map(|note| (note.id.clone(), note.title.clone())).collect();
+        Ok(notes
+            .into_iter()
+            .filter(|note| {
+                note.id != id
+                    && links::parse(&note.body)
+                        .iter()
+                        .any(|link| links::resolve(link, &titles).is_some_and(|(target, _)| target == id))
+            })
+            .collect())
+    }
+
+    async fn create(&self, mut note: Note) -> AppResult<Note> {
+        let mut records = self.records();
+        if records.notes.contains_key(&note.id) {
+            return Err(AppError::Validation(format!("Note {} already exists", note.id)));
+        }
+        note.version = 1;
+        records.notes.insert(note.id.clone(), StoredNote { note: note.clone(), trashed: false });
+        Ok(note)
+    }
+
+    async fn update(&self, mut note: Note, expected_version: Option<i64>) -> AppResult<Note> {
+        let mut records = self.records();
+        let stored = match records.notes.get_mut(&note.id) {
+            Some(stored) if !stored.trashed => stored,
+            _ => return Err(AppError::NotFound(format!("Note {}", note.id))),
+        };
+        if let Some(expected_version) = expected_version {
+            if stored.note.version != expected_version {
+                return Err(AppError::Conflict { expected_version, current: Box::new(stored.note.clone()) });
+            }
+        }
+
+        // Like the SQL update, `file_path`, `created_at` and the flags are kept
+        note.file_path = stored.note.file_path.clone();
+        note.created_at = stored.note.created_at;
+        (note.pinned, note.favorite, note.archived) = (stored.note.pinned, stored.note.favorite, stored.note.archived);
+        note.version = stored.note.version + 1;
+        stored.note = note.clone();
+        Ok(note)
+    }
+
+    async fn set_trashed(&self, id: &str, trashed: bool) -> AppResult<()> {
+        match self.records().notes.get_mut(id) {
+            Some(stored) if stored.trashed != trashed => {
+                stored.trashed = trashed;
+                stored.note.version += 1;
+                Ok(())
+            }
+            _ => Err(AppError::NotFound(format!("Note {}", id))),
+        }
+    }
+
+    async fn delete(&self, id: &str) -> AppResult<()> {
+        let mut records = self.records();
+        records.notes.remove(id);
+        records.attachments.retain(|a| a.note_id != id);
+        Ok(())
+    }
+
+    async fn lock_writes(&self) -> WriteLock<'_> {
+        let writes = self.writes.lock().await;
+        WriteLock::new(writes, self.events.begin_write())
+    }
+}
+
+impl AttachmentRepository for InMemoryStorage {
+    async fn add(&self, attachment: Attachment) -> AppResult<()> {
+        let mut records = self.records();
+        let note = records
+            .notes
+            .get_mut(&attachment.note_id)
+            .ok_or_else(|| AppError::NotFound(format!("Note {}", attachment.note_id)))?;
+        note.note.version += 1;
+        records.attachments.push(attachment);
+        Ok(())
+    }
+
+    async fn list_for_note(&self, note_id: &str) -> AppResult<Vec<Attachment>> {
+        Ok(self.records().attachments.iter().filter(|a| a.note_id == note_id).cloned().collect())
+    }
+}
+
+/// Files keyed by path under a fake `notes/` folder; trashed files are kept
+/// separately so tests can check where things went.
+#[derive(Clone, Default)]
+pub struct InMemoryFileStore {
+    data: Arc<StdMutex<Files>>,
+}
+
+#[derive(Default)]
+struct Files {
+    live: BTreeMap<String, Vec<u8>>,
+    trash: BTreeMap<String, Vec<u8>>,
+}
+
+impl InMemoryFileStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn files(&self) -> std::sync::MutexGuard<'_, Files> {
+        self.data.lock().expect("in-memory file store poisoned")
+    }
+
+    /// Contents of a live file.
+    pub fn read(&self, path: &str) -> Option<Vec<u8>> {
+        self.files().live.get(path).cloned()
+    }
+
+    /// Contents of a file moved to the trash, by its original path.
+    pub fn read_trashed(&self, path: &str) -> Option<Vec<u8>> {
+        self.files().trash.get(path).cloned()
+    }
+
+    /// Paths of all live files.
+    pub fn paths(&self) -> Vec<String> {
+        self.files().live.keys().cloned().collect()
+    }
+}
+
+impl FileStore for InMemoryFileStore {
+    async fn note_path(&self, id: &str) -> String {
+        format!("notes/{}.html", id)
+    }
+
+    async fn write_note(&self, _note_id: &str, path: &str, body: &str) -> AppResult<()> {
+        self.files().live.insert(path.to_string(), body.as_bytes().to_vec());
+        Ok(())
+    }
+
+    async fn write_attachment(&self, note_id: &str, file_name: &str, data: &[u8]) -> AppResult<String> {
+        let mut files = self.files();
+        let dir = format!("notes/attachments/{}", note_id);
+        let name = Path::new(file_name).file_name().and_then(|n| n.to_str()).unwrap_or("attachment");
+        let path = (0..)
+            .map(|n| match n {
+                0 => format!("{}/{}", dir, name),
+                n => format!("{}/{}-{}", dir, n, name),
+            })
+            .find(|p| !files.live.contains_key(p))
+            .expect("unbounded range always yields a free name");
+        files.live.insert(path.clone(), data.to_vec());
+        Ok(path)
+    }
+
+    async fn move_to_trash(&self, note: &NoteWithAttachments) -> AppResult<()> {
+        let mut files = self.files();
+        let paths = std::iter::once(&note.note.file_path).chain(note.attachments.iter().map(|a| &a.file_path));
+        for path in paths {
+            if let Some(data) = files.live.remove(path) {
+                files.trash.insert(path.clone(), data);
+            }
+        }
+        Ok(())
+    }
+
+    async fn remove(&self, note: &NoteWithAttachments) -> AppResult<()> {
+        let mut files = self.files();
+        files.live.remove(&note.note.file_path);
+        for attachment in &note.attachments {
+            files.live.remove(&attachment.file_path);
+        }
+        Ok(())
+    }
+}
@@ -3,24 +3,57 @@ pub mod db_storage;
 pub mod storage;
 pub mod trash_manager;
 pub mod permanent_delete;
-pub mod delete_service;
 pub mod note_service;      
 pub mod note_files; 
+pub mod import;
+pub mod export;
+pub mod maintenance;
+pub mod events;
+pub mod folder_sync;
+pub mod folder_move;
+pub mod repository;
+pub mod memory;
+pub mod vault_root;
+pub mod quick_capture;
+pub mod encryption;
+pub mod note_lock;
+pub mod links;
+pub mod graph;
+pub mod templates;
+pub mod daily_notes;
 
 // New specialized storage modules
 pub mod db_notes;
 pub mod db_attachments;
+pub mod db_tags;
+pub mod db_imports;
+pub mod db_links;
 
 // Existing public API (unchanged)
 pub use db_storage::DbStorage;
 pub use trash_manager::TrashManager;
 pub use permanent_delete::PermanentDelete;
-pub use delete_service::DeleteService;
 
 // NEW: Export specialized storage (no #[cfg(test)])
 pub use db_notes::NoteStorage;
 pub use db_attachments::AttachmentStorage;
+pub use db_tags::TagStorage;
+pub use db_imports::ImportSourceStorage;
+pub use db_links::LinkStorage;
 
 
 
 pub use note_service::NoteService;
+pub use import::ImportService;
+pub use export::ExportService;
+pub use graph::NoteGraph;
+pub use templates::Templates;
+pub use daily_notes::DailyNotes;
+pub use events::{AppEvent, EventBus};
+pub use folder_sync::FolderSync;
+pub use folder_move::{FolderMove, FolderMoveReport};
+pub use quick_capture::QuickCapture;
+pub use encryption::VaultEncryption;
+pub use note_lock::NoteLocks;
+pub use repository::{AttachmentRepository, FileStore, NoteRepository};
+pub use memory::{InMemoryFileStore, InMemoryStorage};
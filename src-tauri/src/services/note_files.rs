@@ -1,22 +1,34 @@
 // src-tauri/src/services/note_files.rs
 use tokio::fs;
-use std::path::PathBuf;
+use tokio::sync::Mutex;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
-/// Handles all note file operations
+use crate::crypto::Keyring;
+use crate::db::schema::NoteWithAttachments;
+use crate::services::import::html::escape;
+use crate::services::repository::FileStore;
+use crate::services::{PermanentDelete, TrashManager};
+use crate::settings::Settings;
+use crate::error::{AppError, AppResult};
+
+/// Handles all note file operations. Contents are sealed by `keyring` in
+/// an encrypted vault.
 pub struct NoteFileStore {
     notes_folder: String,
+    keyring: Keyring,
 }
 
 impl NoteFileStore {
-    pub fn new(notes_folder: String) -> Self {
-        Self { notes_folder }
+    pub fn new(notes_folder: String, keyring: Keyring) -> Self {
+        Self { notes_folder, keyring }
     }
 
     /// Ensures the notes folder exists on disk
-    pub async fn ensure_folder_exists(&self) -> Result<(), String> {
+    pub async fn ensure_folder_exists(&self) -> AppResult<()> {
         fs::create_dir_all(&self.notes_folder)
             .await
-            .map_err(|e| format!("Failed to create notes folder: {}", e))
+            .map_err(AppError::io("Failed to create notes folder"))
     }
 
     /// Builds file path for a note ID: {notes_folder}/{id}.html
@@ -28,9 +40,196 @@ impl NoteFileStore {
     }
 
     /// Writes note body to file
-    pub async fn write_note_file(&self, file_path: &str, body: &str) -> Result<(), String> {
-        fs::write(file_path, body.as_bytes())
+    pub async fn write_note_file(&self, file_path: &str, body: &str) -> AppResult<()> {
+        fs::write(file_path, self.keyring.seal(body.as_bytes())?)
+            .await
+            .map_err(AppError::io("Failed to write note file"))
+    }
+
+    /// Folder holding a note's attachments: {notes_folder}/attachments/{note_id}
+    pub fn attachments_dir(&self, note_id: &str) -> PathBuf {
+        PathBuf::from(&self.notes_folder).join("attachments").join(note_id)
+    }
+
+    /// Writes attachment bytes to {notes_folder}/attachments/{note_id}/{file_name},
+    /// adding `-1`, `-2`, ... when the name is taken. Returns the full path.
+    pub async fn write_attachment(&self, note_id: &str, file_name: &str, data: &[u8]) -> AppResult<String> {
+        let dir = self.attachments_dir(note_id);
+        fs::create_dir_all(&dir)
+            .await
+            .map_err(AppError::io("Failed to create attachments folder"))?;
+
+        let target = unique_path(&dir, file_name);
+        fs::write(&target, self.keyring.seal(&self.keyring.seal_note_data(note_id, data)?)?)
             .await
-            .map_err(|e| format!("Failed to write note file: {}", e))
+            .map_err(AppError::io(format!("Failed to write attachment {}", file_name)))?;
+
+        Ok(target.to_string_lossy().to_string())
+    }
+}
+
+/// [`FileStore`] on the configured notes folder. The folder is read from
+/// settings on every call, so changing it takes effect immediately.
+#[derive(Clone)]
+pub struct DiskFileStore {
+    settings: Arc<Mutex<Settings>>,
+    keyring: Keyring,
+}
+
+impl DiskFileStore {
+    pub fn new(settings: Arc<Mutex<Settings>>, keyring: Keyring) -> Self {
+        Self { settings, keyring }
+    }
+
+    async fn file_store(&self) -> NoteFileStore {
+        NoteFileStore::new(self.notes_folder().await, self.keyring.clone())
+    }
+
+    async fn notes_folder(&self) -> String {
+        self.settings.lock().await.notes_folder.clone()
+    }
+}
+
+impl FileStore for DiskFileStore {
+    async fn note_path(&self, id: &str) -> String {
+        self.file_store().await.path_for_id(id)
+    }
+
+    async fn write_note(&self, note_id: &str, path: &str, body: &str) -> AppResult<()> {
+        let file_store = self.file_store().await;
+        file_store.ensure_folder_exists().await?;
+        file_store.write_note_file(path, &self.keyring.seal_note_text(note_id, body)?).await
+    }
+
+    async fn write_attachment(&self, note_id: &str, file_name: &str, data: &[u8]) -> AppResult<String> {
+        self.file_store()
+            .await
+            .write_attachment(note_id, file_name, data)
+            .await
+    }
+
+    async fn move_to_trash(&self, note: &NoteWithAttachments) -> AppResult<()> {
+        TrashManager::move_to_trash(&self.notes_folder().await, note).await
+    }
+
+    async fn remove(&self, note: &NoteWithAttachments) -> AppResult<()> {
+        PermanentDelete::delete(note).await
+    }
+}
+
+/// Logical attachment type stored in `attachments.attachment_type`.
+pub fn attachment_type_for(mime_type: Option<&str>) -> &'static str {
+    match mime_type {
+        Some(m) if m.starts_with("image/") => "image",
+        Some(m) if m.starts_with("audio/") => "audio",
+        Some(m) if m.starts_with("video/") => "video",
+        Some("application/pdf") => "pdf",
+        _ => "file",
+    }
+}
+
+/// Every way a note body can point at the attachment stored at `file_path`:
+/// Tauri asset-protocol URLs (`convertFileSrc`, per platform), a `file://`
+/// URL, and the raw path (HTML-escaped and as-is). Longest forms come first
+/// so replacing them in order never hits a shorter form inside a longer one.
+/// Indices line up between paths, so `forms(old)[i]` maps to `forms(new)[i]`.
+pub fn reference_forms(file_path: &str) -> Vec<String> {
+    let component = percent_encode(file_path, false);
+    let uri_path = percent_encode(&file_path.replace('\\', "/"), true);
+    let file_url = if uri_path.starts_with('/') {
+        format!("file://{}", uri_path)
+    } else {
+        format!("file:///{}", uri_path)
+    };
+
+    vec![
+        format!("asset://localhost/{}", component),
+        format!("https://asset.localhost/{}", component),
+        format!("http://asset.localhost/{}", component),
+        file_url,
+        escape(file_path),
+        file_path.to_string(),
+    ]
+}
+
+/// Replaces every reference to `old_path` with the same form of `new_path`.
+pub fn rewrite_references(body: &str, old_path: &str, new_path: &str) -> String {
+    let mut body = body.to_string();
+    if old_path == new_path {
+        return body;
+    }
+    for (old, new) in reference_forms(old_path).iter().zip(reference_forms(new_path)) {
+        body = body.replace(old.as_str(), &new);
+    }
+    body
+}
+
+/// `encodeURIComponent`-style encoding (the JS side uses it for asset URLs).
+/// With `keep_path`, `/` and `:` are left as-is for `file://` URLs.
+fn percent_encode(value: &str, keep_path: bool) -> String {
+    let mut out = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9'
+            | b'-' | b'_' | b'.' | b'!' | b'~' | b'*' | b'\'' | b'(' | b')' => out.push(byte as char),
+            b'/' | b':' if keep_path => out.push(byte as char),
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    out
+}
+
+/// `dir/name`, or `dir/name-1`, `dir/name-2`, ... if that already exists.
+fn unique_path(dir: &Path, file_name: &str) -> PathBuf {
+    // Never let a supplied name escape the attachments folder
+    let file_name = Path::new(file_name)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("attachment");
+
+    let candidate = dir.join(file_name);
+    if !candidate.exists() {
+        return candidate;
+    }
+
+    let path = Path::new(file_name);
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("attachment");
+    let ext = path.extension().and_then(|s| s.to_str());
+
+    (1..)
+        .map(|n| match ext {
+            Some(ext) => dir.join(format!("{}-{}.{}", stem, n, ext)),
+            None => dir.join(format!("{}-{}", stem, n)),
+        })
+        .find(|p| !p.exists())
+        .expect("unbounded range always yields a free name")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reference_forms_match_what_the_webview_uses() {
+        let forms = reference_forms("/home/me/notes/attachments/n1/my photo&1.png");
+        assert_eq!(forms[0], "asset://localhost/%2Fhome%2Fme%2Fnotes%2Fattachments%2Fn1%2Fmy%20photo%261.png");
+        assert_eq!(forms[2], "http://asset.localhost/%2Fhome%2Fme%2Fnotes%2Fattachments%2Fn1%2Fmy%20photo%261.png");
+        assert_eq!(forms[3], "file:///home/me/notes/attachments/n1/my%20photo%261.png");
+        assert_eq!(forms[4], "/home/me/notes/attachments/n1/my photo&amp;1.png");
+        assert_eq!(forms[5], "/home/me/notes/attachments/n1/my photo&1.png");
+    }
+
+    #[test]
+    fn windows_paths_become_file_urls() {
+        assert_eq!(reference_forms("C:\\notes\\a b.png")[3], "file:///C:/notes/a%20b.png");
+    }
+
+    #[test]
+    fn rewrite_keeps_the_reference_form() {
+        let body = "<img src=\"asset://localhost/%2Fold%2Fa.png\"><a href=\"/old/a.png\">a</a>";
+        assert_eq!(
+            rewrite_references(body, "/old/a.png", "/new dir/a.png"),
+            "<img src=\"asset://localhost/%2Fnew%20dir%2Fa.png\"><a href=\"/new dir/a.png\">a</a>"
+        );
     }
 }
@@ -0,0 +1,137 @@
+// src-tauri/src/services/note_lock.rs
+//! Password protection of single notes, apart from vault encryption: the
+//! body and attachments are sealed with a key of the note's own, wrapped
+//! by its passphrase. Unlocking keeps that key for the session (see
+//! [`Keyring::unlock_note`](crate::crypto::Keyring::unlock_note)).
+
+use std::fs;
+use std::path::Path;
+
+use crate::crypto::{is_note_sealed, is_note_sealed_text, VaultKey, WrappedKey};
+use crate::db::schema::Note;
+use crate::error::{AppError, AppResult};
+use crate::services::db_storage::DbStorage;
+use crate::services::encryption::{replace_file, MIN_PASSPHRASE_CHARS};
+
+pub struct NoteLocks {
+    storage: DbStorage,
+}
+
+impl NoteLocks {
+    pub fn new(storage: DbStorage) -> Self {
+        Self { storage }
+    }
+
+    /// Protects note `id` with `passphrase` and leaves it locked. Calling
+    /// it again with the same passphrase finishes an interrupted run.
+    pub async fn protect(&self, id: &str, passphrase: &str) -> AppResult<()> {
+        let _writes = self.storage.lock_writes().await;
+        let note = self.stored_note(id).await?;
+        let key = match self.storage.note_password_lock(id).await? {
+            Some(lock) => lock.unlock(passphrase)?,
+            None => {
+                if passphrase.chars().count() < MIN_PASSPHRASE_CHARS {
+                    return Err(AppError::Validation(format!(
+                        "The passphrase needs at least {} characters",
+                        MIN_PASSPHRASE_CHARS
+                    )));
+                }
+                let (lock, key) = WrappedKey::create(passphrase)?;
+                // The row first: sealed files are never left without their key
+                let body = key.seal_note_text(&note.body)?;
+                self.storage.set_note_password_lock(id, &body, Some(&lock)).await?;
+                key
+            }
+        };
+        self.storage.keyring().lock_note(id);
+
+        let body = self.stored_note(id).await?.body;
+        self.write_note_file(&note.file_path, &body)?;
+        for attachment in self.storage.list_attachments(id).await? {
+            self.rewrite_attachment(&attachment.file_path, |data| {
+                if is_note_sealed(&data) { Ok(data) } else { key.seal_note_data(&data) }
+            })?;
+        }
+
+        log::info!("🔒 Note password protected: {}", id);
+        Ok(())
+    }
+
+    /// Removes the protection of note `id`; body and attachments are
+    /// stored as before.
+    pub async fn unprotect(&self, id: &str, passphrase: &str) -> AppResult<()> {
+        let key = self.key(id, passphrase).await?;
+        let _writes = self.storage.lock_writes().await;
+        let note = self.stored_note(id).await?;
+        let body = open_body(&key, &note.body)?;
+
+        // Files first: until the row changes, running this again finishes it
+        self.write_note_file(&note.file_path, &body)?;
+        for attachment in self.storage.list_attachments(id).await? {
+            self.rewrite_attachment(&attachment.file_path, |data| {
+                if is_note_sealed(&data) { key.open_note_data(&data) } else { Ok(data) }
+            })?;
+        }
+        self.storage.set_note_password_lock(id, &body, None).await?;
+        self.storage.keyring().lock_note(id);
+
+        log::info!("🔓 Note password protection removed: {}", id);
+        Ok(())
+    }
+
+    /// Makes note `id` readable (and editable) for the rest of the session.
+    pub async fn unlock(&self, id: &str, passphrase: &str) -> AppResult<()> {
+        let key = self.key(id, passphrase).await?;
+        self.storage.keyring().unlock_note(id, key);
+        Ok(())
+    }
+
+    /// Forgets the session key of note `id`.
+    pub fn lock(&self, id: &str) {
+        self.storage.keyring().lock_note(id);
+    }
+
+    async fn key(&self, id: &str, passphrase: &str) -> AppResult<VaultKey> {
+        self.storage
+            .note_password_lock(id)
+            .await?
+            .ok_or_else(|| AppError::Validation("This note is not password protected".to_string()))?
+            .unlock(passphrase)
+    }
+
+    async fn stored_note(&self, id: &str) -> AppResult<Note> {
+        self.storage
+            .find_stored_note(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Note {}", id)))
+    }
+
+    fn write_note_file(&self, path: &str, body: &str) -> AppResult<()> {
+        let path = Path::new(path);
+        if !path.exists() {
+            return Ok(());
+        }
+        replace_file(path, &self.storage.keyring().seal(body.as_bytes())?)
+    }
+
+    /// Rewrites an attachment file (missing ones are skipped); `change`
+    /// gets and returns the contents inside the vault layer.
+    fn rewrite_attachment(&self, path: &str, change: impl Fn(Vec<u8>) -> AppResult<Vec<u8>>) -> AppResult<()> {
+        let path = Path::new(path);
+        let data = match fs::read(path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(AppError::io(format!("Failed to read {}", path.display()))(e)),
+        };
+        let keyring = self.storage.keyring();
+        replace_file(path, &keyring.seal(&change(keyring.open(&data)?)?)?)
+    }
+}
+
+fn open_body(key: &VaultKey, body: &str) -> AppResult<String> {
+    if is_note_sealed_text(body) {
+        key.open_note_text(body)
+    } else {
+        Ok(body.to_string())
+    }
+}
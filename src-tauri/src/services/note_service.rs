@@ -5,36 +5,52 @@ use chrono::Utc;
 use uuid::Uuid;
 
 use crate::services::db_storage::DbStorage;
-use crate::db::schema::{Note, NoteWithAttachments};
+use crate::db::schema::{Attachment, Note, NoteFilter, NoteWithAttachments};
 use crate::settings::Settings;
-use crate::services::note_files::NoteFileStore;
-
-/// High-level note operations (coordinates DB + files)
-pub struct NoteService {
-    storage: Arc<Mutex<DbStorage>>,
+use crate::settings::model::DeleteBehavior;
+use crate::services::links;
+use crate::services::note_files::{attachment_type_for, DiskFileStore};
+use crate::services::repository::{AttachmentRepository, FileStore, NoteRepository};
+use crate::error::{AppError, AppResult};
+
+/// High-level note operations (coordinates DB + files).
+/// The app uses SQLite and the notes folder; tests can pass in-memory parts
+/// to [`NoteService::with_parts`].
+pub struct NoteService<N = DbStorage, A = DbStorage, F = DiskFileStore> {
+    notes: N,
+    attachments: A,
+    files: F,
     settings: Arc<Mutex<Settings>>,
 }
 
 impl NoteService {
-    pub fn new(storage: Arc<Mutex<DbStorage>>, settings: Arc<Mutex<Settings>>) -> Self {
-        Self { storage, settings }
+    pub fn new(storage: DbStorage, settings: Arc<Mutex<Settings>>) -> Self {
+        let files = DiskFileStore::new(settings.clone(), storage.keyring().clone());
+        Self::with_parts(storage.clone(), storage, files, settings)
     }
+}
 
-    /// Creates a new note (DB + file)
-    pub async fn create(&self, title: String, body: String) -> Result<Note, String> {
-        // Get notes folder
-        let settings = self.settings.lock().await;
-        let notes_folder = settings.notes_folder.clone();
-        drop(settings);
+impl<N, A, F> NoteService<N, A, F>
+where
+    N: NoteRepository,
+    A: AttachmentRepository,
+    F: FileStore,
+{
+    pub fn with_parts(notes: N, attachments: A, files: F, settings: Arc<Mutex<Settings>>) -> Self {
+        Self { notes, attachments, files, settings }
+    }
 
-        // Setup file store
-        let file_store = NoteFileStore::new(notes_folder);
-        file_store.ensure_folder_exists().await?;
+    /// Creates a new note (DB + file)
+    pub async fn create(&self, title: String, body: String) -> AppResult<Note> {
+        self.create_in(title, body, None).await
+    }
 
+    /// Same as [`Self::create`], filed under `notebook`.
+    pub async fn create_in(&self, title: String, body: String, notebook: Option<String>) -> AppResult<Note> {
         // Generate note metadata
         let id = Uuid::new_v4().to_string();
         let now = Utc::now().timestamp();
-        let file_path = file_store.path_for_id(&id);
+        let file_path = self.files.note_path(&id).await;
 
         // Build note
         let note = Note {
@@ -44,54 +60,183 @@ impl NoteService {
             created_at: now,
             updated_at: now,
             file_path: file_path.clone(),
+            notebook,
+            version: 0,
+            pinned: false,
+            favorite: false,
+            archived: false,
         };
 
         // 1) Save to DB
-        let created_note = self.storage.lock().await.create_note(note).await?;
+        let created_note = self.notes.create(note).await?;
 
         // 2) Write file
-        file_store.write_note_file(&file_path, &body).await?;
+        self.files.write_note(&id, &file_path, &body).await?;
 
-        println!("✅ Created note: {} at {}", created_note.id, file_path);
+        log::info!("✅ Created note: {} at {}", created_note.id, file_path);
         Ok(created_note)
     }
 
-    /// Updates existing note (DB + file)
-    pub async fn update(&self, id: String, title: String, body: String) -> Result<Note, String> {
+    /// Updates existing note (DB + file).
+    /// `expected_version` is the version the caller edited; if the note has
+    /// moved on since, nothing is written and the current note is returned
+    /// in [`crate::error::AppError::Conflict`]. `None` overwrites unconditionally.
+    /// A new title is carried into the `[[Title]]` links of linking notes.
+    pub async fn update(
+        &self,
+        id: String,
+        title: String,
+        body: String,
+        expected_version: Option<i64>,
+    ) -> AppResult<Note> {
+        // DB row and file must end up in the same order as other writers'
+        let _writes = self.notes.lock_writes().await;
+
         // Load existing note
-        let mut existing = self.storage.lock().await.get_note(&id).await?.note;
+        let mut existing = self.notes.get(&id).await?;
+        let renamed_from = (!links::same_title(&existing.title, &title)).then(|| existing.title.clone());
+        let linking = match renamed_from {
+            Some(_) => self.notes.backlinks(&id).await?,
+            None => Vec::new(),
+        };
 
         // Update fields
         existing.title = title;
         existing.body = body.clone();
         existing.updated_at = Utc::now().timestamp();
 
-        // 1) Update DB
-        let updated_note = self.storage.lock().await.update_note(existing).await?;
+        // 1) Update DB (the version check happens in the same statement)
+        let updated_note = self.notes.update(existing, expected_version).await?;
 
         // 2) Write file
-        let settings = self.settings.lock().await;
-        let file_store = NoteFileStore::new(settings.notes_folder.clone());
-        drop(settings);
+        self.files.write_note(&updated_note.id, &updated_note.file_path, &body).await?;
 
-        file_store.write_note_file(&updated_note.file_path, &body).await?;
+        // 3) Follow a rename in the notes linking here
+        if let Some(old_title) = renamed_from {
+            self.rename_links(linking, &old_title, &updated_note.title).await?;
+        }
 
-        println!("✅ Updated note: {} at {}", updated_note.id, updated_note.file_path);
+        log::info!("✅ Updated note: {} at {}", updated_note.id, updated_note.file_path);
         Ok(updated_note)
     }
 
+    /// Rewrites `[[old]]` as `[[new]]` in `sources` (DB + file). Their
+    /// `updated_at` stays, as nobody edited them. Caller holds the write lock.
+    async fn rename_links(&self, sources: Vec<Note>, old: &str, new: &str) -> AppResult<()> {
+        let mut renamed = 0;
+        for mut source in sources {
+            let body = links::rename(&source.body, old, new);
+            if body == source.body {
+                continue;
+            }
+            source.body = body;
+            let source = self.notes.update(source, None).await?;
+            self.files.write_note(&source.id, &source.file_path, &source.body).await?;
+            renamed += 1;
+        }
+
+        if renamed > 0 {
+            log::info!("🔗 Renamed [[{}]] to [[{}]] in {} notes", old, new, renamed);
+        }
+        Ok(())
+    }
+
+    /// Deletes a note according to `delete_behavior` (files to the trash
+    /// folder, or gone), then removes its DB records.
+    pub async fn delete(&self, id: String) -> AppResult<()> {
+        let delete_behavior = self.settings.lock().await.delete_behavior.clone();
+
+        // Files go before the row; the folder watcher must not see that gap
+        let _writes = self.notes.lock_writes().await;
+        let note = self.get(id.clone()).await?;
+
+        match delete_behavior {
+            DeleteBehavior::MoveToTrash => self.files.move_to_trash(&note).await?,
+            DeleteBehavior::Permanent => self.files.remove(&note).await?,
+        }
+        self.notes.delete(&id).await?;
+
+        log::info!("✅ Deleted note: {}", id);
+        Ok(())
+    }
+
+    /// Soft delete: hides the note from list and search, files stay in place.
+    pub async fn trash(&self, id: String) -> AppResult<()> {
+        self.notes.set_trashed(&id, true).await?;
+
+        log::info!("🗑️ Moved note to trash: {}", id);
+        Ok(())
+    }
+
+    /// Brings a trashed note back.
+    pub async fn restore(&self, id: String) -> AppResult<Note> {
+        self.notes.set_trashed(&id, false).await?;
+
+        log::info!("♻️ Restored note: {}", id);
+        self.notes.get(&id).await
+    }
+
+    /// Lists notes in the trash
+    pub async fn list_trash(&self) -> AppResult<Vec<Note>> {
+        self.notes.list_trashed().await
+    }
+
+    /// Stores a file for a note (disk + attachment record)
+    pub async fn add_attachment(
+        &self,
+        note_id: String,
+        file_name: String,
+        mime_type: Option<String>,
+        data: Vec<u8>,
+    ) -> AppResult<Attachment> {
+        // Make sure the note exists (and can be written) before writing anything
+        self.notes.get(&note_id).await?;
+        if self.notes.is_locked(&note_id).await? {
+            return Err(AppError::NoteLocked(note_id));
+        }
+
+        let file_path = self.files.write_attachment(&note_id, &file_name, &data).await?;
+        let attachment = Attachment {
+            id: Uuid::new_v4().to_string(),
+            note_id,
+            attachment_type: attachment_type_for(mime_type.as_deref()).to_string(),
+            file_name,
+            file_path,
+            mime_type,
+            size_bytes: Some(data.len() as i64),
+            created_at: Utc::now().timestamp(),
+        };
+        self.attachments.add(attachment.clone()).await?;
+
+        log::info!("✅ Added attachment: {} to note {}", attachment.id, attachment.note_id);
+        Ok(attachment)
+    }
+
     /// Gets note with attachments
-    pub async fn get(&self, id: String) -> Result<NoteWithAttachments, String> {
-        self.storage.lock().await.get_note(&id).await
+    pub async fn get(&self, id: String) -> AppResult<NoteWithAttachments> {
+        let note = self.notes.get(&id).await?;
+        let attachments = self.attachments.list_for_note(&id).await?;
+        let locked = self.notes.is_locked(&id).await?;
+        Ok(NoteWithAttachments { note, attachments, locked })
     }
 
-    /// Lists all notes
-    pub async fn list(&self) -> Result<Vec<Note>, String> {
-        self.storage.lock().await.list_notes().await
+    /// Lists all active, non-archived notes, pinned first
+    pub async fn list(&self) -> AppResult<Vec<Note>> {
+        self.list_filtered(NoteFilter::default()).await
+    }
+
+    /// Lists the notes matching `filter`
+    pub async fn list_filtered(&self, filter: NoteFilter) -> AppResult<Vec<Note>> {
+        self.notes.list(&filter).await
     }
 
     /// Searches notes
-    pub async fn search(&self, query: String) -> Result<Vec<Note>, String> {
-        self.storage.lock().await.search_notes(&query).await
+    pub async fn search(&self, query: String) -> AppResult<Vec<Note>> {
+        self.search_filtered(query, NoteFilter::default()).await
+    }
+
+    /// Searches the notes matching `filter`
+    pub async fn search_filtered(&self, query: String, filter: NoteFilter) -> AppResult<Vec<Note>> {
+        self.notes.search(&query, &filter).await
     }
 }
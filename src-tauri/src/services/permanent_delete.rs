@@ -2,6 +2,7 @@
 use std::path::Path;
 use std::fs;
 use crate::db::schema::NoteWithAttachments;
+use crate::error::{AppError, AppResult};
 
 pub struct PermanentDelete;
 
@@ -9,13 +10,13 @@ impl PermanentDelete {
     /// Permanently delete note and attachments (no recovery)
     pub async fn delete(
         note: &NoteWithAttachments,
-    ) -> Result<(), String> {
+    ) -> AppResult<()> {
         let note_path = Path::new(&note.note.file_path);
 
         // Delete note file
         if note_path.exists() {
             fs::remove_file(&note_path)
-                .map_err(|e| format!("Failed to delete note file: {}", e))?;
+                .map_err(AppError::io("Failed to delete note file"))?;
         }
 
         // Delete attachments
@@ -23,7 +24,7 @@ impl PermanentDelete {
             let attachment_path = Path::new(&attachment.file_path);
             if attachment_path.exists() {
                 fs::remove_file(attachment_path)
-                    .map_err(|e| format!("Failed to delete attachment: {}", e))?;
+                    .map_err(AppError::io("Failed to delete attachment"))?;
             }
         }
 
@@ -0,0 +1,69 @@
+// src-tauri/src/services/quick_capture.rs
+//! Notes typed into the quick capture window: plain text, saved as a note
+//! in the inbox notebook from settings.
+
+use std::sync::Arc;
+
+use tokio::sync::Mutex;
+
+use crate::db::schema::Note;
+use crate::error::{AppError, AppResult};
+use crate::services::db_storage::DbStorage;
+use crate::services::import::html::text_to_html;
+use crate::services::note_service::NoteService;
+use crate::settings::Settings;
+
+/// Longest title taken from the first line of the text.
+const TITLE_CHARS: usize = 60;
+
+pub struct QuickCapture {
+    notes: NoteService,
+    settings: Arc<Mutex<Settings>>,
+}
+
+impl QuickCapture {
+    pub fn new(storage: DbStorage, settings: Arc<Mutex<Settings>>) -> Self {
+        Self { notes: NoteService::new(storage, settings.clone()), settings }
+    }
+
+    /// Saves `text` as a note. Without a title, its first line is used.
+    pub async fn save(&self, title: &str, text: &str) -> AppResult<Note> {
+        let title = title_for(title, text).ok_or_else(|| AppError::Validation("Nothing to save".to_string()))?;
+        let notebook = self.settings.lock().await.quick_capture.notebook.trim().to_string();
+
+        let note = self.notes.create_in(title, text_to_html(text.trim()), Some(notebook)).await?;
+        log::info!("✓ Captured note {} into {}", note.id, note.notebook.as_deref().unwrap_or("(none)"));
+        Ok(note)
+    }
+}
+
+/// `title` if given, else the first line of `text` (shortened).
+fn title_for(title: &str, text: &str) -> Option<String> {
+    let title = title.trim();
+    if !title.is_empty() {
+        return Some(title.to_string());
+    }
+    let line = text.lines().map(str::trim).find(|line| !line.is_empty())?;
+    if line.chars().count() <= TITLE_CHARS {
+        return Some(line.to_string());
+    }
+    let short: String = line.chars().take(TITLE_CHARS - 1).collect();
+    Some(format!("{}…", short.trim_end()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn title_falls_back_to_the_first_line() {
+        assert_eq!(title_for(" Plan ", "body").as_deref(), Some("Plan"));
+        assert_eq!(title_for("", "\n  call Sam  \nabout q3").as_deref(), Some("call Sam"));
+        assert_eq!(title_for(" ", " \n "), None);
+
+        let long = "word ".repeat(20);
+        let title = title_for("", &long).unwrap();
+        assert_eq!(title.chars().count(), TITLE_CHARS);
+        assert!(title.ends_with("word…"));
+    }
+}
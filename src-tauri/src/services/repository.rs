@@ -0,0 +1,155 @@
+// src-tauri/src/services/repository.rs
+//! What [`NoteService`](crate::services::NoteService) needs from storage.
+//!
+//! The app runs on [`DbStorage`] (SQLite) and
+//! [`DiskFileStore`](crate::services::note_files::DiskFileStore) (the notes
+//! folder); tests can swap in the in-memory versions from
+//! [`memory`](crate::services::memory).
+
+use std::future::Future;
+
+use tokio::sync::MutexGuard;
+
+use crate::db::schema::{Attachment, Note, NoteFilter, NoteWithAttachments};
+use crate::error::AppResult;
+use crate::services::db_storage::DbStorage;
+use crate::services::events::WriteGuard;
+
+/// Note rows.
+pub trait NoteRepository: Send + Sync {
+    /// Active note; `NotFound` when missing or in the trash.
+    fn get(&self, id: &str) -> impl Future<Output = AppResult<Note>> + Send;
+
+    /// Active notes matching `filter`, pinned first, then newest first.
+    fn list(&self, filter: &NoteFilter) -> impl Future<Output = AppResult<Vec<Note>>> + Send;
+
+    /// Notes `list` returns for `filter` whose title or body contains `query`.
+    fn search(&self, query: &str, filter: &NoteFilter) -> impl Future<Output = AppResult<Vec<Note>>> + Send;
+
+    /// Notes in the trash, newest first.
+    fn list_trashed(&self) -> impl Future<Output = AppResult<Vec<Note>>> + Send;
+
+    /// Password protected and not unlocked this session.
+    fn is_locked(&self, id: &str) -> impl Future<Output = AppResult<bool>> + Send;
+
+    /// Active notes linking to note `id` (see [`links`](crate::services::links)),
+    /// newest first.
+    fn backlinks(&self, id: &str) -> impl Future<Output = AppResult<Vec<Note>>> + Send;
+
+    /// Stores a new note at version 1.
+    fn create(&self, note: Note) -> impl Future<Output = AppResult<Note>> + Send;
+
+    /// Writes title, body, `updated_at` and notebook and bumps the version.
+    /// With `expected_version`, fails with `Conflict` if the note moved on.
+    fn update(&self, note: Note, expected_version: Option<i64>) -> impl Future<Output = AppResult<Note>> + Send;
+
+    /// Moves an active note to the trash (`true`) or a trashed one back
+    /// (`false`); `NotFound` when the note isn't in the other state.
+    fn set_trashed(&self, id: &str, trashed: bool) -> impl Future<Output = AppResult<()>> + Send;
+
+    /// Removes the note and its attachment records for good.
+    fn delete(&self, id: &str) -> impl Future<Output = AppResult<()>> + Send;
+
+    /// See [`WriteLock`].
+    fn lock_writes(&self) -> impl Future<Output = WriteLock<'_>> + Send;
+}
+
+/// Attachment rows.
+pub trait AttachmentRepository: Send + Sync {
+    fn add(&self, attachment: Attachment) -> impl Future<Output = AppResult<()>> + Send;
+
+    fn list_for_note(&self, note_id: &str) -> impl Future<Output = AppResult<Vec<Attachment>>> + Send;
+}
+
+/// Note bodies and attachment bytes.
+pub trait FileStore: Send + Sync {
+    /// Where the body of note `id` goes.
+    fn note_path(&self, id: &str) -> impl Future<Output = String> + Send;
+
+    /// Writes the body of note `note_id`, creating the folder if needed.
+    fn write_note(&self, note_id: &str, path: &str, body: &str) -> impl Future<Output = AppResult<()>> + Send;
+
+    /// Stores attachment bytes next to the note; returns the path used.
+    fn write_attachment(
+        &self,
+        note_id: &str,
+        file_name: &str,
+        data: &[u8],
+    ) -> impl Future<Output = AppResult<String>> + Send;
+
+    /// Moves the note file and its attachments to the trash folder.
+    fn move_to_trash(&self, note: &NoteWithAttachments) -> impl Future<Output = AppResult<()>> + Send;
+
+    /// Deletes the note file and its attachments (no recovery).
+    fn remove(&self, note: &NoteWithAttachments) -> impl Future<Output = AppResult<()>> + Send;
+}
+
+/// Held across a write that spans several steps (row + files): writers
+/// don't interleave, and the folder watcher ignores the half-done change.
+/// Reads never take it.
+pub struct WriteLock<'a> {
+    _writes: MutexGuard<'a, ()>,
+    _in_flight: WriteGuard,
+}
+
+impl<'a> WriteLock<'a> {
+    pub fn new(writes: MutexGuard<'a, ()>, in_flight: WriteGuard) -> Self {
+        Self { _writes: writes, _in_flight: in_flight }
+    }
+}
+
+impl NoteRepository for DbStorage {
+    async fn get(&self, id: &str) -> AppResult<Note> {
+        Ok(self.get_note(id).await?.note)
+    }
+
+    async fn list(&self, filter: &NoteFilter) -> AppResult<Vec<Note>> {
+        self.list_notes(filter).await
+    }
+
+    async fn search(&self, query: &str, filter: &NoteFilter) -> AppResult<Vec<Note>> {
+        self.search_notes(query, filter).await
+    }
+
+    async fn list_trashed(&self) -> AppResult<Vec<Note>> {
+        self.list_deleted_notes().await
+    }
+
+    async fn is_locked(&self, id: &str) -> AppResult<bool> {
+        self.is_note_locked(id).await
+    }
+
+    async fn backlinks(&self, id: &str) -> AppResult<Vec<Note>> {
+        DbStorage::backlinks(self, id).await
+    }
+
+    async fn create(&self, note: Note) -> AppResult<Note> {
+        self.create_note(note).await
+    }
+
+    async fn update(&self, note: Note, expected_version: Option<i64>) -> AppResult<Note> {
+        self.update_note(note, expected_version).await
+    }
+
+    async fn set_trashed(&self, id: &str, trashed: bool) -> AppResult<()> {
+        self.set_note_deleted(id, trashed).await
+    }
+
+    async fn delete(&self, id: &str) -> AppResult<()> {
+        self.delete_note(id).await
+    }
+
+    async fn lock_writes(&self) -> WriteLock<'_> {
+        DbStorage::lock_writes(self).await
+    }
+}
+
+impl AttachmentRepository for DbStorage {
+    async fn add(&self, attachment: Attachment) -> AppResult<()> {
+        self.create_attachment(attachment).await
+    }
+
+    async fn list_for_note(&self, note_id: &str) -> AppResult<Vec<Attachment>> {
+        self.list_attachments(note_id).await
+    }
+}
@@ -4,6 +4,7 @@ use std::fs;
 use std::path::PathBuf;
 // Import our Note model
 use crate::models::Note;
+use crate::error::{AppError, AppResult};
 
 /// Service that handles saving/loading notes to disk
 pub struct StorageService {
@@ -13,13 +14,13 @@ pub struct StorageService {
 impl StorageService {
     /// Create a new storage service
     /// Takes the app data directory and creates a "notes" folder inside it
-    pub fn new(app_data_dir: PathBuf) -> Result<Self, String> {
+    pub fn new(app_data_dir: PathBuf) -> AppResult<Self> {
         // Build path: app_data_dir/notes/
         let notes_dir = app_data_dir.join("notes");
         
         // Create the directory if it doesn't exist
         fs::create_dir_all(&notes_dir)
-            .map_err(|e| format!("Failed to create notes directory: {}", e))?;
+            .map_err(AppError::io("Failed to create notes directory"))?;
         
         Ok(StorageService { notes_dir })
     }
@@ -37,11 +38,11 @@ impl StorageService {
     }
     
     /// Save a note to disk
-    pub fn save(&self, note: &Note) -> Result<(), String> {
+    pub fn save(&self, note: &Note) -> AppResult<()> {
         // Create the note's folder: notes/abc-123/
         let note_dir = self.note_dir(&note.id);
         fs::create_dir_all(&note_dir)
-            .map_err(|e| format!("Failed to create note directory: {}", e))?;
+            .map_err(AppError::io("Failed to create note directory"))?;
         
         // Convert note to markdown format
         let content = note.to_markdown();
@@ -51,27 +52,27 @@ impl StorageService {
         
         // Write markdown to file
         fs::write(file_path, content)
-            .map_err(|e| format!("Failed to save note: {}", e))
+            .map_err(AppError::io("Failed to save note"))
     }
     
     /// Load a note from disk
-    pub fn load(&self, id: &str) -> Result<Note, String> {
+    pub fn load(&self, id: &str) -> AppResult<Note> {
         // Build file path: notes/abc-123/note.md
         let file_path = self.note_file_path(id);
         
         // Read the entire file as text
         let content = fs::read_to_string(file_path)
-            .map_err(|e| format!("Failed to read note: {}", e))?;
+            .map_err(AppError::io("Failed to read note"))?;
         
         // Parse markdown back into Note struct
         Ok(Note::from_markdown(id.to_string(), &content))
     }
     
     /// List all note IDs
-    pub fn list(&self) -> Result<Vec<String>, String> {
+    pub fn list(&self) -> AppResult<Vec<String>> {
         // Read all entries in the notes directory
         let entries = fs::read_dir(&self.notes_dir)
-            .map_err(|e| format!("Failed to read notes directory: {}", e))?;
+            .map_err(AppError::io("Failed to read notes directory"))?;
         
         let mut note_ids = Vec::new();
         
@@ -96,12 +97,12 @@ impl StorageService {
     }
     
     /// Delete a note and its folder
-    pub fn delete(&self, id: &str) -> Result<(), String> {
+    pub fn delete(&self, id: &str) -> AppResult<()> {
         // Get the note's directory
         let note_dir = self.note_dir(id);
         
         // Remove the entire folder (including note.md and future attachments)
         fs::remove_dir_all(note_dir)
-            .map_err(|e| format!("Failed to delete note: {}", e))
+            .map_err(AppError::io("Failed to delete note"))
     }
 }
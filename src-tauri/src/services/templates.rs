@@ -0,0 +1,243 @@
+// src-tauri/src/services/templates.rs
+//! Note templates: HTML files in the `templates/` folder of the notes
+//! folder, turned into new notes with their placeholders filled in.
+//!
+//! A template may start with a comment giving the note's title, default
+//! tags and notebook; the file name (without `.html`) is its id and the
+//! title when none is given:
+//!
+//! ```text
+//! <!--
+//! title: Meeting {{date}}
+//! tags: meeting, work
+//! notebook: Work
+//! -->
+//! <h1>{{title}}</h1><p>{{time}}, with {{who}}</p>
+//! ```
+//!
+//! `{{date}}` (YYYY-MM-DD) and `{{time}}` (HH:MM) are the local time,
+//! `{{title}}` the new note's title; any other name is a custom variable
+//! from the caller, who can override the built-in ones too. Placeholders
+//! without a value are left as written. Templates are plain files the
+//! user edits, also in an encrypted vault.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use chrono::{Local, NaiveDateTime};
+use serde::Serialize;
+use tokio::sync::Mutex;
+
+use crate::db::schema::Note;
+use crate::error::{AppError, AppResult};
+use crate::services::db_storage::DbStorage;
+use crate::services::import::html::escape;
+use crate::services::note_service::NoteService;
+use crate::settings::Settings;
+
+/// Folder holding the templates, inside the notes folder.
+pub const TEMPLATES_DIR: &str = "templates";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Template {
+    pub id: String,
+    /// Title of the new notes, placeholders not filled in yet.
+    pub title: String,
+    pub tags: Vec<String>,
+    pub notebook: Option<String>,
+    /// Template text after the header comment.
+    pub body: String,
+}
+
+pub struct Templates {
+    notes: NoteService,
+    storage: DbStorage,
+    settings: Arc<Mutex<Settings>>,
+}
+
+impl Templates {
+    pub fn new(storage: DbStorage, settings: Arc<Mutex<Settings>>) -> Self {
+        Self { notes: NoteService::new(storage.clone(), settings.clone()), storage, settings }
+    }
+
+    async fn folder(&self) -> PathBuf {
+        Path::new(&self.settings.lock().await.notes_folder).join(TEMPLATES_DIR)
+    }
+
+    /// Every template, by id. No templates folder means no templates.
+    pub async fn list(&self) -> AppResult<Vec<Template>> {
+        let folder = self.folder().await;
+        let mut entries = match tokio::fs::read_dir(&folder).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(AppError::io("Failed to read templates folder")(e)),
+        };
+
+        let mut templates = Vec::new();
+        while let Some(entry) = entries.next_entry().await.map_err(AppError::io("Failed to read templates folder"))? {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "html") {
+                if let Some(id) = path.file_stem().and_then(|stem| stem.to_str()) {
+                    templates.push(self.get(id).await?);
+                }
+            }
+        }
+        templates.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(templates)
+    }
+
+    /// The template `id`; `NotFound` if there is no such file.
+    pub async fn get(&self, id: &str) -> AppResult<Template> {
+        if id.is_empty() || id.starts_with('.') || id.contains(['/', '\\']) {
+            return Err(AppError::NotFound(format!("Template {}", id)));
+        }
+        let path = self.folder().await.join(format!("{}.html", id));
+        match tokio::fs::read_to_string(&path).await {
+            Ok(text) => Ok(parse(id, &text)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Err(AppError::NotFound(format!("Template {}", id))),
+            Err(e) => Err(AppError::io(format!("Failed to read template {}", id))(e)),
+        }
+    }
+
+    /// Creates a note from template `id` with `vars` filled in, in the
+    /// template's notebook and with its tags.
+    pub async fn create_note(&self, id: &str, vars: HashMap<String, String>) -> AppResult<Note> {
+        self.create_note_in(id, vars, None).await
+    }
+
+    /// Same as [`Self::create_note`], filed under `notebook` if given.
+    pub async fn create_note_in(
+        &self,
+        id: &str,
+        vars: HashMap<String, String>,
+        notebook: Option<String>,
+    ) -> AppResult<Note> {
+        let template = self.get(id).await?;
+        let (title, body) = fill_template(&template, vars, Local::now().naive_local());
+
+        let note = self.notes.create_in(title, body, notebook.or(template.notebook)).await?;
+        if !template.tags.is_empty() {
+            self.storage.set_note_tags(&note.id, &template.tags).await?;
+        }
+        log::info!("✓ Created note {} from template {}", note.id, id);
+        // Tags bumped the version
+        Ok(self.storage.get_note(&note.id).await?.note)
+    }
+}
+
+/// Splits the optional header comment off `text`.
+fn parse(id: &str, text: &str) -> Template {
+    let mut template = Template {
+        id: id.to_string(),
+        title: id.to_string(),
+        tags: Vec::new(),
+        notebook: None,
+        body: text.to_string(),
+    };
+    let Some(header) = text.trim_start().strip_prefix("<!--") else {
+        return template;
+    };
+    let Some((header, body)) = header.split_once("-->") else {
+        return template;
+    };
+
+    let mut fields = false;
+    for line in header.lines() {
+        let Some((key, value)) = line.split_once(':') else { continue };
+        let value = value.trim();
+        match key.trim().to_lowercase().as_str() {
+            "title" if !value.is_empty() => template.title = value.to_string(),
+            "tags" => {
+                template.tags = value.split(',').map(str::trim).filter(|t| !t.is_empty()).map(str::to_string).collect()
+            }
+            "notebook" if !value.is_empty() => template.notebook = Some(value.to_string()),
+            _ => continue,
+        }
+        fields = true;
+    }
+    // Any other leading comment is part of the text
+    if fields {
+        template.body = body.trim_start_matches(['\r', '\n']).to_string();
+    }
+    template
+}
+
+/// Title and body of a note from `template` at local time `now`. A
+/// `title` variable replaces the template's title.
+fn fill_template(template: &Template, mut vars: HashMap<String, String>, now: NaiveDateTime) -> (String, String) {
+    vars.entry("date".to_string()).or_insert_with(|| now.format("%Y-%m-%d").to_string());
+    vars.entry("time".to_string()).or_insert_with(|| now.format("%H:%M").to_string());
+    let title = match vars.get("title") {
+        Some(title) if !title.trim().is_empty() => title.trim().to_string(),
+        _ => fill(&template.title, &vars, false),
+    };
+    vars.insert("title".to_string(), title.clone());
+    let body = fill(&template.body, &vars, true);
+    (title, body)
+}
+
+/// Replaces `{{name}}` (spaces around the name allowed) with its value,
+/// HTML-escaped if `html`. Inserted values aren't filled in again.
+fn fill(text: &str, vars: &HashMap<String, String>, html: bool) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let value = after.find("}}").and_then(|end| Some((vars.get(after[..end].trim())?, end)));
+        match value {
+            Some((value, end)) => {
+                out.push_str(&if html { escape(value) } else { value.clone() });
+                rest = &after[end + 2..];
+            }
+            None => {
+                out.push_str("{{");
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    fn noon() -> NaiveDateTime {
+        NaiveDateTime::parse_from_str("2026-03-04 12:05", "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn reads_the_header_comment() {
+        let text = "<!--\ntitle: Meeting {{date}}\ntags: meeting, , work\nnotebook: Work\n-->\n<h1>{{title}}</h1>";
+        let template = parse("meeting", text);
+        assert_eq!(template.title, "Meeting {{date}}");
+        assert_eq!(template.tags, ["meeting", "work"]);
+        assert_eq!(template.notebook.as_deref(), Some("Work"));
+        assert_eq!(template.body, "<h1>{{title}}</h1>");
+
+        // No header, or a plain comment: all text, titled by the id
+        let plain = parse("incident", "<!-- keep me --><p>x</p>");
+        assert_eq!((plain.title.as_str(), plain.body.as_str()), ("incident", "<!-- keep me --><p>x</p>"));
+        assert!(plain.tags.is_empty() && plain.notebook.is_none());
+    }
+
+    #[test]
+    fn fills_builtin_and_custom_variables() {
+        let template = parse("m", "<!--\ntitle: Meeting {{date}}\n-->\n<p>{{ title }} at {{time}} with {{who}}, {{missing}} {{</p>");
+        let (title, body) = fill_template(&template, vars(&[("who", "Sam & Kim")]), noon());
+        assert_eq!(title, "Meeting 2026-03-04");
+        assert_eq!(body, "<p>Meeting 2026-03-04 at 12:05 with Sam &amp; Kim, {{missing}} {{</p>");
+
+        // The caller's values win, and aren't filled in again
+        let (title, body) = fill_template(&template, vars(&[("title", "Retro"), ("date", "{{time}}")]), noon());
+        assert_eq!(title, "Retro");
+        assert_eq!(body, "<p>Retro at 12:05 with {{who}}, {{missing}} {{</p>");
+    }
+}
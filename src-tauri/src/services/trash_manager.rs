@@ -1,32 +1,37 @@
 // src-tauri/src/services/trash_manager.rs
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::fs;
 use crate::db::schema::NoteWithAttachments;
+use crate::error::{AppError, AppResult};
 
 pub struct TrashManager;
 
 impl TrashManager {
+    /// Trash folder for notes deleted today: {notes_folder}/trash/YYYY-MM-DD
+    pub fn folder_for_today(notes_folder: &str) -> PathBuf {
+        let deletion_date = chrono::Local::now().format("%Y-%m-%d").to_string();
+        Path::new(notes_folder).join("trash").join(deletion_date)
+    }
+
     pub async fn move_to_trash(
         notes_folder: &str,
         note: &NoteWithAttachments,
-    ) -> Result<(), String> {
-        let trash_root = Path::new(notes_folder).join("trash");
-        let deletion_date = chrono::Local::now().format("%Y-%m-%d").to_string();
-        let trash_date_folder = trash_root.join(&deletion_date);
+    ) -> AppResult<()> {
+        let trash_date_folder = Self::folder_for_today(notes_folder);
 
         fs::create_dir_all(&trash_date_folder)
-            .map_err(|e| format!("Failed to create trash folder: {}", e))?;
+            .map_err(AppError::io("Failed to create trash folder"))?;
 
         // Move note file
         let original_note_path = Path::new(&note.note.file_path);
         if original_note_path.exists() {
             let filename = original_note_path
                 .file_name()
-                .ok_or("Invalid note filename")?;
+                .ok_or_else(|| AppError::Validation("Invalid note filename".to_string()))?;
             let trash_note_path = trash_date_folder.join(filename);
 
             fs::rename(&original_note_path, &trash_note_path)
-                .map_err(|e| format!("Failed to move note to trash: {}", e))?;
+                .map_err(AppError::io("Failed to move note to trash"))?;
         }
 
         // Move attachments
@@ -35,11 +40,11 @@ impl TrashManager {
             if attachment_path.exists() {
                 let filename = attachment_path
                     .file_name()
-                    .ok_or("Invalid attachment filename")?;
+                    .ok_or_else(|| AppError::Validation("Invalid attachment filename".to_string()))?;
                 let trash_attachment = trash_date_folder.join(filename);
 
                 fs::rename(&attachment_path, &trash_attachment)
-                    .map_err(|e| format!("Failed to move attachment: {}", e))?;
+                    .map_err(AppError::io("Failed to move attachment"))?;
             }
         }
 
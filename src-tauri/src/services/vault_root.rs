@@ -0,0 +1,89 @@
+// src-tauri/src/services/vault_root.rs
+//! Note and attachment paths are stored relative to the notes folder (the
+//! vault root), so the DB keeps working when the folder moves or is synced
+//! to another machine. Everything above the storage layer sees absolute
+//! paths; [`VaultRoot`] converts between the two.
+
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+/// The folder stored paths are relative to. Clones share it, so pointing
+/// one storage at a new folder re-roots all of them.
+#[derive(Clone, Debug)]
+pub struct VaultRoot(Arc<RwLock<PathBuf>>);
+
+impl VaultRoot {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self(Arc::new(RwLock::new(root.into())))
+    }
+
+    pub fn get(&self) -> PathBuf {
+        self.0.read().expect("vault root poisoned").clone()
+    }
+
+    pub fn set(&self, root: impl Into<PathBuf>) {
+        *self.0.write().expect("vault root poisoned") = root.into();
+    }
+
+    /// DB form of an absolute `path`.
+    pub fn to_stored(&self, path: &str) -> String {
+        to_stored(&self.get(), path)
+    }
+
+    /// Absolute form of a stored path.
+    pub fn resolve(&self, stored: &str) -> String {
+        resolve(&self.get(), stored)
+    }
+}
+
+/// `path` relative to `root` with `/` separators, or unchanged if it isn't
+/// under `root` (or already relative).
+pub fn to_stored(root: &Path, path: &str) -> String {
+    match Path::new(path).strip_prefix(root) {
+        Ok(rest) if Path::new(path).is_absolute() => rest
+            .components()
+            .filter_map(|c| match c {
+                Component::Normal(part) => Some(part.to_string_lossy()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("/"),
+        _ => path.to_string(),
+    }
+}
+
+/// `stored` under `root`; absolute paths (files outside the vault) are kept.
+pub fn resolve(root: &Path, stored: &str) -> String {
+    if Path::new(stored).is_absolute() {
+        return stored.to_string();
+    }
+    stored
+        .split('/')
+        .fold(root.to_path_buf(), |path, part| path.join(part))
+        .to_string_lossy()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paths_under_the_root_round_trip_as_relative() {
+        let root = std::env::temp_dir().join("vault");
+        let path = root.join("attachments").join("n1").join("map.png").to_string_lossy().to_string();
+
+        let stored = to_stored(&root, &path);
+        assert_eq!(stored, "attachments/n1/map.png");
+        assert_eq!(resolve(&root, &stored), path);
+    }
+
+    #[test]
+    fn paths_outside_the_root_stay_absolute() {
+        let root = std::env::temp_dir().join("vault");
+        let outside = std::env::temp_dir().join("vault-old").join("a.html").to_string_lossy().to_string();
+
+        assert_eq!(to_stored(&root, &outside), outside);
+        assert_eq!(resolve(&root, &outside), outside);
+    }
+}
@@ -0,0 +1,95 @@
+// src-tauri/src/settings/migrations.rs
+//! Upgrades settings files written by older versions, keyed on `version`.
+//! Each step rewrites the raw TOML of version `n` into version `n + 1`
+//! (renamed or reshaped fields); fields that are simply new need no step,
+//! `serde(default)` fills them in.
+
+use toml::{Table, Value};
+
+/// Version written by this build.
+pub const CURRENT_VERSION: u32 = 2;
+
+/// `MIGRATIONS[n]` upgrades a version `n` document to `n + 1`.
+const MIGRATIONS: [fn(&mut Table); CURRENT_VERSION as usize] = [v0_to_v1, v1_to_v2];
+
+/// Files from before `version` existed; `version` was the only change.
+fn v0_to_v1(_doc: &mut Table) {}
+
+/// Version 1 left shortcuts that are off out of `[shortcuts]`, so they
+/// came back as the defaults; version 2 writes them as `""`. A missing
+/// table is from before shortcuts and keeps the defaults.
+fn v1_to_v2(doc: &mut Table) {
+    let Some(Value::Table(shortcuts)) = doc.get_mut("shortcuts") else {
+        return;
+    };
+    for field in ["new_note", "quick_capture", "search", "toggle_window"] {
+        shortcuts.entry(field).or_insert_with(|| Value::String(String::new()));
+    }
+}
+
+/// Brings `doc` up to [`CURRENT_VERSION`]. Returns whether anything ran;
+/// documents from a newer build are left as they are.
+pub fn migrate(doc: &mut Table) -> anyhow::Result<bool> {
+    let version = match doc.get("version") {
+        None => 0,
+        Some(Value::Integer(v)) if *v >= 0 => *v as u64,
+        Some(other) => anyhow::bail!("invalid settings version: {}", other),
+    };
+    if version >= CURRENT_VERSION as u64 {
+        return Ok(false);
+    }
+
+    for step in &MIGRATIONS[version as usize..] {
+        step(doc);
+    }
+    doc.insert("version".to_string(), Value::Integer(CURRENT_VERSION as i64));
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc(toml: &str) -> Table {
+        toml::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn unversioned_files_are_upgraded() {
+        let mut d = doc("notes_folder = \"/notes\"");
+        assert!(migrate(&mut d).unwrap());
+        assert_eq!(d["version"].as_integer(), Some(CURRENT_VERSION as i64));
+        assert_eq!(d["notes_folder"].as_str(), Some("/notes"));
+    }
+
+    #[test]
+    fn shortcuts_left_out_by_v1_are_off() {
+        let mut d = doc("version = 1\n[shortcuts]\nquick_capture = \"CmdOrCtrl+Alt+Space\"");
+        assert!(migrate(&mut d).unwrap());
+        let shortcuts = d["shortcuts"].as_table().unwrap();
+        assert_eq!(shortcuts["new_note"].as_str(), Some(""));
+        assert_eq!(shortcuts["quick_capture"].as_str(), Some("CmdOrCtrl+Alt+Space"));
+        assert_eq!(shortcuts["toggle_window"].as_str(), Some(""));
+
+        // Files from before shortcuts get the defaults
+        let mut old = doc("version = 1\nnotes_folder = \"/notes\"");
+        migrate(&mut old).unwrap();
+        assert!(!old.contains_key("shortcuts"));
+    }
+
+    #[test]
+    fn current_and_newer_files_are_left_alone() {
+        let mut current = doc(&format!("version = {}", CURRENT_VERSION));
+        assert!(!migrate(&mut current).unwrap());
+
+        let mut newer = doc(&format!("version = {}\nfuture = true", CURRENT_VERSION + 1));
+        assert!(!migrate(&mut newer).unwrap());
+        assert_eq!(newer["version"].as_integer(), Some(CURRENT_VERSION as i64 + 1));
+    }
+
+    #[test]
+    fn rejects_a_bad_version() {
+        assert!(migrate(&mut doc("version = \"two\"")).is_err());
+        assert!(migrate(&mut doc("version = -1")).is_err());
+    }
+}
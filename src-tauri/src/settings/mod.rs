@@ -1,8 +1,10 @@
 // src-tauri/src/settings/mod.rs
 
+pub mod migrations;
 pub mod model;
 pub mod storage; 
+pub mod validation;
 
-pub use model::Settings;
-pub use storage::{load_or_init, save};
+pub use model::{ApiSettings, DailyNoteSettings, EncryptionSettings, QuickCaptureSettings, Settings, ShortcutAction, ShortcutSettings, TraySettings};
+pub use storage::{load, load_or_init, save, SettingsFiles};
 
@@ -1,12 +1,184 @@
 use serde::{Deserialize, Serialize};
+use std::path::Path;
 
+/// Missing fields take their defaults; renamed or reshaped fields are
+/// handled by [`crate::settings::migrations`].
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Settings {
     pub version: u32,
     pub notes_folder: String,
     pub auto_save_interval_secs: u64,
     pub delete_behavior: DeleteBehavior,
     pub onboarding_completed: bool,
+    pub api: ApiSettings,
+    pub shortcuts: ShortcutSettings,
+    pub quick_capture: QuickCaptureSettings,
+    pub tray: TraySettings,
+    pub encryption: EncryptionSettings,
+    pub daily_notes: DailyNoteSettings,
+}
+
+/// Local HTTP API for integrations (editor plugins, scripts, clippers).
+/// Only ever bound to 127.0.0.1.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ApiSettings {
+    pub enabled: bool,
+    pub port: u16,
+    /// Bearer token clients must send; generated on first start when empty.
+    pub token: String,
+    /// Largest accepted request body (attachment uploads), in MiB.
+    pub max_upload_mb: u64,
+}
+
+impl Default for ApiSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 27_645,
+            token: String::new(),
+            max_upload_mb: 100,
+        }
+    }
+}
+
+/// System-wide shortcuts, as accelerators like `CmdOrCtrl+Alt+N`. They
+/// fire while other apps are focused, so the defaults avoid common app
+/// keys; `None` turns a shortcut off.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShortcutSettings {
+    pub new_note: Option<String>,
+    pub quick_capture: Option<String>,
+    pub search: Option<String>,
+    pub toggle_window: Option<String>,
+}
+
+impl Default for ShortcutSettings {
+    fn default() -> Self {
+        Self {
+            new_note: Some("CmdOrCtrl+Alt+N".to_string()),
+            quick_capture: Some("CmdOrCtrl+Alt+Space".to_string()),
+            search: None,
+            toggle_window: None,
+        }
+    }
+}
+
+impl ShortcutSettings {
+    pub fn get(&self, action: ShortcutAction) -> Option<&str> {
+        let accelerator = match action {
+            ShortcutAction::NewNote => &self.new_note,
+            ShortcutAction::QuickCapture => &self.quick_capture,
+            ShortcutAction::Search => &self.search,
+            ShortcutAction::ToggleWindow => &self.toggle_window,
+        };
+        accelerator.as_deref().map(str::trim).filter(|a| !a.is_empty())
+    }
+
+    /// Every action that has a shortcut, with its accelerator.
+    pub fn bindings(&self) -> impl Iterator<Item = (ShortcutAction, &str)> {
+        ShortcutAction::ALL
+            .into_iter()
+            .filter_map(|action| self.get(action).map(|accelerator| (action, accelerator)))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShortcutAction {
+    NewNote,
+    QuickCapture,
+    Search,
+    ToggleWindow,
+}
+
+impl ShortcutAction {
+    pub const ALL: [ShortcutAction; 4] = [Self::NewNote, Self::QuickCapture, Self::Search, Self::ToggleWindow];
+
+    /// Settings field, as used in validation errors (`shortcuts.<field>`).
+    pub fn field(self) -> &'static str {
+        match self {
+            Self::NewNote => "new_note",
+            Self::QuickCapture => "quick_capture",
+            Self::Search => "search",
+            Self::ToggleWindow => "toggle_window",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::NewNote => "New note",
+            Self::QuickCapture => "Quick capture",
+            Self::Search => "Search",
+            Self::ToggleWindow => "Show/hide window",
+        }
+    }
+}
+
+/// The small always-on-top window for jotting a note down.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuickCaptureSettings {
+    /// Notebook captured notes are filed under.
+    pub notebook: String,
+    /// Start the body with the clipboard text.
+    pub prefill_from_clipboard: bool,
+}
+
+impl Default for QuickCaptureSettings {
+    fn default() -> Self {
+        Self { notebook: "Inbox".to_string(), prefill_from_clipboard: false }
+    }
+}
+
+/// Tray icon menu and window behaviour.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TraySettings {
+    /// Closing the main window hides it; the app keeps running in the tray.
+    pub close_to_tray: bool,
+    /// Recently edited notes listed in the tray menu.
+    pub recent_notes: usize,
+}
+
+impl Default for TraySettings {
+    fn default() -> Self {
+        Self { close_to_tray: false, recent_notes: 5 }
+    }
+}
+
+/// Encryption at rest (only applies once turned on for a vault).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EncryptionSettings {
+    /// Locks the vault after this long without reading or writing notes;
+    /// 0 never locks.
+    pub auto_lock_minutes: u64,
+}
+
+impl Default for EncryptionSettings {
+    fn default() -> Self {
+        Self { auto_lock_minutes: 15 }
+    }
+}
+
+/// One note per day, opened by `open_daily_note`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DailyNoteSettings {
+    /// Title as a chrono format, like `%Y-%m-%d` or `%A, %B %-d`.
+    pub title_format: String,
+    /// Notebook daily notes are filed under; empty keeps the template's.
+    pub notebook: String,
+    /// Template id new daily notes start from; empty starts them blank.
+    pub template: String,
+}
+
+impl Default for DailyNoteSettings {
+    fn default() -> Self {
+        Self { title_format: "%Y-%m-%d".to_string(), notebook: "Daily".to_string(), template: String::new() }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,11 +190,17 @@ pub enum DeleteBehavior {
 impl Default for Settings {
     fn default() -> Self {
         Self {
-            version: 1,
+            version: crate::settings::migrations::CURRENT_VERSION,
             notes_folder: String::from("./notes"),
             auto_save_interval_secs: 30,
             delete_behavior: DeleteBehavior::MoveToTrash,
             onboarding_completed: false,
+            api: ApiSettings::default(),
+            shortcuts: ShortcutSettings::default(),
+            quick_capture: QuickCaptureSettings::default(),
+            tray: TraySettings::default(),
+            encryption: EncryptionSettings::default(),
+            daily_notes: DailyNoteSettings::default(),
         }
     }
 }
@@ -31,4 +209,77 @@ impl Settings {
     pub fn config_path(app_config_dir: std::path::PathBuf) -> std::path::PathBuf {
         app_config_dir.join("settings.toml")
     }
+
+    /// Makes `notes_folder` absolute. Relative values (like the default
+    /// `./notes`) are taken relative to the app data dir, not the working
+    /// directory, so the GUI and the CLI always open the same folder.
+    /// An empty value is left empty for validation to reject.
+    pub fn resolve_notes_folder(&mut self, app_data_dir: &Path) {
+        let folder = Path::new(&self.notes_folder);
+        if folder.is_relative() && !self.notes_folder.trim().is_empty() {
+            let relative = folder.strip_prefix(".").unwrap_or(folder);
+            self.notes_folder = app_data_dir.join(relative).to_string_lossy().to_string();
+        }
+    }
+
+    /// Takes the fields only the backend writes from `current`, so an
+    /// update built from an older copy can't undo them (the API token is
+    /// generated on start and rotated by `regenerate_api_token`).
+    pub fn keep_backend_fields(&mut self, current: &Settings) {
+        self.api.token = current.api.token.clone();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relative_notes_folder_resolves_against_data_dir() {
+        let mut settings = Settings::default();
+        settings.resolve_notes_folder(Path::new("/data/app"));
+        assert_eq!(Path::new(&settings.notes_folder), Path::new("/data/app/notes"));
+    }
+
+    #[test]
+    fn absolute_notes_folder_is_kept() {
+        let absolute = std::env::temp_dir().join("my-notes");
+        let mut settings = Settings {
+            notes_folder: absolute.to_string_lossy().to_string(),
+            ..Settings::default()
+        };
+        settings.resolve_notes_folder(Path::new("/data/app"));
+        assert_eq!(Path::new(&settings.notes_folder), absolute);
+    }
+
+    #[test]
+    fn updates_keep_the_current_api_token() {
+        let mut current = Settings::default();
+        current.api.token = "rotated".to_string();
+        let mut stale = Settings::default();
+        stale.api.token = "old".to_string();
+        stale.api.port = 31_000;
+
+        stale.keep_backend_fields(&current);
+        assert_eq!(stale.api.token, "rotated");
+        assert_eq!(stale.api.port, 31_000, "user-editable fields are the update's");
+    }
+
+    #[test]
+    fn blank_shortcuts_are_off() {
+        let shortcuts = ShortcutSettings {
+            search: Some("  ".to_string()),
+            toggle_window: Some(" Alt+Shift+R ".to_string()),
+            ..ShortcutSettings::default()
+        };
+        let bindings: Vec<_> = shortcuts.bindings().collect();
+        assert_eq!(
+            bindings,
+            vec![
+                (ShortcutAction::NewNote, "CmdOrCtrl+Alt+N"),
+                (ShortcutAction::QuickCapture, "CmdOrCtrl+Alt+Space"),
+                (ShortcutAction::ToggleWindow, "Alt+Shift+R"),
+            ]
+        );
+    }
 }
@@ -1,32 +1,279 @@
-use crate::settings::model::Settings;
-use std::{fs, path::PathBuf};
+use crate::settings::migrations::{self, CURRENT_VERSION};
+use crate::settings::model::{Settings, ShortcutAction};
+use std::{fs, path::Path, path::PathBuf};
 
-pub fn load_or_init(app_config_dir: PathBuf) -> anyhow::Result<Settings> {
-    let path = Settings::config_path(app_config_dir);
+/// Loads the settings file, migrating it to the current version (the old
+/// file is kept as `settings.toml.bak`). A file that can't be parsed is
+/// renamed to `settings.toml.broken-<timestamp>` and replaced by defaults,
+/// so a bad edit never keeps the app from starting; that path is returned
+/// so the user can be told.
+pub fn load_or_init(app_config_dir: PathBuf) -> anyhow::Result<(Settings, Option<PathBuf>)> {
+    load_or_init_file(Settings::config_path(app_config_dir))
+}
 
+fn load_or_init_file(path: PathBuf) -> anyhow::Result<(Settings, Option<PathBuf>)> {
     if !path.exists() {
         let settings = Settings::default();
         save(&path, &settings)?;
-        return Ok(settings);
+        return Ok((settings, None));
     }
 
     let contents = fs::read_to_string(&path)?;
-    let mut loaded: Settings = toml::from_str(&contents)?;
+    let (settings, migrated) = match parse(&contents) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            let broken = path.with_extension(format!("toml.broken-{}", chrono::Utc::now().timestamp()));
+            fs::rename(&path, &broken)?;
+            log::warn!("⚠️ Settings file is invalid ({}); kept it as {} and using defaults", e, broken.display());
+            let settings = Settings::default();
+            save(&path, &settings)?;
+            return Ok((settings, Some(broken)));
+        }
+    };
 
-    // simple migration: ensure version and future defaults
-    if loaded.version == 0 {
-        loaded.version = 1;
+    if migrated {
+        fs::copy(&path, path.with_extension("toml.bak"))?;
+        log::info!("✓ Settings migrated to version {}", CURRENT_VERSION);
+    }
+    // Normalize the file, unless a newer build wrote fields we'd drop
+    if settings.version <= CURRENT_VERSION {
+        save(&path, &settings)?;
     }
+    Ok((settings, None))
+}
 
-    save(&path, &loaded)?; // normalize file
-    Ok(loaded)
+/// Read-only variant for secondary processes (CLI): never writes the file
+/// (migrations only apply in memory), falls back to defaults when it
+/// doesn't exist yet.
+pub fn load(app_config_dir: PathBuf) -> anyhow::Result<Settings> {
+    read_file(&Settings::config_path(app_config_dir))
+}
+
+fn read_file(path: &Path) -> anyhow::Result<Settings> {
+    if !path.exists() {
+        return Ok(Settings::default());
+    }
+
+    let contents = fs::read_to_string(path)?;
+    Ok(parse(&contents)?.0)
+}
+
+/// Parses a settings document of any version; `true` if it was migrated.
+fn parse(contents: &str) -> anyhow::Result<(Settings, bool)> {
+    let mut doc: toml::Table = toml::from_str(contents)?;
+    let migrated = migrations::migrate(&mut doc)?;
+    Ok((toml::Value::Table(doc).try_into()?, migrated))
 }
 
 pub fn save(path: &PathBuf, settings: &Settings) -> anyhow::Result<()> {
-    let toml_str = toml::to_string_pretty(settings)?;
+    write_toml(path, &toml::to_string_pretty(&to_table(settings)?)?)
+}
+
+/// Fields a vault can set for itself; all others are app-wide.
+pub const VAULT_FIELDS: [&str; 4] = ["notes_folder", "delete_behavior", "auto_save_interval_secs", "encryption"];
+
+/// Where the open vault's settings live: the app-wide file in the config
+/// dir, plus the vault's own overrides file, if it has one (the default
+/// vault keeps everything in the app-wide file).
+#[derive(Debug, Clone)]
+pub struct SettingsFiles {
+    pub app: PathBuf,
+    pub vault: Option<PathBuf>,
+}
+
+impl SettingsFiles {
+    /// App-wide settings (see `load_or_init`) with the vault's overrides.
+    pub fn load(&self) -> anyhow::Result<(Settings, Option<PathBuf>)> {
+        let (settings, broken) = load_or_init_file(self.app.clone())?;
+        Ok((self.with_overrides(settings)?, broken))
+    }
+
+    /// Read-only variant of [`SettingsFiles::load`] (see [`load`]).
+    pub fn read(&self) -> anyhow::Result<Settings> {
+        self.with_overrides(read_file(&self.app)?)
+    }
+
+    fn with_overrides(&self, settings: Settings) -> anyhow::Result<Settings> {
+        let Some(vault) = self.vault.as_ref().filter(|path| path.exists()) else {
+            return Ok(settings);
+        };
+
+        let overrides: toml::Table = toml::from_str(&fs::read_to_string(vault)?)?;
+        let mut doc = to_table(&settings)?;
+        for field in VAULT_FIELDS {
+            if let Some(value) = overrides.get(field) {
+                doc.insert(field.to_string(), value.clone());
+            }
+        }
+        Ok(toml::Value::Table(doc).try_into()?)
+    }
+
+    /// Saves vault fields to the vault's file and the rest app-wide; the
+    /// app-wide file keeps its own values for the vault fields.
+    pub fn save(&self, settings: &Settings) -> anyhow::Result<()> {
+        let Some(vault) = &self.vault else {
+            return save(&self.app, settings);
+        };
+
+        let mut doc = to_table(settings)?;
+        let current: toml::Table = fs::read_to_string(&self.app)
+            .ok()
+            .and_then(|contents| toml::from_str(&contents).ok())
+            .unwrap_or_default();
+        let mut overrides = toml::Table::new();
+        for field in VAULT_FIELDS {
+            if let Some(value) = doc.remove(field) {
+                overrides.insert(field.to_string(), value);
+            }
+            if let Some(value) = current.get(field) {
+                doc.insert(field.to_string(), value.clone());
+            }
+        }
+
+        write_toml(&self.app, &toml::to_string_pretty(&doc)?)?;
+        write_toml(vault, &toml::to_string_pretty(&overrides)?)
+    }
+}
+
+/// `settings` as a TOML table. TOML has no null, so shortcuts that are
+/// off are written as `""` rather than left out (which reads back as the
+/// default shortcut).
+fn to_table(settings: &Settings) -> anyhow::Result<toml::Table> {
+    let mut table = match toml::Value::try_from(settings)? {
+        toml::Value::Table(table) => table,
+        other => anyhow::bail!("settings serialized as {}", other.type_str()),
+    };
+    if let Some(toml::Value::Table(shortcuts)) = table.get_mut("shortcuts") {
+        for action in ShortcutAction::ALL {
+            shortcuts.entry(action.field()).or_insert_with(|| toml::Value::String(String::new()));
+        }
+    }
+    Ok(table)
+}
+
+fn write_toml(path: &Path, contents: &str) -> anyhow::Result<()> {
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)?;
     }
-    fs::write(path, toml_str)?;
+    fs::write(path, contents)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct ConfigDir(PathBuf);
+
+    impl ConfigDir {
+        fn new(settings: Option<&str>) -> Self {
+            let dir = std::env::temp_dir().join(format!("rusty-notes-config-{}", uuid::Uuid::new_v4()));
+            fs::create_dir_all(&dir).unwrap();
+            if let Some(contents) = settings {
+                fs::write(dir.join("settings.toml"), contents).unwrap();
+            }
+            Self(dir)
+        }
+
+        fn files(&self) -> Vec<String> {
+            let mut names: Vec<String> = fs::read_dir(&self.0)
+                .unwrap()
+                .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+                .collect();
+            names.sort();
+            names
+        }
+
+        fn read(&self, name: &str) -> String {
+            fs::read_to_string(self.0.join(name)).unwrap()
+        }
+    }
+
+    impl Drop for ConfigDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn is_broken_copy(name: &str) -> bool {
+        Path::new(name).extension().is_some_and(|ext| ext.to_string_lossy().starts_with("broken-"))
+    }
+
+    #[test]
+    fn old_file_is_migrated_with_a_backup() {
+        let old = "notes_folder = \"/my/notes\"\nonboarding_completed = true\n";
+        let dir = ConfigDir::new(Some(old));
+
+        let (settings, broken) = load_or_init(dir.0.clone()).unwrap();
+        assert!(broken.is_none());
+        assert_eq!(settings.version, CURRENT_VERSION);
+        assert_eq!(settings.notes_folder, "/my/notes");
+        assert!(settings.onboarding_completed);
+        // Missing fields got their defaults
+        assert_eq!(settings.auto_save_interval_secs, Settings::default().auto_save_interval_secs);
+
+        assert_eq!(dir.read("settings.toml.bak"), old);
+        assert!(dir.read("settings.toml").contains(&format!("version = {}", CURRENT_VERSION)));
+    }
+
+    #[test]
+    fn broken_file_is_kept_and_defaults_used() {
+        let dir = ConfigDir::new(Some("notes_folder = [oops"));
+
+        let (settings, kept) = load_or_init(dir.0.clone()).unwrap();
+        assert_eq!(settings.notes_folder, Settings::default().notes_folder);
+
+        let files = dir.files();
+        let broken = files.iter().find(|name| is_broken_copy(name)).expect("broken file kept");
+        assert_eq!(dir.read(broken), "notes_folder = [oops");
+        // The caller learns where it went
+        assert_eq!(kept, Some(dir.0.join(broken)));
+        assert!(files.contains(&"settings.toml".to_string()));
+    }
+
+    #[test]
+    fn current_file_is_not_backed_up() {
+        let dir = ConfigDir::new(None);
+        load_or_init(dir.0.clone()).unwrap();
+        load_or_init(dir.0.clone()).unwrap();
+        assert_eq!(dir.files(), vec!["settings.toml"]);
+    }
+
+    #[test]
+    fn shortcuts_turned_off_stay_off() {
+        let dir = ConfigDir::new(None);
+        let mut settings = Settings::default();
+        settings.shortcuts.new_note = None;
+        save(&Settings::config_path(dir.0.clone()), &settings).unwrap();
+
+        let (loaded, _) = load_or_init(dir.0.clone()).unwrap();
+        assert_eq!(loaded.shortcuts.get(ShortcutAction::NewNote), None);
+        assert_eq!(loaded.shortcuts.quick_capture, Settings::default().shortcuts.quick_capture);
+    }
+
+    #[test]
+    fn vault_fields_go_to_the_vault_file() {
+        let dir = ConfigDir::new(Some("notes_folder = \"/personal\"\nonboarding_completed = true\n"));
+        let files = SettingsFiles {
+            app: dir.0.join("settings.toml"),
+            vault: Some(dir.0.join("work").join("settings.toml")),
+        };
+
+        let (mut settings, _) = files.load().unwrap();
+        assert_eq!(settings.notes_folder, "/personal");
+        settings.notes_folder = "/work".to_string();
+        settings.auto_save_interval_secs = 5;
+        settings.onboarding_completed = false;
+        files.save(&settings).unwrap();
+
+        let (reloaded, _) = files.load().unwrap();
+        assert_eq!((reloaded.notes_folder.as_str(), reloaded.auto_save_interval_secs), ("/work", 5));
+        assert!(!reloaded.onboarding_completed);
+
+        // The app-wide file (default vault) is untouched for vault fields
+        let (app, _) = load_or_init(dir.0.clone()).unwrap();
+        assert_eq!(app.notes_folder, "/personal");
+        assert_eq!(app.auto_save_interval_secs, Settings::default().auto_save_interval_secs);
+        assert!(!app.onboarding_completed);
+    }
+}
@@ -0,0 +1,209 @@
+// src-tauri/src/settings/validation.rs
+//! Checks a settings update before it is saved. Every problem is reported
+//! against its field, so the settings form can show it next to the input.
+
+use std::fs;
+use std::path::Path;
+
+use std::fmt::Write;
+
+use chrono::NaiveDate;
+use tauri_plugin_global_shortcut::{Modifiers, Shortcut};
+
+use crate::error::{AppError, AppResult, FieldError};
+use crate::settings::{Settings, ShortcutAction, ShortcutSettings};
+
+/// Autosave bounds: at least every hour, at most every second.
+const AUTO_SAVE_SECS: std::ops::RangeInclusive<u64> = 1..=3600;
+
+/// More recent notes than this make the tray menu unwieldy.
+const MAX_TRAY_NOTES: usize = 20;
+
+/// Validates `new` (with `notes_folder` already resolved) against the
+/// settings in use. Creates the notes folder if needed and makes sure it is
+/// writable, so a bad folder never reaches the settings file.
+pub fn validate_update(new: &Settings, current: &Settings) -> AppResult<()> {
+    let mut errors = check(new, current);
+    if !errors.iter().any(|e| e.field == "notes_folder") {
+        if let Err(message) = ensure_writable(Path::new(&new.notes_folder)) {
+            errors.push(FieldError::new("notes_folder", message));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(AppError::InvalidFields(errors))
+    }
+}
+
+/// Checks that need no disk access.
+fn check(new: &Settings, current: &Settings) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+
+    if new.notes_folder.trim().is_empty() {
+        errors.push(FieldError::new("notes_folder", "Choose a folder for your notes"));
+    } else if !Path::new(&new.notes_folder).is_absolute() {
+        errors.push(FieldError::new("notes_folder", "The notes folder must be an absolute path"));
+    }
+
+    if !AUTO_SAVE_SECS.contains(&new.auto_save_interval_secs) {
+        errors.push(FieldError::new(
+            "auto_save_interval_secs",
+            format!(
+                "Autosave interval must be between {} and {} seconds",
+                AUTO_SAVE_SECS.start(),
+                AUTO_SAVE_SECS.end()
+            ),
+        ));
+    }
+
+    if new.version < current.version {
+        errors.push(FieldError::new(
+            "version",
+            format!("Settings version can't go back from {} to {}", current.version, new.version),
+        ));
+    }
+
+    if new.api.port < 1024 {
+        errors.push(FieldError::new("api.port", "Port must be between 1024 and 65535"));
+    }
+    if new.api.max_upload_mb == 0 {
+        errors.push(FieldError::new("api.max_upload_mb", "Upload limit must be at least 1 MiB"));
+    }
+
+    if new.quick_capture.notebook.trim().is_empty() {
+        errors.push(FieldError::new("quick_capture.notebook", "Choose a notebook for captured notes"));
+    }
+
+    if new.tray.recent_notes > MAX_TRAY_NOTES {
+        errors.push(FieldError::new(
+            "tray.recent_notes",
+            format!("The tray can list at most {} notes", MAX_TRAY_NOTES),
+        ));
+    }
+
+    let title_format = &new.daily_notes.title_format;
+    if title_format.trim().is_empty() {
+        errors.push(FieldError::new("daily_notes.title_format", "Enter a title format for daily notes"));
+    } else if write!(String::new(), "{}", NaiveDate::MIN.format(title_format)).is_err() {
+        errors.push(FieldError::new("daily_notes.title_format", "Not a valid date format"));
+    }
+
+    errors.extend(check_shortcuts(&new.shortcuts));
+    errors
+}
+
+/// Every shortcut must parse, use a modifier (a bare key would be taken
+/// from every other app) and differ from the others.
+fn check_shortcuts(shortcuts: &ShortcutSettings) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    let mut taken: Vec<(ShortcutAction, Shortcut)> = Vec::new();
+
+    for (action, accelerator) in shortcuts.bindings() {
+        let field = format!("shortcuts.{}", action.field());
+        let shortcut: Shortcut = match accelerator.parse() {
+            Ok(shortcut) => shortcut,
+            Err(e) => {
+                errors.push(FieldError::new(field, format!("\"{}\" is not a valid shortcut: {}", accelerator, e)));
+                continue;
+            }
+        };
+        if shortcut.mods == Modifiers::empty() || shortcut.mods == Modifiers::SHIFT {
+            errors.push(FieldError::new(field, "Shortcuts need Ctrl, Alt or Cmd"));
+        } else if let Some((other, _)) = taken.iter().find(|(_, s)| *s == shortcut) {
+            errors.push(FieldError::new(field, format!("Already used for {}", other.label())));
+        } else {
+            taken.push((action, shortcut));
+        }
+    }
+    errors
+}
+
+/// Creates `folder` if missing and writes (then removes) a probe file.
+pub(crate) fn ensure_writable(folder: &Path) -> Result<(), String> {
+    if folder.exists() && !folder.is_dir() {
+        return Err("The notes folder path points to a file".to_string());
+    }
+    fs::create_dir_all(folder).map_err(|e| format!("Can't create the notes folder: {}", e))?;
+
+    let probe = folder.join(format!(".rusty-notes-write-test-{}", uuid::Uuid::new_v4()));
+    fs::write(&probe, b"").map_err(|e| format!("The notes folder is not writable: {}", e))?;
+    let _ = fs::remove_file(&probe);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn valid(folder: &Path) -> Settings {
+        Settings { notes_folder: folder.to_string_lossy().to_string(), ..Settings::default() }
+    }
+
+    fn fields(result: AppResult<()>) -> Vec<String> {
+        match result {
+            Err(AppError::InvalidFields(errors)) => errors.into_iter().map(|e| e.field).collect(),
+            other => panic!("expected field errors, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn reports_every_bad_field() {
+        let current = Settings { version: 2, ..Settings::default() };
+        let new = Settings {
+            notes_folder: " ".to_string(),
+            auto_save_interval_secs: 0,
+            version: 1,
+            ..Settings::default()
+        };
+        assert_eq!(fields(validate_update(&new, &current)), vec!["notes_folder", "auto_save_interval_secs", "version"]);
+    }
+
+    #[test]
+    fn rejects_invalid_bare_and_duplicate_shortcuts() {
+        let shortcuts = ShortcutSettings {
+            new_note: Some("Ctrl+Alt+N".to_string()),
+            quick_capture: Some("Ctrl+Nope".to_string()),
+            search: Some("Shift+F".to_string()),
+            toggle_window: Some("alt+ctrl+n".to_string()),
+        };
+        let errors = check_shortcuts(&shortcuts);
+        let fields: Vec<_> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, vec!["shortcuts.quick_capture", "shortcuts.search", "shortcuts.toggle_window"]);
+        assert!(errors[2].message.contains("New note"));
+
+        assert!(check_shortcuts(&ShortcutSettings::default()).is_empty());
+    }
+
+    #[test]
+    fn rejects_a_bad_daily_title_format() {
+        for (format, bad) in [("%A, %B %-d %Y", false), ("Day %Q", true), (" ", true)] {
+            let mut settings = Settings::default();
+            settings.daily_notes.title_format = format.to_string();
+            let errors = check(&settings, &Settings::default());
+            assert_eq!(errors.iter().any(|e| e.field == "daily_notes.title_format"), bad, "{}", format);
+        }
+    }
+
+    #[test]
+    fn creates_a_missing_folder() {
+        let dir = std::env::temp_dir().join(format!("rusty-notes-settings-{}", uuid::Uuid::new_v4()));
+        let folder = dir.join("nested").join("notes");
+
+        validate_update(&valid(&folder), &Settings::default()).unwrap();
+        assert!(folder.is_dir());
+        assert_eq!(fs::read_dir(&folder).unwrap().count(), 0, "probe file left behind");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn rejects_a_file_as_notes_folder() {
+        let file = std::env::temp_dir().join(format!("rusty-notes-settings-{}.txt", uuid::Uuid::new_v4()));
+        fs::write(&file, b"x").unwrap();
+
+        let result = validate_update(&valid(&file), &Settings::default());
+        fs::remove_file(&file).unwrap();
+        assert_eq!(fields(result), vec!["notes_folder"]);
+    }
+}
@@ -1,14 +1,59 @@
 pub mod notes;
+pub mod window;
 
 use tauri::AppHandle;
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
 
-pub fn register_all(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
-    println!("➡️ register_all() called");
-    notes::register_all(app)?;
+use crate::settings::{ShortcutAction, ShortcutSettings};
+
+/// Registers a global shortcut for every action configured in `shortcuts`.
+/// One that fails (e.g. taken by another app) doesn't stop the others.
+pub fn register_all(app: &AppHandle, shortcuts: &ShortcutSettings) -> Result<(), Box<dyn std::error::Error>> {
+    let mut failures = Vec::new();
+
+    for (action, accelerator) in shortcuts.bindings() {
+        if let Err(e) = register(app, action, accelerator) {
+            failures.push(format!("{} ({}): {}", action.label(), accelerator, e));
+            continue;
+        }
+        println!("  ✓ Shortcut {} → {}", accelerator, action.label());
+    }
+
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(failures.join("; ").into())
+    }
+}
+
+fn register(app: &AppHandle, action: ShortcutAction, accelerator: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let shortcut: Shortcut = accelerator.parse()?;
+    app.global_shortcut().on_shortcut(shortcut, move |app, _shortcut, event| {
+        if event.state() == ShortcutState::Pressed {
+            trigger(app, action);
+        }
+    })?;
     Ok(())
 }
 
+fn trigger(app: &AppHandle, action: ShortcutAction) {
+    match action {
+        ShortcutAction::NewNote => notes::create(app),
+        ShortcutAction::QuickCapture => notes::quick_capture(app),
+        ShortcutAction::Search => notes::search(app),
+        ShortcutAction::ToggleWindow => window::toggle(app),
+    }
+}
+
+/// Removes every shortcut this app registered.
 pub fn unregister_all(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
-    notes::unregister_all(app)?;
+    app.global_shortcut().unregister_all()?;
     Ok(())
 }
+
+/// Drops and re-adds every shortcut (after a settings change).
+pub fn reregister_all(app: &AppHandle, shortcuts: &ShortcutSettings) -> Result<(), Box<dyn std::error::Error>> {
+    // Nothing registered (e.g. it failed at startup) is fine here
+    let _ = unregister_all(app);
+    register_all(app, shortcuts)
+}
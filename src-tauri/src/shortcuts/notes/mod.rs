@@ -1,18 +1,20 @@
 mod create;
 
-use tauri::AppHandle;
+use tauri::{AppHandle, Emitter};
 
-/// Register all note-related shortcuts
-pub fn register_all(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
-    create::register(app)?;
-    
-    println!("  ✓ Note shortcuts registered");
-    Ok(())
+use crate::shortcuts::window;
+
+pub use create::create;
+
+/// Opens the quick capture window.
+pub fn quick_capture(app: &AppHandle) {
+    window::open_quick_capture(app);
 }
 
-/// Unregister all note shortcuts
-pub fn unregister_all(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
-    create::unregister(app)?;
-    
-    Ok(())
+/// Brings the window up with the search field focused.
+pub fn search(app: &AppHandle) {
+    window::show(app);
+    if let Err(e) = app.emit("focus-search", ()) {
+        eprintln!("Failed to emit focus-search event: {e}");
+    }
 }
@@ -0,0 +1,74 @@
+use tauri::{AppHandle, Emitter, Manager, WebviewUrl, WebviewWindow, WebviewWindowBuilder};
+
+/// Label of the window from `tauri.conf.json`.
+pub const MAIN_WINDOW: &str = "main";
+/// Label (and route) of the quick capture window.
+pub const QUICK_CAPTURE_WINDOW: &str = "quick-capture";
+
+fn main_window(app: &AppHandle) -> Option<WebviewWindow> {
+    let window = app.get_webview_window(MAIN_WINDOW);
+    if window.is_none() {
+        eprintln!("❌ No {} window", MAIN_WINDOW);
+    }
+    window
+}
+
+/// Shows, restores and focuses the main window.
+pub fn show(app: &AppHandle) {
+    let Some(window) = main_window(app) else { return };
+    let result = window.show().and_then(|_| window.unminimize()).and_then(|_| window.set_focus());
+    if let Err(e) = result {
+        eprintln!("Failed to show window: {e}");
+    }
+}
+
+/// Hides the main window if it's visible, shows it otherwise.
+pub fn toggle(app: &AppHandle) {
+    let Some(window) = main_window(app) else { return };
+    match window.is_visible() {
+        Ok(true) => {
+            if let Err(e) = window.hide() {
+                eprintln!("Failed to hide window: {e}");
+            }
+        }
+        _ => show(app),
+    }
+}
+
+/// Shows the quick capture window on top of everything, creating it on
+/// first use. The main window is left as it is.
+pub fn open_quick_capture(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window(QUICK_CAPTURE_WINDOW) {
+        if let Err(e) = window.show().and_then(|_| window.set_focus()) {
+            eprintln!("Failed to show quick capture: {e}");
+        }
+        // The page is kept while hidden; let it reset and prefill again
+        if let Err(e) = app.emit_to(QUICK_CAPTURE_WINDOW, "quick-capture-opened", ()) {
+            eprintln!("Failed to emit quick-capture-opened event: {e}");
+        }
+        return;
+    }
+
+    let built = WebviewWindowBuilder::new(app, QUICK_CAPTURE_WINDOW, WebviewUrl::App(QUICK_CAPTURE_WINDOW.into()))
+        .title("Quick capture")
+        .inner_size(480.0, 320.0)
+        .resizable(false)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .center()
+        .focused(true)
+        .build();
+    if let Err(e) = built {
+        eprintln!("❌ Failed to open quick capture: {e}");
+    }
+}
+
+/// Hides (rather than closes) the quick capture window, so it opens
+/// instantly next time.
+pub fn hide_quick_capture(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window(QUICK_CAPTURE_WINDOW) {
+        if let Err(e) = window.hide() {
+            eprintln!("Failed to hide quick capture: {e}");
+        }
+    }
+}
@@ -0,0 +1,205 @@
+// src-tauri/src/tray.rs
+//! Tray icon with quick actions (new note, quick capture, search), the
+//! recently edited notes and quit. The recent list follows note events.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use tauri::menu::{IsMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu};
+use tauri::tray::TrayIconBuilder;
+use tauri::{AppHandle, Emitter, Manager, Window, WindowEvent};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::Mutex;
+
+use crate::db::schema::{Note, NoteFilter};
+use crate::error::AppError;
+use crate::services::{AppEvent, EventBus};
+use crate::settings::{Settings, TraySettings};
+use crate::shortcuts::{notes, window};
+use crate::vaults::StorageSlot;
+
+type SettingsState = Arc<Mutex<Settings>>;
+
+const TRAY_ID: &str = "main";
+/// Menu ids of recent notes are this plus the note id.
+const NOTE_PREFIX: &str = "note:";
+/// Longest note title shown in the menu.
+const LABEL_CHARS: usize = 40;
+
+/// `settings.tray.close_to_tray` for the window close handler, which
+/// can't wait for the settings lock.
+pub struct CloseToTray(AtomicBool);
+
+impl CloseToTray {
+    pub fn new(enabled: bool) -> Self {
+        Self(AtomicBool::new(enabled))
+    }
+}
+
+/// Adds the tray icon (recent notes follow via [`follow_notes`]).
+pub fn create(app: &AppHandle) -> tauri::Result<()> {
+    let menu = menu(app, &[])?;
+    let mut tray = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip("rusty-notes")
+        .menu(&menu)
+        .show_menu_on_left_click(true)
+        .on_menu_event(|app, event| on_menu_event(app, event.id().as_ref()));
+    if let Some(icon) = app.default_window_icon() {
+        tray = tray.icon(icon.clone());
+    }
+    tray.build(app)?;
+    Ok(())
+}
+
+/// Applies saved tray settings; the menu is rebuilt for `recent_notes`.
+pub async fn apply(app: &AppHandle, settings: &TraySettings) {
+    app.state::<CloseToTray>().0.store(settings.close_to_tray, Ordering::Relaxed);
+    refresh(app).await;
+}
+
+/// Keeps the recent notes current until the bus closes.
+pub async fn follow_notes(app: AppHandle, bus: EventBus) {
+    let mut rx = bus.subscribe();
+    refresh(&app).await;
+    loop {
+        match rx.recv().await {
+            Ok(envelope) => match envelope.event {
+                AppEvent::AttachmentAdded { .. } | AppEvent::SettingsChanged => continue,
+                _ => refresh(&app).await,
+            },
+            Err(RecvError::Lagged(_)) => refresh(&app).await,
+            Err(RecvError::Closed) => break,
+        }
+    }
+}
+
+/// Hides the main window instead of closing it when `close_to_tray` is on.
+pub fn on_window_event(window: &Window, event: &WindowEvent) {
+    if let WindowEvent::CloseRequested { api, .. } = event {
+        let close_to_tray = window.state::<CloseToTray>().0.load(Ordering::Relaxed);
+        if window.label() == window::MAIN_WINDOW && close_to_tray {
+            api.prevent_close();
+            if let Err(e) = window.hide() {
+                eprintln!("Failed to hide window: {e}");
+            }
+        }
+    }
+}
+
+/// Rebuilds the menu with the latest recent notes.
+async fn refresh(app: &AppHandle) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else { return };
+    let limit = app.state::<SettingsState>().lock().await.tray.recent_notes;
+    let notes = match app.state::<StorageSlot>().get().list_notes(&NoteFilter::default()).await {
+        // Pinned ones come first in the list
+        Ok(mut notes) => {
+            notes.sort_by_key(|note| std::cmp::Reverse(note.updated_at));
+            notes
+        }
+        // Titles are sealed while an encrypted vault is locked
+        Err(AppError::Locked) => Vec::new(),
+        Err(e) => {
+            eprintln!("❌ Failed to list recent notes for the tray: {}", e);
+            Vec::new()
+        }
+    };
+
+    let result = menu(app, &recent(&notes, limit)).and_then(|menu| tray.set_menu(Some(menu)));
+    if let Err(e) = result {
+        eprintln!("❌ Failed to update the tray menu: {}", e);
+    }
+}
+
+fn menu(app: &AppHandle, recent: &[(String, String)]) -> tauri::Result<Menu<tauri::Wry>> {
+    let item = |id: &str, text: &str| MenuItem::with_id(app, id, text, true, None::<&str>);
+
+    let notes = recent
+        .iter()
+        .map(|(id, label)| item(&format!("{}{}", NOTE_PREFIX, id), label))
+        .collect::<tauri::Result<Vec<_>>>()?;
+    let notes: Vec<&dyn IsMenuItem<tauri::Wry>> = notes.iter().map(|n| n as &dyn IsMenuItem<tauri::Wry>).collect();
+    let recent = Submenu::with_items(app, "Recent notes", !notes.is_empty(), &notes)?;
+
+    Menu::with_items(app, &[
+        &item("new-note", "New note")?,
+        &item("quick-capture", "Quick capture")?,
+        &item("search", "Search")?,
+        &PredefinedMenuItem::separator(app)?,
+        &recent,
+        &PredefinedMenuItem::separator(app)?,
+        &item("show", "Show window")?,
+        &item("quit", "Quit")?,
+    ])
+}
+
+fn on_menu_event(app: &AppHandle, id: &str) {
+    match id {
+        "new-note" => notes::create(app),
+        "quick-capture" => notes::quick_capture(app),
+        "search" => notes::search(app),
+        "show" => window::show(app),
+        "quit" => app.exit(0),
+        _ => {
+            if let Some(note_id) = id.strip_prefix(NOTE_PREFIX) {
+                window::show(app);
+                if let Err(e) = app.emit_to(window::MAIN_WINDOW, "open-note", note_id) {
+                    eprintln!("Failed to emit open-note event: {e}");
+                }
+            }
+        }
+    }
+}
+
+/// Menu id and label of the first `limit` notes (already newest first).
+fn recent(notes: &[Note], limit: usize) -> Vec<(String, String)> {
+    notes
+        .iter()
+        .take(limit)
+        .map(|note| {
+            let title = note.title.trim();
+            let label = if title.is_empty() {
+                "Untitled".to_string()
+            } else if title.chars().count() > LABEL_CHARS {
+                let short: String = title.chars().take(LABEL_CHARS - 1).collect();
+                format!("{}…", short.trim_end())
+            } else {
+                title.to_string()
+            };
+            (note.id.clone(), label)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(id: &str, title: &str) -> Note {
+        Note {
+            id: id.to_string(),
+            title: title.to_string(),
+            body: String::new(),
+            created_at: 0,
+            updated_at: 0,
+            file_path: String::new(),
+            notebook: None,
+            version: 0,
+            pinned: false,
+            favorite: false,
+            archived: false,
+        }
+    }
+
+    #[test]
+    fn lists_the_first_notes_with_readable_labels() {
+        let long = "x".repeat(50);
+        let notes = [note("a", " Plan "), note("b", ""), note("c", &long), note("d", "Old")];
+
+        let items = recent(&notes, 3);
+        let labels: Vec<_> = items.iter().map(|(_, label)| label.as_str()).collect();
+        assert_eq!(labels[..2], ["Plan", "Untitled"]);
+        assert_eq!(labels[2].chars().count(), LABEL_CHARS);
+        assert_eq!(items[2].0, "c");
+        assert!(recent(&notes, 0).is_empty());
+    }
+}
@@ -0,0 +1,133 @@
+// src-tauri/src/vaults/mod.rs
+//! Vaults: separate sets of notes (e.g. work and personal), each with its
+//! own DB, notes folder and settings overrides
+//! ([`crate::settings::storage::VAULT_FIELDS`]). The default vault is the
+//! app data dir itself, so installs from before vaults keep their layout.
+//!
+//! The open vault's [`DbStorage`] sits in a [`StorageSlot`] and its
+//! settings in the usual settings state, so switching swaps both in place.
+
+pub mod registry;
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+use tokio::sync::Mutex;
+
+use crate::db;
+use crate::error::{AppError, AppResult};
+use crate::services::{DbStorage, EventBus, FolderMove};
+use crate::settings::{Settings, SettingsFiles};
+
+pub use registry::{Vault, VaultRegistry, DEFAULT_VAULT_ID};
+
+/// Storage of the open vault, as app state. Callers take a clone per call,
+/// so a switch only affects calls made after it.
+pub struct StorageSlot(RwLock<DbStorage>);
+
+impl StorageSlot {
+    pub fn new(storage: DbStorage) -> Self {
+        Self(RwLock::new(storage))
+    }
+
+    pub fn get(&self) -> DbStorage {
+        self.0.read().expect("storage slot poisoned").clone()
+    }
+
+    /// Puts `storage` in place and returns the previous one.
+    pub fn replace(&self, storage: DbStorage) -> DbStorage {
+        std::mem::replace(&mut *self.0.write().expect("storage slot poisoned"), storage)
+    }
+}
+
+/// The open vault and where its settings are saved.
+#[derive(Debug, Clone)]
+pub struct OpenVault {
+    pub vault: Vault,
+    pub settings_files: SettingsFiles,
+    /// Where an unreadable settings file was kept when defaults replaced it.
+    pub settings_recovered_from: Option<PathBuf>,
+}
+
+pub type OpenVaultState = RwLock<OpenVault>;
+
+/// A vault ready to be swapped in.
+pub struct LoadedVault {
+    pub vault: Vault,
+    pub settings: Settings,
+    pub settings_files: SettingsFiles,
+    pub settings_recovered_from: Option<PathBuf>,
+    pub storage: DbStorage,
+}
+
+impl LoadedVault {
+    /// What the open vault state keeps once this vault is swapped in.
+    pub fn open_vault(&self) -> OpenVault {
+        OpenVault {
+            vault: self.vault.clone(),
+            settings_files: self.settings_files.clone(),
+            settings_recovered_from: self.settings_recovered_from.clone(),
+        }
+    }
+}
+
+/// Puts `loaded` in `slot` and `settings`, returning what the open vault
+/// state needs. Multi-step writes that already started on the old storage
+/// finish first; like them, this takes the write lock before settings
+/// (their file writes lock settings too), so a switch can't deadlock.
+pub async fn swap_in(slot: &StorageSlot, settings: &Mutex<Settings>, loaded: LoadedVault) -> OpenVault {
+    let open = loaded.open_vault();
+    let old = slot.get();
+    let _writes = old.lock_writes().await;
+    let mut settings = settings.lock().await;
+    slot.replace(loaded.storage);
+    *settings = loaded.settings;
+    open
+}
+
+/// Loads the vault's settings and opens its DB, publishing on the app's
+/// `events`. Also finishes what an earlier run left undone: a notes folder
+/// move cut short after it started switching, absolute file paths, and
+/// notes whose links aren't indexed yet.
+pub async fn load(vault: &Vault, config_dir: &Path, events: EventBus) -> AppResult<LoadedVault> {
+    std::fs::create_dir_all(&vault.data_dir).map_err(AppError::io("Failed to create vault dir"))?;
+
+    let settings_files = vault.settings_files(config_dir);
+    let (mut settings, settings_recovered_from) = settings_files.load().unwrap_or_else(|e| {
+        // Unreadable config: run on defaults, don't refuse to open
+        log::error!("❌ Failed to load settings of vault {}, using defaults: {}", vault.name, e);
+        (Settings::default(), None)
+    });
+    // Relative folders (the default `./notes`) live in the vault
+    settings.resolve_notes_folder(&vault.data_dir);
+
+    let pool = db::init_database(&vault.data_dir).await?;
+    let storage = DbStorage::with_events(pool, vault.data_dir.clone(), events);
+    storage.set_notes_folder(&settings.notes_folder);
+
+    let settings = Arc::new(Mutex::new(settings));
+    let mover = FolderMove::new(storage.clone(), settings.clone(), settings_files.clone(), &vault.data_dir);
+    match mover.resume_interrupted().await {
+        Ok(Some(report)) => log::info!("✓ Finished moving notes folder to {}", report.to),
+        Ok(None) => {}
+        Err(e) => log::error!("❌ Failed to finish moving notes folder: {}", e),
+    }
+
+    // Rows written before paths were stored relative to the notes folder
+    match storage.store_paths_relative().await {
+        Ok(0) => {}
+        Ok(changed) => log::info!("✓ Stored {} file paths relative to the notes folder", changed),
+        Err(e) => log::error!("❌ Failed to migrate file paths: {}", e),
+    }
+
+    // Notes stored before links were indexed (a locked vault waits for unlock)
+    match storage.index_unindexed_links().await {
+        Ok(0) | Err(AppError::Locked) => {}
+        Ok(indexed) => log::info!("✓ Indexed links of {} notes", indexed),
+        Err(e) => log::error!("❌ Failed to index note links: {}", e),
+    }
+
+    let settings = settings.lock().await.clone();
+    log::info!("✓ Vault {} loaded: {}", vault.name, settings.notes_folder);
+    Ok(LoadedVault { vault: vault.clone(), settings, settings_files, settings_recovered_from, storage })
+}
@@ -0,0 +1,210 @@
+// src-tauri/src/vaults/registry.rs
+//! `vaults.toml` in the config dir: every known vault and the one opened
+//! last.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, AppResult};
+use crate::settings::{Settings, SettingsFiles};
+
+/// Id of the vault that lives directly in the app data dir.
+pub const DEFAULT_VAULT_ID: &str = "default";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Vault {
+    pub id: String,
+    pub name: String,
+    /// Holds the vault's DB, its default notes folder and its settings
+    pub data_dir: PathBuf,
+}
+
+impl Vault {
+    /// Where this vault's settings are read from and saved to.
+    pub fn settings_files(&self, config_dir: &Path) -> SettingsFiles {
+        SettingsFiles {
+            app: Settings::config_path(config_dir.to_path_buf()),
+            vault: (self.id != DEFAULT_VAULT_ID).then(|| self.data_dir.join("settings.toml")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VaultRegistry {
+    #[serde(default)]
+    pub last_opened: Option<String>,
+    #[serde(default)]
+    pub vaults: Vec<Vault>,
+}
+
+impl VaultRegistry {
+    pub fn path(config_dir: &Path) -> PathBuf {
+        config_dir.join("vaults.toml")
+    }
+
+    /// Only the default vault.
+    pub fn new(app_data_dir: &Path) -> Self {
+        Self::default().with_default(app_data_dir)
+    }
+
+    /// Reads the registry; the default vault (the app data dir) is always
+    /// listed, so installs from before vaults need no migration.
+    pub fn load(config_dir: &Path, app_data_dir: &Path) -> AppResult<Self> {
+        let registry: Self = match fs::read_to_string(Self::path(config_dir)) {
+            Ok(contents) => toml::from_str(&contents).map_err(AppError::internal("Invalid vaults.toml"))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Self::default(),
+            Err(e) => return Err(AppError::io("Failed to read vaults.toml")(e)),
+        };
+        Ok(registry.with_default(app_data_dir))
+    }
+
+    fn with_default(mut self, app_data_dir: &Path) -> Self {
+        if !self.vaults.iter().any(|v| v.id == DEFAULT_VAULT_ID) {
+            self.vaults.insert(0, Vault {
+                id: DEFAULT_VAULT_ID.to_string(),
+                name: "Default".to_string(),
+                data_dir: app_data_dir.to_path_buf(),
+            });
+        }
+        self
+    }
+
+    pub fn save(&self, config_dir: &Path) -> AppResult<()> {
+        let contents = toml::to_string_pretty(self).map_err(AppError::internal("Failed to encode vaults"))?;
+        fs::create_dir_all(config_dir).map_err(AppError::io("Failed to create config dir"))?;
+        fs::write(Self::path(config_dir), contents).map_err(AppError::io("Failed to save vaults.toml"))
+    }
+
+    pub fn get(&self, id: &str) -> AppResult<&Vault> {
+        self.vaults
+            .iter()
+            .find(|v| v.id == id)
+            .ok_or_else(|| AppError::NotFound(format!("Vault {}", id)))
+    }
+
+    /// A vault by id or by name (any case).
+    pub fn find(&self, id_or_name: &str) -> AppResult<&Vault> {
+        let wanted = id_or_name.trim();
+        self.vaults
+            .iter()
+            .find(|v| v.id == wanted)
+            .or_else(|| self.vaults.iter().find(|v| v.name.eq_ignore_ascii_case(wanted)))
+            .ok_or_else(|| AppError::NotFound(format!("Vault {}", wanted)))
+    }
+
+    /// The vault opened last, or the default one.
+    pub fn last_opened(&self) -> &Vault {
+        self.last_opened
+            .as_deref()
+            .and_then(|id| self.get(id).ok())
+            .unwrap_or_else(|| self.get(DEFAULT_VAULT_ID).expect("default vault is always listed"))
+    }
+
+    /// Adds a vault with its data under `<app data>/vaults/<id>`.
+    pub fn create(&mut self, name: &str, app_data_dir: &Path) -> AppResult<Vault> {
+        let name = self.check_name(name, None)?;
+        let id = uuid::Uuid::new_v4().to_string();
+        let vault = Vault {
+            data_dir: app_data_dir.join("vaults").join(&id),
+            id,
+            name,
+        };
+        self.vaults.push(vault.clone());
+        Ok(vault)
+    }
+
+    pub fn rename(&mut self, id: &str, name: &str) -> AppResult<Vault> {
+        let name = self.check_name(name, Some(id))?;
+        let vault = self
+            .vaults
+            .iter_mut()
+            .find(|v| v.id == id)
+            .ok_or_else(|| AppError::NotFound(format!("Vault {}", id)))?;
+        vault.name = name;
+        Ok(vault.clone())
+    }
+
+    /// Removes a vault from the list; its files stay on disk.
+    pub fn close(&mut self, id: &str) -> AppResult<Vault> {
+        if id == DEFAULT_VAULT_ID {
+            return Err(AppError::Validation("The default vault can't be closed".to_string()));
+        }
+        let index = self
+            .vaults
+            .iter()
+            .position(|v| v.id == id)
+            .ok_or_else(|| AppError::NotFound(format!("Vault {}", id)))?;
+        if self.last_opened.as_deref() == Some(id) {
+            self.last_opened = None;
+        }
+        Ok(self.vaults.remove(index))
+    }
+
+    /// Trimmed `name`, unless it's empty or another vault has it.
+    fn check_name(&self, name: &str, renaming: Option<&str>) -> AppResult<String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(AppError::Validation("Vault name can't be empty".to_string()));
+        }
+        let taken = self
+            .vaults
+            .iter()
+            .any(|v| Some(v.id.as_str()) != renaming && v.name.eq_ignore_ascii_case(name));
+        if taken {
+            return Err(AppError::Validation(format!("A vault named \"{}\" already exists", name)));
+        }
+        Ok(name.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry() -> VaultRegistry {
+        VaultRegistry::new(Path::new("/data"))
+    }
+
+    #[test]
+    fn always_lists_the_default_vault() {
+        let registry = registry();
+        assert_eq!(registry.last_opened().id, DEFAULT_VAULT_ID);
+        assert_eq!(registry.last_opened().data_dir, Path::new("/data"));
+    }
+
+    #[test]
+    fn names_are_unique_and_non_empty() {
+        let mut registry = registry();
+        let work = registry.create(" Work ", Path::new("/data")).unwrap();
+        assert_eq!(work.name, "Work");
+        assert_eq!(work.data_dir, Path::new("/data/vaults").join(&work.id));
+
+        assert!(matches!(registry.create("work", Path::new("/data")), Err(AppError::Validation(_))));
+        assert!(matches!(registry.create("  ", Path::new("/data")), Err(AppError::Validation(_))));
+        assert!(matches!(registry.rename(&work.id, "default"), Err(AppError::Validation(_))));
+        assert_eq!(registry.rename(&work.id, "WORK").unwrap().name, "WORK");
+    }
+
+    #[test]
+    fn finds_vaults_by_id_or_name() {
+        let mut registry = registry();
+        let work = registry.create("Work", Path::new("/data")).unwrap();
+        assert_eq!(registry.find(&work.id).unwrap().id, work.id);
+        assert_eq!(registry.find(" work ").unwrap().id, work.id);
+        assert_eq!(registry.find("Default").unwrap().id, DEFAULT_VAULT_ID);
+        assert!(matches!(registry.find("home"), Err(AppError::NotFound(_))));
+    }
+
+    #[test]
+    fn closing_forgets_the_vault() {
+        let mut registry = registry();
+        let work = registry.create("Work", Path::new("/data")).unwrap();
+        registry.last_opened = Some(work.id.clone());
+
+        registry.close(&work.id).unwrap();
+        assert_eq!(registry.last_opened().id, DEFAULT_VAULT_ID);
+        assert!(matches!(registry.close(DEFAULT_VAULT_ID), Err(AppError::Validation(_))));
+    }
+}
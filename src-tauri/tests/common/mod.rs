@@ -0,0 +1,51 @@
+// src-tauri/tests/common/mod.rs
+//! Temporary data dir + DB + notes folder shared by the integration tests.
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use tokio::sync::Mutex;
+
+use app_lib::db;
+use app_lib::services::{DbStorage, ImportService, NoteService};
+use app_lib::settings::Settings;
+
+/// A fresh app data dir; deleted on drop.
+pub struct Fixture {
+    pub dir: PathBuf,
+    pub storage: DbStorage,
+    pub settings: Arc<Mutex<Settings>>,
+}
+
+impl Fixture {
+    pub async fn new() -> Self {
+        let dir = std::env::temp_dir().join(format!("rusty-notes-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let pool = db::init_database(&dir).await.unwrap();
+        let settings = Settings {
+            notes_folder: dir.join("notes").to_string_lossy().to_string(),
+            ..Settings::default()
+        };
+        Self {
+            storage: DbStorage::new(pool, dir.clone()),
+            settings: Arc::new(Mutex::new(settings)),
+            dir,
+        }
+    }
+
+    pub fn import_service(&self) -> ImportService {
+        ImportService::new(self.storage.clone(), self.settings.clone())
+    }
+
+    pub fn note_service(&self) -> NoteService {
+        NoteService::new(self.storage.clone(), self.settings.clone())
+    }
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
@@ -0,0 +1,88 @@
+// src-tauri/tests/daily_notes.rs
+//! One note per date: created on first open, found again after that, with
+//! the closest earlier and later daily notes to navigate to.
+
+mod common;
+
+use std::fs;
+use std::path::Path;
+
+use app_lib::error::AppError;
+use app_lib::services::DailyNotes;
+use common::Fixture;
+
+#[tokio::test]
+async fn opens_the_same_note_for_a_date() {
+    let fx = Fixture::new().await;
+    let daily = DailyNotes::new(fx.storage.clone(), fx.settings.clone());
+
+    let first = daily.open(Some("2026-03-04")).await.unwrap();
+    assert_eq!((first.note.note.title.as_str(), first.date.as_str()), ("2026-03-04", "2026-03-04"));
+    assert_eq!(first.note.note.notebook.as_deref(), Some("Daily"));
+    assert_eq!((first.previous, first.next), (None, None));
+
+    // Renamed, or under another title format: still the same note
+    fx.note_service()
+        .update(first.note.note.id.clone(), "Wednesday".to_string(), "<p>x</p>".to_string(), None)
+        .await
+        .unwrap();
+    fx.settings.lock().await.daily_notes.title_format = "%d.%m.%Y".to_string();
+    let again = daily.open(Some("2026-03-04")).await.unwrap();
+    assert_eq!((again.note.note.id.as_str(), again.note.note.title.as_str()), (first.note.note.id.as_str(), "Wednesday"));
+    assert_eq!(fx.storage.list_notes(&Default::default()).await.unwrap().len(), 1);
+
+    // Concurrent opens of a new day make one note
+    let (a, b) = tokio::join!(daily.open(Some("2026-03-05")), daily.open(Some("2026-03-05")));
+    assert_eq!(a.unwrap().note.note.id, b.unwrap().note.note.id);
+    assert_eq!(fx.storage.list_notes(&Default::default()).await.unwrap().len(), 2);
+
+    assert!(matches!(daily.open(Some("March 4")).await, Err(AppError::Validation(_))));
+    assert_eq!(daily.open(None).await.unwrap().note.note.notebook.as_deref(), Some("Daily"));
+}
+
+#[tokio::test]
+async fn navigates_between_existing_days() {
+    let fx = Fixture::new().await;
+    let daily = DailyNotes::new(fx.storage.clone(), fx.settings.clone());
+    daily.open(Some("2026-03-01")).await.unwrap();
+    let middle = daily.open(Some("2026-03-10")).await.unwrap();
+    daily.open(Some("2026-04-02")).await.unwrap();
+
+    let opened = daily.open(Some("2026-03-10")).await.unwrap();
+    assert_eq!((opened.previous.as_deref(), opened.next.as_deref()), (Some("2026-03-01"), Some("2026-04-02")));
+
+    // A trashed day is skipped, and opening it again starts a new note
+    fx.note_service().trash(middle.note.note.id.clone()).await.unwrap();
+    let first = daily.open(Some("2026-03-01")).await.unwrap();
+    assert_eq!(first.next.as_deref(), Some("2026-04-02"));
+    let reopened = daily.open(Some("2026-03-10")).await.unwrap();
+    assert_ne!(reopened.note.note.id, middle.note.note.id);
+}
+
+#[tokio::test]
+async fn starts_from_the_configured_template() {
+    let fx = Fixture::new().await;
+    let folder = Path::new(&fx.settings.lock().await.notes_folder).join("templates");
+    fs::create_dir_all(&folder).unwrap();
+    fs::write(folder.join("day.html"), "<!--\ntags: journal\nnotebook: Journal\n-->\n<h1>{{title}}</h1><p>{{date}}</p>").unwrap();
+    {
+        let mut settings = fx.settings.lock().await;
+        settings.daily_notes.template = "day".to_string();
+        settings.daily_notes.title_format = "%A, %B %-d".to_string();
+    }
+    let daily = DailyNotes::new(fx.storage.clone(), fx.settings.clone());
+
+    let note = daily.open(Some("2026-03-04")).await.unwrap().note;
+    assert_eq!(note.note.title, "Wednesday, March 4");
+    assert_eq!(note.note.body, "<h1>Wednesday, March 4</h1><p>2026-03-04</p>");
+    assert_eq!(note.note.notebook.as_deref(), Some("Daily"));
+    assert_eq!(fx.storage.list_note_tags(&note.note.id).await.unwrap(), ["journal"]);
+
+    // Without a notebook of its own, the template's is used
+    fx.settings.lock().await.daily_notes.notebook = String::new();
+    let note = daily.open(Some("2026-03-05")).await.unwrap().note;
+    assert_eq!(note.note.notebook.as_deref(), Some("Journal"));
+
+    fx.settings.lock().await.daily_notes.template = "missing".to_string();
+    assert!(matches!(daily.open(Some("2026-03-06")).await, Err(AppError::NotFound(_))));
+}
@@ -0,0 +1,98 @@
+// src-tauri/tests/encryption.rs
+//! Encryption at rest: turning it on seals files and DB columns, locking
+//! refuses access, and a passphrase change rewrites no note.
+
+mod common;
+
+use std::fs;
+
+use sqlx::Row;
+
+use app_lib::crypto::{is_sealed, KeyFile};
+use app_lib::db;
+use app_lib::error::AppError;
+use app_lib::services::VaultEncryption;
+use common::Fixture;
+
+const PASSPHRASE: &str = "correct horse";
+
+fn encryption(fx: &Fixture) -> VaultEncryption {
+    VaultEncryption::new(fx.storage.clone(), fx.settings.clone(), &fx.dir)
+}
+
+async fn stored_columns(fx: &Fixture, id: &str) -> (String, String) {
+    let mut conn = db::open_connection(&fx.dir).await.unwrap();
+    let row = sqlx::query("SELECT title, body FROM notes WHERE id = ?")
+        .bind(id)
+        .fetch_one(&mut conn)
+        .await
+        .unwrap();
+    (row.get("title"), row.get("body"))
+}
+
+#[tokio::test]
+async fn enabling_seals_files_and_columns_but_notes_still_read() {
+    let fx = Fixture::new().await;
+    let notes = fx.note_service();
+    let note = notes.create("Acme call".to_string(), "<p>budget 40k</p>".to_string()).await.unwrap();
+    let attachment = notes
+        .add_attachment(note.id.clone(), "q3.txt".to_string(), None, b"numbers".to_vec())
+        .await
+        .unwrap();
+
+    let report = encryption(&fx).enable(PASSPHRASE).await.unwrap();
+    assert_eq!((report.notes, report.files), (1, 2));
+    assert!(KeyFile::exists(&fx.dir));
+
+    assert!(is_sealed(&fs::read(&note.file_path).unwrap()));
+    assert!(is_sealed(&fs::read(&attachment.file_path).unwrap()));
+    let (title, body) = stored_columns(&fx, &note.id).await;
+    assert!(!title.contains("Acme") && !body.contains("budget"));
+
+    let read = notes.get(note.id.clone()).await.unwrap();
+    assert_eq!((read.note.title.as_str(), read.note.body.as_str()), ("Acme call", "<p>budget 40k</p>"));
+    assert_eq!(notes.search("BUDGET".to_string()).await.unwrap().len(), 1);
+
+    // New writes are sealed too; enabling again seals nothing twice
+    let later = notes.create("Later".to_string(), "<p>new</p>".to_string()).await.unwrap();
+    assert!(is_sealed(&fs::read(&later.file_path).unwrap()));
+    let again = encryption(&fx).enable(PASSPHRASE).await.unwrap();
+    assert_eq!((again.notes, again.files), (0, 0));
+}
+
+#[tokio::test]
+async fn locked_vault_refuses_until_unlocked() {
+    let fx = Fixture::new().await;
+    let notes = fx.note_service();
+    let note = notes.create("Secret".to_string(), "<p>x</p>".to_string()).await.unwrap();
+    let encryption = encryption(&fx);
+    encryption.enable(PASSPHRASE).await.unwrap();
+
+    encryption.lock();
+    assert!(encryption.status().locked);
+    assert!(matches!(notes.get(note.id.clone()).await, Err(AppError::Locked)));
+    assert!(matches!(notes.create("New".to_string(), String::new()).await, Err(AppError::Locked)));
+
+    assert!(matches!(encryption.unlock("wrong passphrase"), Err(AppError::Validation(_))));
+    encryption.unlock(PASSPHRASE).unwrap();
+    assert_eq!(notes.get(note.id).await.unwrap().note.title, "Secret");
+}
+
+#[tokio::test]
+async fn passphrase_change_rewrites_no_note() {
+    let fx = Fixture::new().await;
+    let notes = fx.note_service();
+    let note = notes.create("Plan".to_string(), "<p>x</p>".to_string()).await.unwrap();
+    let encryption = encryption(&fx);
+    encryption.enable(PASSPHRASE).await.unwrap();
+    let sealed = fs::read(&note.file_path).unwrap();
+
+    assert!(encryption.change_passphrase(PASSPHRASE, "short").is_err());
+    encryption.change_passphrase(PASSPHRASE, "battery staple").unwrap();
+    assert_eq!(fs::read(&note.file_path).unwrap(), sealed);
+
+    encryption.lock();
+    assert!(encryption.unlock(PASSPHRASE).is_err());
+    encryption.unlock("battery staple").unwrap();
+    assert_eq!(notes.get(note.id).await.unwrap().note.title, "Plan");
+}
@@ -0,0 +1,76 @@
+// src-tauri/tests/events.rs
+//! Storage mutations publish versioned events; other processes' writes
+//! surface as `ExternalChange`, our own never do.
+
+mod common;
+
+use std::time::Duration;
+
+use tokio::sync::broadcast::Receiver;
+use tokio::time::timeout;
+
+use app_lib::db;
+use app_lib::services::events::{watch_external_changes, EventEnvelope};
+use app_lib::services::AppEvent;
+use common::Fixture;
+
+async fn next(rx: &mut Receiver<EventEnvelope>) -> AppEvent {
+    timeout(Duration::from_secs(2), rx.recv()).await.expect("no event").unwrap().event
+}
+
+#[tokio::test]
+async fn every_note_mutation_publishes_the_new_version() {
+    let fx = Fixture::new().await;
+    let mut rx = fx.storage.events().subscribe();
+    let notes = fx.note_service();
+
+    let note = notes.create("A".to_string(), "<p>a</p>".to_string()).await.unwrap();
+    assert_eq!(note.version, 1);
+    assert_eq!(next(&mut rx).await, AppEvent::NoteCreated { id: note.id.clone(), version: 1 });
+
+    let updated = notes.update(note.id.clone(), "B".to_string(), "<p>b</p>".to_string(), Some(1)).await.unwrap();
+    assert_eq!(updated.version, 2);
+    assert_eq!(next(&mut rx).await, AppEvent::NoteUpdated { id: note.id.clone(), version: 2 });
+
+    fx.storage.set_note_tags(&note.id, &["x".to_string()]).await.unwrap();
+    assert_eq!(next(&mut rx).await, AppEvent::NoteUpdated { id: note.id.clone(), version: 3 });
+
+    let attachment = notes
+        .add_attachment(note.id.clone(), "a.txt".to_string(), None, b"a".to_vec())
+        .await
+        .unwrap();
+    assert_eq!(
+        next(&mut rx).await,
+        AppEvent::AttachmentAdded { note_id: note.id.clone(), attachment_id: attachment.id, version: 4 }
+    );
+
+    fx.storage.set_note_archived(&note.id, true).await.unwrap();
+    assert_eq!(next(&mut rx).await, AppEvent::NoteUpdated { id: note.id.clone(), version: 5 });
+
+    fx.storage.delete_note(&note.id).await.unwrap();
+    assert_eq!(next(&mut rx).await, AppEvent::NoteDeleted { id: note.id.clone(), version: 6, permanent: true });
+}
+
+#[tokio::test]
+async fn only_other_connections_trigger_external_change() {
+    let fx = Fixture::new().await;
+    let bus = fx.storage.events().clone();
+    let mut rx = bus.subscribe();
+
+    let conn = db::open_connection(&fx.dir).await.unwrap();
+    let watcher = tokio::spawn(watch_external_changes(conn, bus, Duration::from_millis(10)));
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    // Local writes: only their own events, no ExternalChange
+    let note = fx.note_service().create("A".to_string(), String::new()).await.unwrap();
+    fx.storage.record_import("test", "1", &note.id, 0).await.unwrap();
+    assert!(matches!(next(&mut rx).await, AppEvent::NoteCreated { .. }));
+    assert!(timeout(Duration::from_millis(200), rx.recv()).await.is_err());
+
+    // Another process (separate connection) writing
+    let mut other = db::open_connection(&fx.dir).await.unwrap();
+    sqlx::query("UPDATE notes SET title = 'from cli'").execute(&mut other).await.unwrap();
+    assert_eq!(next(&mut rx).await, AppEvent::ExternalChange);
+
+    watcher.abort();
+}
@@ -0,0 +1,69 @@
+// src-tauri/tests/export.rs
+//! JSON export → import into another data dir must be lossless.
+
+mod common;
+
+use std::fs;
+
+use app_lib::services::export::{ExportFormat, NoteExport};
+use app_lib::services::import::ImportFormat;
+use app_lib::services::note_files::reference_forms;
+use app_lib::services::ExportService;
+use common::Fixture;
+
+#[tokio::test]
+async fn json_round_trip_is_lossless() {
+    let source = Fixture::new().await;
+    let notes = source.note_service();
+
+    let note = notes.create("Trip".to_string(), "<p>draft</p>".to_string()).await.unwrap();
+    let attachment = notes
+        .add_attachment(note.id.clone(), "map 1.png".to_string(), Some("image/png".to_string()), b"png".to_vec())
+        .await
+        .unwrap();
+    let asset_url = reference_forms(&attachment.file_path)[0].clone();
+    let body = format!("<p>See</p><img src=\"{}\">", asset_url);
+    notes.update(note.id.clone(), "Trip".to_string(), body, None).await.unwrap();
+    source.storage.set_note_tags(&note.id, &["travel".to_string()]).await.unwrap();
+    source.storage.set_note_archived(&note.id, true).await.unwrap();
+
+    let exported = ExportService::new(source.storage.clone())
+        .export_note(&note.id, ExportFormat::Json)
+        .await
+        .unwrap();
+    let original: NoteExport = serde_json::from_str(&exported.contents).unwrap();
+    assert!(original.note.note.archived);
+
+    let target = Fixture::new().await;
+    let file = target.dir.join(&exported.file_name);
+    fs::write(&file, &exported.contents).unwrap();
+    let report = target.import_service().run(ImportFormat::Json.importer(), file, |_| {}).await.unwrap();
+    assert_eq!((report.imported, report.errors.len()), (1, 0));
+
+    let reexported = ExportService::new(target.storage.clone())
+        .export_note(&note.id, ExportFormat::Json)
+        .await
+        .unwrap();
+    let copy: NoteExport = serde_json::from_str(&reexported.contents).unwrap();
+
+    assert_eq!(copy.note.note.id, original.note.note.id);
+    assert_eq!(copy.note.note.title, original.note.note.title);
+    assert_eq!(copy.note.note.created_at, original.note.note.created_at);
+    assert_eq!(copy.note.note.updated_at, original.note.note.updated_at);
+    assert_eq!(copy.tags, original.tags);
+    assert!(copy.note.note.archived);
+    assert_eq!(copy.attachment_contents, original.attachment_contents);
+
+    let (old, new) = (&original.note.attachments[0], &copy.note.attachments[0]);
+    assert_eq!(new.id, old.id);
+    assert_eq!(new.created_at, old.created_at);
+    assert_eq!(new.file_name, old.file_name);
+    assert_ne!(new.file_path, old.file_path);
+
+    // Same reference form, now pointing at the copy's file
+    assert_eq!(
+        copy.note.note.body,
+        format!("<p>See</p><img src=\"{}\">", reference_forms(&new.file_path)[0])
+    );
+    assert_eq!(fs::read(&new.file_path).unwrap(), b"png");
+}
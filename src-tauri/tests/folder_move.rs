@@ -0,0 +1,91 @@
+// src-tauri/tests/folder_move.rs
+//! Moving the notes folder: files, DB paths and settings move together,
+//! and an interrupted move can be resumed.
+
+mod common;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use app_lib::error::AppError;
+use app_lib::services::FolderMove;
+use app_lib::settings::SettingsFiles;
+use common::Fixture;
+
+fn mover(fx: &Fixture) -> FolderMove {
+    let settings_files = SettingsFiles { app: fx.dir.join("settings.toml"), vault: None };
+    FolderMove::new(fx.storage.clone(), fx.settings.clone(), settings_files, &fx.dir)
+}
+
+#[tokio::test]
+async fn moves_files_paths_and_setting() {
+    let fx = Fixture::new().await;
+    let notes = fx.note_service();
+    let note = notes.create("A".to_string(), "<p>a</p>".to_string()).await.unwrap();
+    let attachment = notes
+        .add_attachment(note.id.clone(), "map.png".to_string(), Some("image/png".to_string()), b"png".to_vec())
+        .await
+        .unwrap();
+    let body = format!("<p>a</p><img src=\"{}\">", attachment.file_path);
+    notes.update(note.id.clone(), "A".to_string(), body, None).await.unwrap();
+    let old_folder = fx.dir.join("notes");
+    fs::create_dir_all(old_folder.join("trash")).unwrap();
+    fs::write(old_folder.join("trash").join("old.html"), "<p>old</p>").unwrap();
+
+    let to = fx.dir.join("moved");
+    let report = mover(&fx).run(to.clone()).await.unwrap();
+    assert_eq!((report.notes, report.attachments), (1, 1));
+
+    let moved = fx.storage.get_note(&note.id).await.unwrap();
+    let new_attachment = moved.attachments[0].file_path.clone();
+    assert!(Path::new(&moved.note.file_path).starts_with(&to));
+    assert!(Path::new(&new_attachment).starts_with(&to));
+    assert_eq!(moved.note.body, format!("<p>a</p><img src=\"{}\">", new_attachment));
+    assert_eq!(fs::read_to_string(&moved.note.file_path).unwrap(), moved.note.body);
+    assert_eq!(fs::read(&new_attachment).unwrap(), b"png");
+    assert_eq!(fs::read_to_string(to.join("trash").join("old.html")).unwrap(), "<p>old</p>");
+
+    assert_eq!(fx.settings.lock().await.notes_folder, to.to_string_lossy());
+    let saved = fs::read_to_string(fx.dir.join("settings.toml")).unwrap();
+    assert!(saved.contains(to.to_string_lossy().as_ref()));
+    assert!(!old_folder.exists(), "originals left behind");
+    assert!(!fx.dir.join("folder-move.json").exists());
+}
+
+#[tokio::test]
+async fn refuses_a_folder_that_is_not_empty() {
+    let fx = Fixture::new().await;
+    fx.note_service().create("A".to_string(), "<p>a</p>".to_string()).await.unwrap();
+    let to = fx.dir.join("taken");
+    fs::create_dir_all(&to).unwrap();
+    fs::write(to.join("other.txt"), "x").unwrap();
+
+    let result = mover(&fx).run(to).await;
+    assert!(matches!(result, Err(AppError::Validation(_))));
+    assert!(fx.dir.join("notes").exists());
+}
+
+#[tokio::test]
+async fn resumes_an_interrupted_copy() {
+    let fx = Fixture::new().await;
+    let note = fx.note_service().create("A".to_string(), "<p>all of it</p>".to_string()).await.unwrap();
+    let to = fx.dir.join("moved");
+
+    // A previous run died midway through copying this file
+    let rest = Path::new(&note.file_path).strip_prefix(fx.dir.join("notes")).unwrap();
+    let partial: PathBuf = to.join(rest);
+    fs::create_dir_all(partial.parent().unwrap()).unwrap();
+    fs::write(&partial, "<p>al").unwrap();
+    let journal = serde_json::json!({ "from": fx.dir.join("notes"), "to": to, "stage": "copying" });
+    fs::write(fx.dir.join("folder-move.json"), journal.to_string()).unwrap();
+
+    // Another target can't start until this move is done
+    let other = mover(&fx).run(fx.dir.join("elsewhere")).await;
+    assert!(matches!(other, Err(AppError::Validation(_))));
+
+    mover(&fx).run(to.clone()).await.unwrap();
+    let moved = fx.storage.get_note(&note.id).await.unwrap().note;
+    assert_eq!(moved.file_path, partial.to_string_lossy());
+    assert_eq!(fs::read_to_string(&partial).unwrap(), "<p>all of it</p>");
+    assert!(!Path::new(&note.file_path).exists());
+}
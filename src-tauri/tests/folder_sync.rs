@@ -0,0 +1,99 @@
+// src-tauri/tests/folder_sync.rs
+//! External edits in the notes folder are synced into the DB.
+
+mod common;
+
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use app_lib::services::folder_sync::{self, SyncAction};
+use app_lib::services::{AppEvent, FolderSync, TrashManager};
+use app_lib::settings::model::DeleteBehavior;
+use common::Fixture;
+
+fn folder_sync(fx: &Fixture) -> FolderSync {
+    FolderSync::new(fx.storage.clone(), fx.settings.clone())
+}
+
+#[tokio::test]
+async fn external_edit_updates_the_note_but_own_writes_do_not() {
+    let fx = Fixture::new().await;
+    let note = fx.note_service().create("A".to_string(), "<p>a</p>".to_string()).await.unwrap();
+    let sync = folder_sync(&fx);
+    let path = Path::new(&note.file_path);
+
+    assert_eq!(sync.sync_path(path).await.unwrap(), SyncAction::Unchanged);
+
+    fs::write(path, "<p>edited elsewhere</p>").unwrap();
+    assert_eq!(sync.sync_path(path).await.unwrap(), SyncAction::Updated(note.id.clone()));
+
+    let stored = fx.storage.get_note(&note.id).await.unwrap().note;
+    assert_eq!((stored.title.as_str(), stored.body.as_str()), ("A", "<p>edited elsewhere</p>"));
+    assert_eq!(stored.version, 2);
+}
+
+#[tokio::test]
+async fn new_files_become_notes_and_other_files_are_ignored() {
+    let fx = Fixture::new().await;
+    let folder = fx.dir.join("notes");
+    fs::create_dir_all(folder.join("trash")).unwrap();
+    fs::write(folder.join("Groceries.html"), "<p>milk</p>").unwrap();
+    fs::write(folder.join("readme.txt"), "not a note").unwrap();
+    fs::write(folder.join("trash").join("old.html"), "<p>old</p>").unwrap();
+
+    let actions = folder_sync(&fx).scan().await.unwrap();
+    assert_eq!(actions, vec![SyncAction::Created("Groceries".to_string())]);
+
+    let note = fx.storage.get_note("Groceries").await.unwrap().note;
+    assert_eq!((note.title.as_str(), note.body.as_str()), ("Groceries", "<p>milk</p>"));
+}
+
+#[tokio::test]
+async fn removed_file_goes_to_trash_with_its_last_body() {
+    let fx = Fixture::new().await;
+    let notes = fx.note_service();
+    let note = notes.create("A".to_string(), "<p>keep me</p>".to_string()).await.unwrap();
+    notes.add_attachment(note.id.clone(), "a.txt".to_string(), None, b"a".to_vec()).await.unwrap();
+
+    fs::remove_file(&note.file_path).unwrap();
+    let sync = folder_sync(&fx);
+    assert_eq!(sync.sync_path(Path::new(&note.file_path)).await.unwrap(), SyncAction::Deleted(note.id.clone()));
+
+    assert!(fx.storage.find_note(&note.id).await.unwrap().is_none());
+    let trash = TrashManager::folder_for_today(&fx.settings.lock().await.notes_folder);
+    assert_eq!(fs::read_to_string(trash.join(format!("{}.html", note.id))).unwrap(), "<p>keep me</p>");
+    assert!(trash.join("a.txt").exists());
+}
+
+#[tokio::test]
+async fn removed_file_with_permanent_delete_removes_attachments() {
+    let fx = Fixture::new().await;
+    fx.settings.lock().await.delete_behavior = DeleteBehavior::Permanent;
+    let notes = fx.note_service();
+    let note = notes.create("A".to_string(), String::new()).await.unwrap();
+    let attachment = notes.add_attachment(note.id.clone(), "a.txt".to_string(), None, b"a".to_vec()).await.unwrap();
+
+    fs::remove_file(&note.file_path).unwrap();
+    let action = folder_sync(&fx).sync_path(Path::new(&note.file_path)).await.unwrap();
+
+    assert_eq!(action, SyncAction::Deleted(note.id.clone()));
+    assert!(fx.storage.find_note(&note.id).await.unwrap().is_none());
+    assert!(!Path::new(&attachment.file_path).exists());
+    assert!(!fx.dir.join("notes").join("trash").exists());
+}
+
+#[tokio::test]
+async fn watcher_publishes_external_edits() {
+    let fx = Fixture::new().await;
+    let note = fx.note_service().create("A".to_string(), "<p>a</p>".to_string()).await.unwrap();
+    let bus = fx.storage.events().clone();
+    let mut rx = bus.subscribe();
+
+    let folder = fx.dir.join("notes");
+    let _watcher = folder_sync::watch(folder_sync(&fx), &folder, bus, Duration::from_millis(50)).unwrap();
+    fs::write(&note.file_path, "<p>b</p>").unwrap();
+
+    let event = tokio::time::timeout(Duration::from_secs(5), rx.recv()).await.expect("no event").unwrap();
+    assert_eq!(event.event, AppEvent::NoteUpdated { id: note.id.clone(), version: 2 });
+}
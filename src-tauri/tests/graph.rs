@@ -0,0 +1,67 @@
+// src-tauri/tests/graph.rs
+//! The note graph built from the DB: nodes with tags and notebook, link
+//! edges, filters and file exports.
+
+mod common;
+
+use app_lib::services::graph::{GraphFilter, GraphFormat};
+use app_lib::services::NoteGraph;
+use common::Fixture;
+
+#[tokio::test]
+async fn graph_has_notes_tags_and_links() {
+    let fx = Fixture::new().await;
+    let notes = fx.note_service();
+    let plan = notes.create_in("Plan".to_string(), "<p>[[Budget]]</p>".to_string(), Some("Work".to_string())).await.unwrap();
+    let budget = notes.create("Budget".to_string(), "<p>[[Plan]] [[Missing]]</p>".to_string()).await.unwrap();
+    let trip = notes.create("Trip".to_string(), "<p>[[Budget]]</p>".to_string()).await.unwrap();
+    let old = notes.create("Old".to_string(), "<p>[[Plan]]</p>".to_string()).await.unwrap();
+    fx.storage.set_note_tags(&plan.id, &["q3".to_string(), "money".to_string()]).await.unwrap();
+    fx.storage.set_note_archived(&old.id, true).await.unwrap();
+
+    let graph = NoteGraph::new(fx.storage.clone()).build(&GraphFilter::default()).await.unwrap();
+    let mut titles: Vec<_> = graph.nodes.iter().map(|node| node.title.as_str()).collect();
+    titles.sort();
+    assert_eq!(titles, ["Budget", "Plan", "Trip"]);
+    let plan_node = graph.nodes.iter().find(|node| node.id == plan.id).unwrap();
+    assert_eq!((plan_node.tags.clone(), plan_node.notebook.as_deref()), (vec!["money".to_string(), "q3".to_string()], Some("Work")));
+
+    // Broken links and links from archived notes aren't edges
+    let mut edges: Vec<_> = graph.edges.iter().map(|edge| (edge.source.clone(), edge.target.clone())).collect();
+    edges.sort();
+    let mut expected = vec![
+        (plan.id.clone(), budget.id.clone()),
+        (budget.id.clone(), plan.id.clone()),
+        (trip.id.clone(), budget.id.clone()),
+    ];
+    expected.sort();
+    assert_eq!(edges, expected);
+
+    let filter = GraphFilter { around: Some(plan.id.clone()), ..GraphFilter::default() };
+    let nearby = NoteGraph::new(fx.storage.clone()).build(&filter).await.unwrap();
+    assert_eq!(nearby.nodes.len(), 2);
+    let filter = GraphFilter { tag: Some("q3".to_string()), ..GraphFilter::default() };
+    let tagged = NoteGraph::new(fx.storage.clone()).build(&filter).await.unwrap();
+    assert_eq!((tagged.nodes.len(), tagged.edges.len()), (1, 0));
+}
+
+#[tokio::test]
+async fn exports_each_format() {
+    let fx = Fixture::new().await;
+    let notes = fx.note_service();
+    let a = notes.create("A".to_string(), "<p>[[B]]</p>".to_string()).await.unwrap();
+    let b = notes.create("B".to_string(), "<p></p>".to_string()).await.unwrap();
+    let graph = NoteGraph::new(fx.storage.clone());
+
+    let dot = graph.export(&GraphFilter::default(), GraphFormat::Dot).await.unwrap();
+    assert_eq!(dot.file_name, "Notes graph.dot");
+    assert!(dot.contents.contains(&format!("\"{}\" -> \"{}\";", a.id, b.id)));
+
+    let graphml = graph.export(&GraphFilter::default(), GraphFormat::Graphml).await.unwrap();
+    assert!(graphml.contents.contains(&format!("<edge source=\"{}\" target=\"{}\"/>", a.id, b.id)));
+
+    let json = graph.export(&GraphFilter::default(), GraphFormat::Json).await.unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json.contents).unwrap();
+    assert_eq!(value["edges"][0]["source"], a.id.as_str());
+    assert_eq!(value["nodes"].as_array().unwrap().len(), 2);
+}
@@ -0,0 +1,75 @@
+// src-tauri/tests/import.rs
+//! Import pipeline against a real SQLite DB and notes folder.
+
+mod common;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use app_lib::services::import::ImportFormat;
+use app_lib::db::schema::NoteFilter;
+use common::Fixture;
+
+const NOTE_ID: &str = "0123456789abcdef0123456789abcdef";
+const RESOURCE_ID: &str = "22222222222222222222222222222222";
+
+fn joplin_export(dir: &Path) -> PathBuf {
+    let export = dir.join("joplin");
+    fs::create_dir_all(export.join("resources")).unwrap();
+    fs::write(
+        export.join(format!("{}.md", NOTE_ID)),
+        format!("Trip\n\nSee ![map](:/{})\n\nid: {}\ntype_: 1", RESOURCE_ID, NOTE_ID),
+    )
+    .unwrap();
+    fs::write(
+        export.join(format!("{}.md", RESOURCE_ID)),
+        format!("../../map.png\n\nid: {}\nfile_extension: png\ntype_: 4", RESOURCE_ID),
+    )
+    .unwrap();
+    fs::write(export.join("resources").join(format!("{}.png", RESOURCE_ID)), b"png").unwrap();
+    export
+}
+
+#[tokio::test]
+async fn joplin_resources_are_stored_and_linked() {
+    let fx = Fixture::new().await;
+    let export = joplin_export(&fx.dir);
+
+    let report = fx.import_service().run(ImportFormat::Joplin.importer(), export.clone(), |_| {}).await.unwrap();
+    assert_eq!((report.imported, report.errors.len()), (1, 0));
+
+    let storage = &fx.storage;
+    let note_id = storage.list_notes(&NoteFilter::default()).await.unwrap()[0].id.clone();
+    let note = storage.get_note(&note_id).await.unwrap();
+    let attachment = &note.attachments[0];
+
+    // The resource title tried to escape the attachments folder
+    assert_eq!(attachment.file_name, "map.png");
+    assert!(attachment.file_path.starts_with(&fx.dir.join("notes").join("attachments").join(&note_id).to_string_lossy().to_string()));
+    assert_eq!(fs::read(&attachment.file_path).unwrap(), b"png");
+
+    assert!(!note.note.body.contains(":/"));
+    assert!(note.note.body.contains(&format!("<img src=\"{}\"", attachment.file_path)));
+    assert_eq!(fs::read_to_string(&note.note.file_path).unwrap(), note.note.body);
+
+    let again = fx.import_service().run(ImportFormat::Joplin.importer(), export, |_| {}).await.unwrap();
+    assert_eq!((again.imported, again.skipped_duplicates), (0, 1));
+}
+
+#[tokio::test]
+async fn archived_notes_are_hidden_from_list_and_search() {
+    let fx = Fixture::new().await;
+    let export = fx.dir.join("keep");
+    fs::create_dir_all(&export).unwrap();
+    fs::write(export.join("a.json"), r#"{"title": "Old idea", "textContent": "x", "isArchived": true}"#).unwrap();
+    fs::write(export.join("b.json"), r#"{"title": "New idea", "textContent": "x"}"#).unwrap();
+
+    let report = fx.import_service().run(ImportFormat::Keep.importer(), export, |_| {}).await.unwrap();
+    assert_eq!(report.imported, 2);
+
+    let storage = &fx.storage;
+    let filter = NoteFilter::default();
+    let titles: Vec<String> = storage.list_notes(&filter).await.unwrap().into_iter().map(|n| n.title).collect();
+    assert_eq!(titles, vec!["New idea".to_string()]);
+    assert_eq!(storage.search_notes("idea", &filter).await.unwrap().len(), 1);
+}
@@ -0,0 +1,95 @@
+// src-tauri/tests/links.rs
+//! Links between notes: backlinks, outgoing and broken links, and renames
+//! carried into the notes linking to the renamed one.
+
+mod common;
+
+use std::fs;
+
+use app_lib::db::schema::Note;
+use app_lib::services::{NoteLocks, VaultEncryption};
+use common::Fixture;
+
+fn titles(notes: Vec<Note>) -> Vec<String> {
+    notes.into_iter().map(|note| note.title).collect()
+}
+
+#[tokio::test]
+async fn backlinks_and_outgoing_links() {
+    let fx = Fixture::new().await;
+    let notes = fx.note_service();
+    let plan = notes.create("Plan".to_string(), "<p>steps</p>".to_string()).await.unwrap();
+    let body = format!("<p>See [[plan]] and <a href=\"note://{}\">the plan</a>, [[Budget]]</p>", plan.id);
+    let trip = notes.create("Trip".to_string(), body).await.unwrap();
+    notes.create("Other".to_string(), "<p>no links</p>".to_string()).await.unwrap();
+
+    assert_eq!(titles(fx.storage.backlinks(&plan.id).await.unwrap()), ["Trip"]);
+    assert!(fx.storage.backlinks(&trip.id).await.unwrap().is_empty());
+
+    let outgoing = fx.storage.outgoing_links(&trip.id).await.unwrap();
+    let resolved: Vec<_> = outgoing.iter().map(|link| (link.target.as_str(), link.note_id.as_deref(), link.broken)).collect();
+    assert_eq!(
+        resolved,
+        [("plan", Some(plan.id.as_str()), false), (plan.id.as_str(), Some(plan.id.as_str()), false), ("Budget", None, true)]
+    );
+
+    // Edits re-index: the link is gone once the text is
+    notes.update(trip.id.clone(), "Trip".to_string(), "<p>done</p>".to_string(), None).await.unwrap();
+    assert!(fx.storage.backlinks(&plan.id).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn broken_links_follow_their_targets() {
+    let fx = Fixture::new().await;
+    let notes = fx.note_service();
+    let trip = notes.create("Trip".to_string(), "<p>[[Budget]]</p>".to_string()).await.unwrap();
+
+    let broken = fx.storage.broken_links().await.unwrap();
+    assert_eq!(broken.len(), 1);
+    assert_eq!((broken[0].source_id.as_str(), broken[0].target.as_str()), (trip.id.as_str(), "Budget"));
+
+    let budget = notes.create("Budget".to_string(), "<p>$</p>".to_string()).await.unwrap();
+    assert!(fx.storage.broken_links().await.unwrap().is_empty());
+    assert_eq!(titles(fx.storage.backlinks(&budget.id).await.unwrap()), ["Trip"]);
+
+    // A trashed target breaks the link again; a trashed source drops it
+    notes.trash(budget.id.clone()).await.unwrap();
+    assert_eq!(fx.storage.broken_links().await.unwrap().len(), 1);
+    notes.trash(trip.id.clone()).await.unwrap();
+    assert!(fx.storage.broken_links().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn renaming_rewrites_linking_notes() {
+    let fx = Fixture::new().await;
+    let notes = fx.note_service();
+    let plan = notes.create("Plan".to_string(), "<p>steps</p>".to_string()).await.unwrap();
+    let trip = notes.create("Trip".to_string(), "<p>[[Plan]], [[ plan ]] and [[Planning]]</p>".to_string()).await.unwrap();
+
+    notes.update(plan.id.clone(), "Q3 & Plan".to_string(), "<p>steps</p>".to_string(), None).await.unwrap();
+
+    let expected = "<p>[[Q3 &amp; Plan]], [[Q3 &amp; Plan]] and [[Planning]]</p>";
+    let rewritten = notes.get(trip.id.clone()).await.unwrap().note;
+    assert_eq!(rewritten.body, expected);
+    assert_eq!((rewritten.updated_at, rewritten.version), (trip.updated_at, trip.version + 1));
+    assert_eq!(fs::read_to_string(&trip.file_path).unwrap(), expected);
+    assert_eq!(titles(fx.storage.backlinks(&plan.id).await.unwrap()), ["Trip"]);
+}
+
+#[tokio::test]
+async fn protected_and_encrypted_notes() {
+    let fx = Fixture::new().await;
+    let notes = fx.note_service();
+    let plan = notes.create("Plan".to_string(), "<p>steps</p>".to_string()).await.unwrap();
+    let diary = notes.create("Diary".to_string(), "<p>[[Plan]]</p>".to_string()).await.unwrap();
+    notes.create("Trip".to_string(), "<p>[[Plan]]</p>".to_string()).await.unwrap();
+
+    // A protected note's links would give its text away
+    NoteLocks::new(fx.storage.clone()).protect(&diary.id, "my diary key").await.unwrap();
+    assert_eq!(titles(fx.storage.backlinks(&plan.id).await.unwrap()), ["Trip"]);
+
+    VaultEncryption::new(fx.storage.clone(), fx.settings.clone(), &fx.dir).enable("vault passphrase").await.unwrap();
+    assert_eq!(titles(fx.storage.backlinks(&plan.id).await.unwrap()), ["Trip"]);
+    notes.create("Notes".to_string(), "<p>[[Missing]]</p>".to_string()).await.unwrap();
+    assert_eq!(fx.storage.broken_links().await.unwrap()[0].target, "Missing");
+}
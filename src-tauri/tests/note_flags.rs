@@ -0,0 +1,87 @@
+// src-tauri/tests/note_flags.rs
+//! Pinned, favorite and archived notes: order and filters of list and
+//! search, on SQLite and in memory.
+
+mod common;
+
+use std::sync::Arc;
+
+use tokio::sync::Mutex;
+
+use app_lib::db::schema::{Note, NoteFilter};
+use app_lib::services::{InMemoryFileStore, InMemoryStorage, NoteService};
+use app_lib::settings::Settings;
+use common::Fixture;
+
+fn titles(notes: Vec<Note>) -> Vec<String> {
+    notes.into_iter().map(|note| note.title).collect()
+}
+
+#[tokio::test]
+async fn flags_order_and_filter_the_list() {
+    let fx = Fixture::new().await;
+    let notes = fx.note_service();
+    let pinned = notes.create("Pinned idea".to_string(), "<p>x</p>".to_string()).await.unwrap();
+    let favorite = notes.create("Favorite idea".to_string(), "<p>x</p>".to_string()).await.unwrap();
+    let archived = notes.create("Archived idea".to_string(), "<p>x</p>".to_string()).await.unwrap();
+    notes.create("Plain idea".to_string(), "<p>x</p>".to_string()).await.unwrap();
+
+    fx.storage.set_note_pinned(&pinned.id, true).await.unwrap();
+    fx.storage.set_note_favorite(&favorite.id, true).await.unwrap();
+    fx.storage.set_note_archived(&archived.id, true).await.unwrap();
+
+    let listed = notes.list().await.unwrap();
+    assert_eq!(listed[0].title, "Pinned idea");
+    assert!(listed[0].pinned && !listed[0].favorite);
+    assert_eq!(listed.len(), 3);
+
+    let favorites = NoteFilter { favorites: true, ..NoteFilter::default() };
+    assert_eq!(titles(notes.list_filtered(favorites.clone()).await.unwrap()), ["Favorite idea"]);
+    let archive = NoteFilter { archived: true, ..NoteFilter::default() };
+    assert_eq!(titles(notes.list_filtered(archive.clone()).await.unwrap()), ["Archived idea"]);
+    let pins = NoteFilter { pinned: true, ..NoteFilter::default() };
+    assert_eq!(titles(notes.list_filtered(pins).await.unwrap()), ["Pinned idea"]);
+
+    // Search applies the same filter
+    assert_eq!(notes.search("idea".to_string()).await.unwrap().len(), 3);
+    assert_eq!(titles(notes.search_filtered("idea".to_string(), archive).await.unwrap()), ["Archived idea"]);
+    assert!(notes.search_filtered("plain".to_string(), favorites).await.unwrap().is_empty());
+
+    // Archived isn't trashed: still readable, and back in the list once unarchived
+    assert!(notes.get(archived.id.clone()).await.unwrap().note.archived);
+    assert!(notes.list_trash().await.unwrap().is_empty());
+    fx.storage.set_note_archived(&archived.id, false).await.unwrap();
+    assert_eq!(notes.list().await.unwrap().len(), 4);
+}
+
+#[tokio::test]
+async fn edits_keep_the_flags() {
+    let fx = Fixture::new().await;
+    let notes = fx.note_service();
+    let note = notes.create("A".to_string(), "<p>a</p>".to_string()).await.unwrap();
+    fx.storage.set_note_pinned(&note.id, true).await.unwrap();
+    fx.storage.set_note_favorite(&note.id, true).await.unwrap();
+
+    let updated = notes.update(note.id.clone(), "B".to_string(), "<p>b</p>".to_string(), None).await.unwrap();
+    assert_eq!(updated.version, note.version + 3);
+    let stored = notes.get(note.id.clone()).await.unwrap().note;
+    assert!(stored.pinned && stored.favorite && !stored.archived);
+
+    // Flags left out of JSON when false
+    let json = serde_json::to_value(&note).unwrap();
+    assert!(json.get("pinned").is_none());
+    assert_eq!(serde_json::to_value(&stored).unwrap()["pinned"], true);
+}
+
+#[tokio::test]
+async fn in_memory_storage_filters_the_same_way() {
+    let storage = InMemoryStorage::new();
+    let settings = Arc::new(Mutex::new(Settings::default()));
+    let service = NoteService::with_parts(storage.clone(), storage, InMemoryFileStore::new(), settings);
+    service.create("A".to_string(), "<p>a</p>".to_string()).await.unwrap();
+    service.create("B".to_string(), "<p>b</p>".to_string()).await.unwrap();
+
+    assert_eq!(service.list().await.unwrap().len(), 2);
+    let archive = NoteFilter { archived: true, ..NoteFilter::default() };
+    assert!(service.list_filtered(archive).await.unwrap().is_empty());
+}
@@ -0,0 +1,123 @@
+// src-tauri/tests/note_lock.rs
+//! Password protected notes: sealed body and attachments, metadata only
+//! until unlocked, and never matched by content in search.
+
+mod common;
+
+use std::fs;
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+
+use app_lib::crypto::{is_note_sealed, is_note_sealed_text};
+use app_lib::error::AppError;
+use app_lib::services::export::ExportFormat;
+use app_lib::services::{ExportService, NoteLocks, VaultEncryption};
+use common::Fixture;
+
+const PASSPHRASE: &str = "my diary key";
+
+#[tokio::test]
+async fn protected_note_shows_metadata_only_until_unlocked() {
+    let fx = Fixture::new().await;
+    let notes = fx.note_service();
+    let note = notes.create("Diary".to_string(), "<p>dear diary</p>".to_string()).await.unwrap();
+    let attachment = notes
+        .add_attachment(note.id.clone(), "photo.png".to_string(), None, b"png".to_vec())
+        .await
+        .unwrap();
+    let locks = NoteLocks::new(fx.storage.clone());
+
+    locks.protect(&note.id, PASSPHRASE).await.unwrap();
+    assert!(is_note_sealed_text(&fs::read_to_string(&note.file_path).unwrap()));
+    assert!(is_note_sealed(&fs::read(&attachment.file_path).unwrap()));
+
+    let locked = notes.get(note.id.clone()).await.unwrap();
+    assert!(locked.locked);
+    assert_eq!((locked.note.title.as_str(), locked.note.body.as_str()), ("Diary", ""));
+    let update = notes.update(note.id.clone(), "Diary".to_string(), "<p>x</p>".to_string(), None).await;
+    assert!(matches!(update, Err(AppError::NoteLocked(_))));
+    let attach = notes.add_attachment(note.id.clone(), "b.txt".to_string(), None, b"b".to_vec()).await;
+    assert!(matches!(attach, Err(AppError::NoteLocked(_))));
+
+    assert!(matches!(locks.unlock(&note.id, "wrong passphrase").await, Err(AppError::Validation(_))));
+    locks.unlock(&note.id, PASSPHRASE).await.unwrap();
+    let unlocked = notes.get(note.id.clone()).await.unwrap();
+    assert!(!unlocked.locked);
+    assert_eq!(unlocked.note.body, "<p>dear diary</p>");
+
+    // Edits while unlocked stay sealed on disk
+    notes.update(note.id.clone(), "Diary".to_string(), "<p>day 2</p>".to_string(), None).await.unwrap();
+    let file = fs::read_to_string(&note.file_path).unwrap();
+    assert!(is_note_sealed_text(&file) && !file.contains("day 2"));
+
+    locks.lock(&note.id);
+    assert_eq!(notes.get(note.id).await.unwrap().note.body, "");
+}
+
+#[tokio::test]
+async fn search_never_matches_protected_content() {
+    let fx = Fixture::new().await;
+    let notes = fx.note_service();
+    let secret = notes.create("Plans".to_string(), "<p>surprise party</p>".to_string()).await.unwrap();
+    notes.create("Open".to_string(), "<p>party supplies</p>".to_string()).await.unwrap();
+    let locks = NoteLocks::new(fx.storage.clone());
+    locks.protect(&secret.id, PASSPHRASE).await.unwrap();
+    locks.unlock(&secret.id, PASSPHRASE).await.unwrap();
+
+    let titles = |found: Vec<app_lib::db::schema::Note>| found.into_iter().map(|n| n.title).collect::<Vec<_>>();
+    assert_eq!(titles(notes.search("party".to_string()).await.unwrap()), ["Open"]);
+    assert_eq!(titles(notes.search("plans".to_string()).await.unwrap()), ["Plans"]);
+
+    // Same in an encrypted vault, where search runs in memory
+    VaultEncryption::new(fx.storage.clone(), fx.settings.clone(), &fx.dir).enable("vault passphrase").await.unwrap();
+    assert_eq!(titles(notes.search("party".to_string()).await.unwrap()), ["Open"]);
+}
+
+#[tokio::test]
+async fn unprotecting_restores_plain_files() {
+    let fx = Fixture::new().await;
+    let notes = fx.note_service();
+    let note = notes.create("Keys".to_string(), "<p>1234</p>".to_string()).await.unwrap();
+    let attachment = notes
+        .add_attachment(note.id.clone(), "scan.pdf".to_string(), None, b"pdf".to_vec())
+        .await
+        .unwrap();
+    let locks = NoteLocks::new(fx.storage.clone());
+    locks.protect(&note.id, PASSPHRASE).await.unwrap();
+
+    assert!(locks.unprotect(&note.id, "wrong passphrase").await.is_err());
+    locks.unprotect(&note.id, PASSPHRASE).await.unwrap();
+    assert_eq!(fs::read_to_string(&note.file_path).unwrap(), "<p>1234</p>");
+    assert_eq!(fs::read(&attachment.file_path).unwrap(), b"pdf");
+
+    let read = notes.get(note.id.clone()).await.unwrap();
+    assert!(!read.locked);
+    assert_eq!(read.note.body, "<p>1234</p>");
+    assert_eq!(notes.search("1234".to_string()).await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn bodies_that_look_sealed_are_plain_text() {
+    let fx = Fixture::new().await;
+    let notes = fx.note_service();
+    let plain = notes.create("Plain".to_string(), "rnn1:not sealed".to_string()).await.unwrap();
+    notes
+        .add_attachment(plain.id.clone(), "x.bin".to_string(), None, b"RNN1 data".to_vec())
+        .await
+        .unwrap();
+    assert_eq!(notes.get(plain.id.clone()).await.unwrap().note.body, "rnn1:not sealed");
+    let export = ExportService::new(fx.storage.clone()).export_note(&plain.id, ExportFormat::Json).await.unwrap();
+    assert!(export.contents.contains(&BASE64.encode(b"RNN1 data")));
+
+    // A protected note's body is sealed even when it looks sealed already
+    let diary = notes.create("Diary".to_string(), "<p>a</p>".to_string()).await.unwrap();
+    let locks = NoteLocks::new(fx.storage.clone());
+    locks.protect(&diary.id, PASSPHRASE).await.unwrap();
+    locks.unlock(&diary.id, PASSPHRASE).await.unwrap();
+    notes.update(diary.id.clone(), "Diary".to_string(), "rnn1:day 2".to_string(), None).await.unwrap();
+    assert_ne!(fs::read_to_string(&diary.file_path).unwrap(), "rnn1:day 2");
+    assert_eq!(notes.get(diary.id.clone()).await.unwrap().note.body, "rnn1:day 2");
+    locks.lock(&diary.id);
+    assert_eq!(notes.get(diary.id).await.unwrap().note.body, "");
+}
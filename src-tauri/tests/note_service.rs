@@ -0,0 +1,165 @@
+// src-tauri/tests/note_service.rs
+//! `NoteService` flows end to end: create, update, delete, trash, restore.
+//! Most run on the in-memory parts; the trash round trip also runs on SQLite.
+
+mod common;
+
+use std::sync::Arc;
+
+use tokio::sync::Mutex;
+
+use app_lib::error::AppError;
+use app_lib::services::{InMemoryFileStore, InMemoryStorage, NoteService};
+use app_lib::settings::model::DeleteBehavior;
+use app_lib::settings::Settings;
+use common::Fixture;
+
+struct Memory {
+    storage: InMemoryStorage,
+    files: InMemoryFileStore,
+    service: NoteService<InMemoryStorage, InMemoryStorage, InMemoryFileStore>,
+}
+
+fn memory(delete_behavior: DeleteBehavior) -> Memory {
+    let storage = InMemoryStorage::new();
+    let files = InMemoryFileStore::new();
+    let settings = Arc::new(Mutex::new(Settings { delete_behavior, ..Settings::default() }));
+    let service = NoteService::with_parts(storage.clone(), storage.clone(), files.clone(), settings);
+    Memory { storage, files, service }
+}
+
+fn text(bytes: Option<Vec<u8>>) -> Option<String> {
+    bytes.map(|b| String::from_utf8(b).unwrap())
+}
+
+#[tokio::test]
+async fn create_writes_row_and_file() {
+    let m = memory(DeleteBehavior::MoveToTrash);
+    let note = m.service.create("Plan".to_string(), "<p>trip</p>".to_string()).await.unwrap();
+
+    assert_eq!(note.version, 1);
+    assert_eq!(text(m.files.read(&note.file_path)).as_deref(), Some("<p>trip</p>"));
+
+    let listed = m.service.list().await.unwrap();
+    assert_eq!(listed.iter().map(|n| n.id.as_str()).collect::<Vec<_>>(), vec![note.id.as_str()]);
+    assert_eq!(m.service.search("TRIP".to_string()).await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn update_rewrites_file_and_rejects_stale_versions() {
+    let m = memory(DeleteBehavior::MoveToTrash);
+    let note = m.service.create("A".to_string(), "<p>a</p>".to_string()).await.unwrap();
+
+    let updated = m.service.update(note.id.clone(), "B".to_string(), "<p>b</p>".to_string(), Some(1)).await.unwrap();
+    assert_eq!((updated.version, updated.file_path.as_str()), (2, note.file_path.as_str()));
+    assert_eq!(text(m.files.read(&note.file_path)).as_deref(), Some("<p>b</p>"));
+
+    let stale = m.service.update(note.id.clone(), "C".to_string(), "<p>c</p>".to_string(), Some(1)).await;
+    match stale {
+        Err(AppError::Conflict { current, .. }) => assert_eq!(current.body, "<p>b</p>"),
+        other => panic!("expected a conflict, got {:?}", other),
+    }
+    // Nothing written by the rejected update
+    assert_eq!(text(m.files.read(&note.file_path)).as_deref(), Some("<p>b</p>"));
+}
+
+#[tokio::test]
+async fn delete_moves_files_to_the_trash_folder() {
+    let m = memory(DeleteBehavior::MoveToTrash);
+    let note = m.service.create("A".to_string(), "<p>a</p>".to_string()).await.unwrap();
+    let attachment = m
+        .service
+        .add_attachment(note.id.clone(), "map.png".to_string(), Some("image/png".to_string()), b"png".to_vec())
+        .await
+        .unwrap();
+
+    m.service.delete(note.id.clone()).await.unwrap();
+
+    assert!(m.files.paths().is_empty());
+    assert_eq!(text(m.files.read_trashed(&note.file_path)).as_deref(), Some("<p>a</p>"));
+    assert_eq!(m.files.read_trashed(&attachment.file_path), Some(b"png".to_vec()));
+    assert!(matches!(m.service.get(note.id.clone()).await, Err(AppError::NotFound(_))));
+    // Deleted for good: not in the soft-delete trash either
+    assert!(m.service.list_trash().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn permanent_delete_removes_files() {
+    let m = memory(DeleteBehavior::Permanent);
+    let note = m.service.create("A".to_string(), "<p>a</p>".to_string()).await.unwrap();
+    m.service.add_attachment(note.id.clone(), "a.txt".to_string(), None, b"x".to_vec()).await.unwrap();
+
+    m.service.delete(note.id.clone()).await.unwrap();
+
+    assert!(m.files.paths().is_empty());
+    assert!(m.files.read_trashed(&note.file_path).is_none());
+    assert!(m.storage_is_empty().await);
+}
+
+#[tokio::test]
+async fn trash_and_restore_round_trip() {
+    let m = memory(DeleteBehavior::MoveToTrash);
+    let note = m.service.create("A".to_string(), "<p>a</p>".to_string()).await.unwrap();
+
+    m.service.trash(note.id.clone()).await.unwrap();
+    assert!(m.service.list().await.unwrap().is_empty());
+    assert!(m.service.search("a".to_string()).await.unwrap().is_empty());
+    assert_eq!(m.service.list_trash().await.unwrap()[0].id, note.id);
+    // Files stay where they are while the note is in the trash
+    assert!(m.files.read(&note.file_path).is_some());
+
+    // Trashed notes can't be edited or trashed twice
+    let edit = m.service.update(note.id.clone(), "A".to_string(), "<p>x</p>".to_string(), None).await;
+    assert!(matches!(edit, Err(AppError::NotFound(_))));
+    assert!(matches!(m.service.trash(note.id.clone()).await, Err(AppError::NotFound(_))));
+
+    let restored = m.service.restore(note.id.clone()).await.unwrap();
+    assert_eq!((restored.body.as_str(), restored.version), ("<p>a</p>", 3));
+    assert_eq!(m.service.list().await.unwrap().len(), 1);
+    assert!(m.service.list_trash().await.unwrap().is_empty());
+    assert!(matches!(m.service.restore(note.id.clone()).await, Err(AppError::NotFound(_))));
+}
+
+#[tokio::test]
+async fn attachments_need_an_active_note() {
+    let m = memory(DeleteBehavior::MoveToTrash);
+    let missing = m.service.add_attachment("nope".to_string(), "a.txt".to_string(), None, b"x".to_vec()).await;
+    assert!(matches!(missing, Err(AppError::NotFound(_))));
+    assert!(m.files.paths().is_empty());
+}
+
+#[tokio::test]
+async fn rename_rewrites_links_to_the_note() {
+    let m = memory(DeleteBehavior::MoveToTrash);
+    let plan = m.service.create("Plan".to_string(), "<p>[[Plan]]</p>".to_string()).await.unwrap();
+    let trip = m.service.create("Trip".to_string(), "<p>[[plan]]</p>".to_string()).await.unwrap();
+
+    m.service.update(plan.id.clone(), "Q3".to_string(), "<p>[[Plan]]</p>".to_string(), None).await.unwrap();
+    assert_eq!(text(m.files.read(&trip.file_path)).as_deref(), Some("<p>[[Q3]]</p>"));
+    // The renamed note's own text is as the caller sent it
+    assert_eq!(text(m.files.read(&plan.file_path)).as_deref(), Some("<p>[[Plan]]</p>"));
+}
+
+#[tokio::test]
+async fn trash_and_restore_on_sqlite() {
+    let fx = Fixture::new().await;
+    let notes = fx.note_service();
+    let note = notes.create("A".to_string(), "<p>a</p>".to_string()).await.unwrap();
+
+    notes.trash(note.id.clone()).await.unwrap();
+    assert!(notes.list().await.unwrap().is_empty());
+    assert_eq!(notes.list_trash().await.unwrap()[0].id, note.id);
+    assert!(matches!(notes.trash(note.id.clone()).await, Err(AppError::NotFound(_))));
+
+    let restored = notes.restore(note.id.clone()).await.unwrap();
+    assert_eq!((restored.body.as_str(), restored.version), ("<p>a</p>", 3));
+    assert_eq!(notes.list().await.unwrap().len(), 1);
+    assert!(matches!(notes.restore(note.id.clone()).await, Err(AppError::NotFound(_))));
+}
+
+impl Memory {
+    async fn storage_is_empty(&self) -> bool {
+        use app_lib::services::NoteRepository;
+        self.storage.list(&Default::default()).await.unwrap().is_empty() && self.storage.list_trashed().await.unwrap().is_empty()
+    }
+}
@@ -0,0 +1,67 @@
+// src-tauri/tests/notes.rs
+//! Optimistic concurrency on note updates and the errors the webview sees.
+
+mod common;
+
+use std::fs;
+
+use app_lib::error::AppError;
+use common::Fixture;
+
+#[tokio::test]
+async fn stale_update_is_rejected_with_the_current_note() {
+    let fx = Fixture::new().await;
+    let notes = fx.note_service();
+    let note = notes.create("A".to_string(), "<p>a</p>".to_string()).await.unwrap();
+
+    // Two editors start from version 1; the first one wins
+    let first = notes.update(note.id.clone(), "A".to_string(), "<p>first</p>".to_string(), Some(1)).await.unwrap();
+    assert_eq!(first.version, 2);
+
+    let second = notes.update(note.id.clone(), "A".to_string(), "<p>second</p>".to_string(), Some(1)).await;
+    match second {
+        Err(AppError::Conflict { expected_version, current }) => {
+            assert_eq!(expected_version, 1);
+            assert_eq!((current.version, current.body.as_str()), (2, "<p>first</p>"));
+        }
+        other => panic!("expected a conflict, got {:?}", other),
+    }
+
+    let stored = notes.get(note.id.clone()).await.unwrap().note;
+    assert_eq!((stored.version, stored.body.as_str()), (2, "<p>first</p>"));
+    assert_eq!(fs::read_to_string(&stored.file_path).unwrap(), "<p>first</p>");
+}
+
+#[tokio::test]
+async fn update_without_expected_version_overwrites() {
+    let fx = Fixture::new().await;
+    let notes = fx.note_service();
+    let note = notes.create("A".to_string(), String::new()).await.unwrap();
+    notes.update(note.id.clone(), "B".to_string(), String::new(), Some(1)).await.unwrap();
+
+    let forced = notes.update(note.id.clone(), "C".to_string(), String::new(), None).await.unwrap();
+    assert_eq!((forced.title.as_str(), forced.version), ("C", 3));
+}
+
+#[tokio::test]
+async fn conflict_serializes_for_the_webview() {
+    let fx = Fixture::new().await;
+    let notes = fx.note_service();
+    let note = notes.create("A".to_string(), String::new()).await.unwrap();
+    notes.update(note.id.clone(), "B".to_string(), String::new(), None).await.unwrap();
+
+    let error = notes.update(note.id.clone(), "C".to_string(), String::new(), Some(1)).await.unwrap_err();
+    let json = serde_json::to_value(&error).unwrap();
+    assert_eq!(json["code"], "conflict");
+    assert_eq!(json["details"]["expected_version"], 1);
+    assert_eq!(json["details"]["current"]["title"], "B");
+    assert_eq!(json["details"]["current"]["version"], 2);
+}
+
+#[tokio::test]
+async fn missing_note_is_not_found() {
+    let fx = Fixture::new().await;
+    let error = fx.note_service().get("nope".to_string()).await.unwrap_err();
+    assert!(matches!(error, AppError::NotFound(_)));
+    assert_eq!(serde_json::to_value(&error).unwrap()["code"], "not_found");
+}
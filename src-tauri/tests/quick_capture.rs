@@ -0,0 +1,38 @@
+// src-tauri/tests/quick_capture.rs
+//! Quick capture files plain text into the inbox notebook.
+
+mod common;
+
+use std::fs;
+
+use app_lib::error::AppError;
+use app_lib::services::QuickCapture;
+use common::Fixture;
+
+fn capture(fx: &Fixture) -> QuickCapture {
+    QuickCapture::new(fx.storage.clone(), fx.settings.clone())
+}
+
+#[tokio::test]
+async fn saves_text_into_the_inbox() {
+    let fx = Fixture::new().await;
+    fx.settings.lock().await.quick_capture.notebook = " Later ".to_string();
+
+    let note = capture(&fx).save("", "Call <Sam>\n\n- [ ] book room\n").await.unwrap();
+    assert_eq!(note.title, "Call <Sam>");
+    assert_eq!(note.notebook.as_deref(), Some("Later"));
+    assert!(note.body.starts_with("<p>Call &lt;Sam&gt;</p>"));
+    assert!(note.body.contains("data-type=\"taskItem\""));
+
+    let stored = fx.storage.get_note(&note.id).await.unwrap().note;
+    assert_eq!(stored.notebook.as_deref(), Some("Later"));
+    assert_eq!(fs::read_to_string(&stored.file_path).unwrap(), note.body);
+}
+
+#[tokio::test]
+async fn refuses_an_empty_capture() {
+    let fx = Fixture::new().await;
+    let result = capture(&fx).save(" ", "\n  \n").await;
+    assert!(matches!(result, Err(AppError::Validation(_))));
+    assert!(fx.note_service().list().await.unwrap().is_empty());
+}
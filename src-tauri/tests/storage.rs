@@ -0,0 +1,103 @@
+// src-tauri/tests/storage.rs
+//! Shared storage: reads run next to writes, multi-step writes stay ordered.
+
+mod common;
+
+use std::fs;
+use std::time::Duration;
+
+use app_lib::db::schema::NoteFilter;
+use common::Fixture;
+
+#[tokio::test]
+async fn reads_are_not_blocked_by_a_pending_write() {
+    let fx = Fixture::new().await;
+    fx.note_service().create("Plan".to_string(), "<p>trip</p>".to_string()).await.unwrap();
+
+    let _writes = fx.storage.lock_writes().await;
+    let reader = fx.storage.clone();
+    let filter = NoteFilter::default();
+    let (listed, found) = tokio::time::timeout(Duration::from_secs(2), async move {
+        (reader.list_notes(&filter).await.unwrap(), reader.search_notes("trip", &filter).await.unwrap())
+    })
+    .await
+    .expect("reads waited for the write lock");
+
+    assert_eq!((listed.len(), found.len()), (1, 1));
+}
+
+#[tokio::test]
+async fn concurrent_autosaves_leave_row_and_file_in_sync() {
+    let fx = Fixture::new().await;
+    let note = fx.note_service().create("A".to_string(), String::new()).await.unwrap();
+
+    let saves: Vec<_> = (0..20)
+        .map(|i| {
+            let notes = fx.note_service();
+            let id = note.id.clone();
+            tokio::spawn(async move { notes.update(id, "A".to_string(), format!("<p>{}</p>", i), None).await })
+        })
+        .collect();
+    for save in saves {
+        save.await.unwrap().unwrap();
+    }
+
+    let stored = fx.storage.get_note(&note.id).await.unwrap().note;
+    assert_eq!(stored.version, 21);
+    assert_eq!(fs::read_to_string(&stored.file_path).unwrap(), stored.body);
+}
+
+/// `file_path` exactly as stored in the row.
+async fn stored_path(fx: &Fixture, table: &str, id: &str) -> String {
+    let pool = app_lib::db::init_database(&fx.dir).await.unwrap();
+    let (path,): (String,) = sqlx::query_as(&format!("SELECT file_path FROM {} WHERE id = ?", table))
+        .bind(id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    path
+}
+
+#[tokio::test]
+async fn paths_are_stored_relative_to_the_notes_folder() {
+    let fx = Fixture::new().await;
+    let notes = fx.note_service();
+    let note = notes.create("A".to_string(), "<p>a</p>".to_string()).await.unwrap();
+    let attachment = notes.add_attachment(note.id.clone(), "a.txt".to_string(), None, b"a".to_vec()).await.unwrap();
+
+    assert_eq!(stored_path(&fx, "notes", &note.id).await, format!("{}.html", note.id));
+    assert_eq!(
+        stored_path(&fx, "attachments", &attachment.id).await,
+        format!("attachments/{}/a.txt", note.id)
+    );
+
+    // Same DB opened on another machine / after the folder moved
+    let elsewhere = fx.dir.join("synced");
+    fx.storage.set_notes_folder(&elsewhere);
+    let moved = fx.storage.get_note(&note.id).await.unwrap();
+    assert_eq!(moved.note.file_path, elsewhere.join(format!("{}.html", note.id)).to_string_lossy());
+    assert!(std::path::Path::new(&moved.attachments[0].file_path).starts_with(&elsewhere));
+}
+
+#[tokio::test]
+async fn absolute_paths_from_older_rows_are_migrated() {
+    let fx = Fixture::new().await;
+    let pool = app_lib::db::init_database(&fx.dir).await.unwrap();
+    let inside = fx.dir.join("notes").join("old.html").to_string_lossy().to_string();
+    let outside = fx.dir.join("elsewhere").join("other.html").to_string_lossy().to_string();
+    for (id, path) in [("old", &inside), ("other", &outside)] {
+        sqlx::query("INSERT INTO notes (id, title, body, created_at, updated_at, file_path) VALUES (?, ?, '', 0, 0, ?)")
+            .bind(id)
+            .bind(id)
+            .bind(path)
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+
+    assert_eq!(fx.storage.store_paths_relative().await.unwrap(), 1);
+    assert_eq!(stored_path(&fx, "notes", "old").await, "old.html");
+    assert_eq!(stored_path(&fx, "notes", "other").await, outside);
+    assert_eq!(fx.storage.get_note("old").await.unwrap().note.file_path, inside);
+    assert_eq!(fx.storage.store_paths_relative().await.unwrap(), 0);
+}
@@ -0,0 +1,70 @@
+// src-tauri/tests/templates.rs
+//! Notes created from the templates in `<notes folder>/templates`.
+
+mod common;
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use app_lib::error::AppError;
+use app_lib::services::Templates;
+use common::Fixture;
+
+const MEETING: &str = "<!--\ntitle: Meeting {{date}}\ntags: meeting, work\nnotebook: Work\n-->\n<h1>{{title}}</h1><p>With {{who}}</p>";
+
+async fn write_templates(fx: &Fixture) -> Templates {
+    let folder = Path::new(&fx.settings.lock().await.notes_folder).join("templates");
+    fs::create_dir_all(&folder).unwrap();
+    fs::write(folder.join("meeting.html"), MEETING).unwrap();
+    fs::write(folder.join("incident.html"), "<p>{{title}} at {{time}}</p>").unwrap();
+    fs::write(folder.join("notes.txt"), "not a template").unwrap();
+    Templates::new(fx.storage.clone(), fx.settings.clone())
+}
+
+#[tokio::test]
+async fn lists_the_html_files() {
+    let fx = Fixture::new().await;
+    let templates = Templates::new(fx.storage.clone(), fx.settings.clone());
+    assert!(templates.list().await.unwrap().is_empty());
+
+    let templates = write_templates(&fx).await;
+    let listed = templates.list().await.unwrap();
+    let ids: Vec<_> = listed.iter().map(|t| t.id.as_str()).collect();
+    assert_eq!(ids, ["incident", "meeting"]);
+    assert_eq!((listed[1].title.as_str(), listed[1].notebook.as_deref()), ("Meeting {{date}}", Some("Work")));
+}
+
+#[tokio::test]
+async fn creates_a_note_with_tags_and_notebook() {
+    let fx = Fixture::new().await;
+    let templates = write_templates(&fx).await;
+
+    let vars = HashMap::from([("who".to_string(), "Sam <ops>".to_string()), ("date".to_string(), "Monday".to_string())]);
+    let note = templates.create_note("meeting", vars).await.unwrap();
+    assert_eq!(note.title, "Meeting Monday");
+    assert_eq!(note.body, "<h1>Meeting Monday</h1><p>With Sam &lt;ops&gt;</p>");
+    assert_eq!(note.notebook.as_deref(), Some("Work"));
+    assert_eq!(fx.storage.list_note_tags(&note.id).await.unwrap(), ["meeting", "work"]);
+    // The returned note is current: it can be saved without a conflict
+    let stored = fx.storage.get_note(&note.id).await.unwrap().note;
+    assert_eq!(stored.version, note.version);
+    assert_eq!(fs::read_to_string(&note.file_path).unwrap(), note.body);
+
+    // No header: titled by the id unless the caller names it
+    let note = templates.create_note("incident", HashMap::new()).await.unwrap();
+    assert_eq!((note.title.as_str(), note.notebook), ("incident", None));
+    assert!(fx.storage.list_note_tags(&note.id).await.unwrap().is_empty());
+    let named = HashMap::from([("title".to_string(), "Outage".to_string())]);
+    assert!(templates.create_note("incident", named).await.unwrap().body.starts_with("<p>Outage at "));
+}
+
+#[tokio::test]
+async fn unknown_templates_are_not_found() {
+    let fx = Fixture::new().await;
+    let templates = write_templates(&fx).await;
+    for id in ["missing", "../notes", "", "notes.txt"] {
+        assert!(matches!(templates.create_note(id, HashMap::new()).await, Err(AppError::NotFound(_))), "{}", id);
+    }
+    assert!(fx.storage.list_notes(&Default::default()).await.unwrap().is_empty());
+}
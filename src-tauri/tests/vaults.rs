@@ -0,0 +1,120 @@
+// src-tauri/tests/vaults.rs
+//! Vaults keep their own DB, notes folder and settings overrides.
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::Mutex;
+
+use app_lib::services::{AppEvent, EventBus, NoteService};
+use app_lib::settings::model::DeleteBehavior;
+use app_lib::vaults::{self, LoadedVault, StorageSlot, VaultRegistry};
+
+struct Dirs {
+    root: PathBuf,
+    config: PathBuf,
+    data: PathBuf,
+}
+
+impl Dirs {
+    fn new() -> Self {
+        let root = std::env::temp_dir().join(format!("rusty-notes-vaults-{}", uuid::Uuid::new_v4()));
+        Self { config: root.join("config"), data: root.join("data"), root }
+    }
+}
+
+impl Drop for Dirs {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
+
+fn notes(loaded: &LoadedVault) -> NoteService {
+    NoteService::new(loaded.storage.clone(), Arc::new(Mutex::new(loaded.settings.clone())))
+}
+
+#[tokio::test]
+async fn vaults_keep_notes_and_settings_apart() {
+    let dirs = Dirs::new();
+    let mut registry = VaultRegistry::load(&dirs.config, &dirs.data).unwrap();
+    let work = registry.create("Work", &dirs.data).unwrap();
+    registry.last_opened = Some(work.id.clone());
+    registry.save(&dirs.config).unwrap();
+
+    let bus = EventBus::new();
+    let default = vaults::load(registry.get(vaults::DEFAULT_VAULT_ID).unwrap(), &dirs.config, bus.clone())
+        .await
+        .unwrap();
+    let mut opened = vaults::load(&work, &dirs.config, bus.clone()).await.unwrap();
+
+    // Each vault's relative notes folder lives in its own data dir
+    assert_eq!(PathBuf::from(&default.settings.notes_folder), dirs.data.join("notes"));
+    assert_eq!(PathBuf::from(&opened.settings.notes_folder), work.data_dir.join("notes"));
+
+    let mut events = bus.subscribe();
+    let note = notes(&opened).create("Plan".to_string(), "<p>q3</p>".to_string()).await.unwrap();
+    assert!(notes(&default).list().await.unwrap().is_empty());
+    // Every vault publishes on the app's bus
+    assert!(matches!(events.recv().await.unwrap().event, AppEvent::NoteCreated { .. }));
+
+    // Overrides stay with the vault
+    opened.settings.delete_behavior = DeleteBehavior::Permanent;
+    opened.settings.onboarding_completed = true;
+    opened.settings_files.save(&opened.settings).unwrap();
+    let default = vaults::load(registry.get(vaults::DEFAULT_VAULT_ID).unwrap(), &dirs.config, bus.clone())
+        .await
+        .unwrap();
+    assert!(matches!(default.settings.delete_behavior, DeleteBehavior::MoveToTrash));
+    assert!(default.settings.onboarding_completed, "app-wide fields are shared");
+
+    let reopened = VaultRegistry::load(&dirs.config, &dirs.data).unwrap();
+    assert_eq!(reopened.last_opened().id, work.id);
+    let work_again = vaults::load(reopened.last_opened(), &dirs.config, bus).await.unwrap();
+    assert!(matches!(work_again.settings.delete_behavior, DeleteBehavior::Permanent));
+    assert_eq!(notes(&work_again).list().await.unwrap()[0].id, note.id);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn switching_during_saves_does_not_deadlock() {
+    let dirs = Dirs::new();
+    let mut registry = VaultRegistry::load(&dirs.config, &dirs.data).unwrap();
+    let work = registry.create("Work", &dirs.data).unwrap();
+    let default = registry.get(vaults::DEFAULT_VAULT_ID).unwrap().clone();
+
+    let bus = EventBus::new();
+    let first = vaults::load(&default, &dirs.config, bus.clone()).await.unwrap();
+    let settings = Arc::new(Mutex::new(first.settings.clone()));
+    let slot = Arc::new(StorageSlot::new(first.storage.clone()));
+    let note = notes(&first).create("Plan".to_string(), "<p>0</p>".to_string()).await.unwrap();
+
+    // Saves lock writes, then settings (for the notes folder); switches
+    // used to lock them the other way round
+    let saving = {
+        let (slot, settings, id) = (slot.clone(), settings.clone(), note.id.clone());
+        tokio::spawn(async move {
+            for i in 0..200 {
+                let service = NoteService::new(slot.get(), settings.clone());
+                // Saves after the first switch land on another vault's DB
+                let _ = service.update(id.clone(), "Plan".to_string(), format!("<p>{}</p>", i), None).await;
+            }
+        })
+    };
+    let switching = async {
+        for i in 0..10 {
+            let vault = if i % 2 == 0 { &work } else { &default };
+            let loaded = vaults::load(vault, &dirs.config, bus.clone()).await.unwrap();
+            let open = vaults::swap_in(&slot, &settings, loaded).await;
+            assert_eq!(open.vault.id, vault.id);
+            assert_eq!(PathBuf::from(&settings.lock().await.notes_folder), vault.data_dir.join("notes"));
+        }
+    };
+
+    tokio::time::timeout(Duration::from_secs(30), async {
+        switching.await;
+        saving.await.unwrap();
+    })
+    .await
+    .expect("vault switch deadlocked with a save");
+}
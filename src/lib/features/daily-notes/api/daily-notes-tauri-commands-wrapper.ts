@@ -0,0 +1,20 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { NoteWithAttachmentsDTO } from '$lib/features/notes/notes-types';
+
+export interface DailyNoteDTO extends NoteWithAttachmentsDTO {
+  date: string; // YYYY-MM-DD
+  /** Closest dates with a daily note, to open with open() */
+  previous: string | null;
+  next: string | null;
+}
+
+/**
+ * Tauri API wrapper for daily notes: one note per date, titled and filed
+ * as the daily note settings say
+ */
+export class DailyNotesAPI {
+  /** The note for `date` (YYYY-MM-DD, default today), created if missing */
+  static async open(date?: string): Promise<DailyNoteDTO> {
+    return await invoke<DailyNoteDTO>('open_daily_note', { date });
+  }
+}
@@ -0,0 +1,42 @@
+import { invoke } from '@tauri-apps/api/core';
+
+export interface EncryptionStatus {
+  encrypted: boolean;
+  /** Note reads and writes fail with `{ code: 'locked' }` until unlocked */
+  locked: boolean;
+}
+
+export interface EncryptionReport {
+  notes: number;
+  files: number;
+}
+
+/**
+ * Tauri API wrapper for encryption at rest of the open vault
+ */
+export class EncryptionAPI {
+  static async status(): Promise<EncryptionStatus> {
+    return await invoke<EncryptionStatus>('encryption_status');
+  }
+
+  /**
+   * Encrypts the vault (at least 8 characters). Calling it again with the
+   * same passphrase finishes an interrupted run.
+   */
+  static async enable(passphrase: string): Promise<EncryptionReport> {
+    return await invoke<EncryptionReport>('enable_encryption', { passphrase });
+  }
+
+  static async unlock(passphrase: string): Promise<void> {
+    await invoke('unlock_vault', { passphrase });
+  }
+
+  static async lock(): Promise<void> {
+    await invoke('lock_vault');
+  }
+
+  /** Rewraps the vault key only; no note is rewritten. */
+  static async changePassphrase(oldPassphrase: string, newPassphrase: string): Promise<void> {
+    await invoke('change_passphrase', { oldPassphrase, newPassphrase });
+  }
+}
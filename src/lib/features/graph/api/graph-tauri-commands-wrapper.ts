@@ -0,0 +1,50 @@
+import { invoke } from '@tauri-apps/api/core';
+
+export type GraphFormat = 'dot' | 'graphml' | 'json';
+
+/** A note has to match every filter given */
+export interface GraphFilter {
+  tag?: string;
+  notebook?: string;
+  /** Only notes at most `hops` (default 1) links away from this note */
+  around?: string;
+  hops?: number;
+}
+
+export interface GraphNode {
+  id: string;
+  title: string;
+  tags: string[];
+  notebook: string | null;
+}
+
+/** A link from note `source` to note `target` */
+export interface GraphEdge {
+  source: string;
+  target: string;
+}
+
+export interface Graph {
+  nodes: GraphNode[];
+  edges: GraphEdge[];
+}
+
+export interface ExportedGraph {
+  file_name: string;
+  mime_type: string;
+  contents: string;
+}
+
+/**
+ * Tauri API wrapper for the note graph (active notes and their links)
+ */
+export class GraphAPI {
+  static async get(filter?: GraphFilter): Promise<Graph> {
+    return await invoke<Graph>('get_graph', { filter });
+  }
+
+  /** The caller picks where to save `contents` */
+  static async export(format: GraphFormat, filter?: GraphFilter): Promise<ExportedGraph> {
+    return await invoke<ExportedGraph>('export_graph', { format, filter });
+  }
+}
@@ -1,12 +1,12 @@
 import { invoke } from '@tauri-apps/api/core';
-import type { NoteDTO, NoteWithAttachmentsDTO } from '../notes-types';
+import type { BrokenLinkDTO, NoteDTO, NoteFilter, NoteWithAttachmentsDTO, OutgoingLinkDTO } from '../notes-types';
 
 /**
  * Tauri API wrapper for note operations
  */
 export class NotesAPI {
-  static async list(): Promise<NoteDTO[]> {
-    return await invoke<NoteDTO[]>('list_notes');
+  static async list(filter?: NoteFilter): Promise<NoteDTO[]> {
+    return await invoke<NoteDTO[]>('list_notes', { filter });
   }
 
   static async listWithAttachments(): Promise<NoteWithAttachmentsDTO[]> {
@@ -25,8 +25,12 @@ export class NotesAPI {
     return await invoke<NoteDTO>('create_note', { title, body });
   }
 
-  static async update(id: string, title: string, body: string): Promise<void> {
-    await invoke('update_note', { id, title, body });
+  /**
+   * Pass the `version` the edit started from: if the note changed since,
+   * the call rejects with `{ code: 'conflict', details: { current } }` instead of overwriting.
+   */
+  static async update(id: string, title: string, body: string, expectedVersion?: number): Promise<NoteDTO> {
+    return await invoke<NoteDTO>('update_note', { id, title, body, expectedVersion });
   }
 
   /**
@@ -68,8 +72,55 @@ export class NotesAPI {
     return await invoke<NoteDTO[]>('list_deleted_notes');
   }
 
-  static async search(query: string): Promise<NoteDTO[]> {
-    return await invoke<NoteDTO[]>('search_notes', { query });
+  static async search(query: string, filter?: NoteFilter): Promise<NoteDTO[]> {
+    return await invoke<NoteDTO[]>('search_notes', { query, filter });
+  }
+
+  static async setPinned(id: string, pinned: boolean): Promise<void> {
+    await invoke('set_note_pinned', { id, pinned });
+  }
+
+  static async setFavorite(id: string, favorite: boolean): Promise<void> {
+    await invoke('set_note_favorite', { id, favorite });
+  }
+
+  /** Archived notes leave the default list and search, not for the trash */
+  static async setArchived(id: string, archived: boolean): Promise<void> {
+    await invoke('set_note_archived', { id, archived });
+  }
+
+  /**
+   * Password protect a note (at least 8 characters). It stays locked:
+   * `get_note` returns `locked: true` and an empty body until unlock().
+   */
+  static async protect(id: string, passphrase: string): Promise<void> {
+    await invoke('protect_note', { id, passphrase });
+  }
+
+  static async unprotect(id: string, passphrase: string): Promise<void> {
+    await invoke('unprotect_note', { id, passphrase });
+  }
+
+  /** Readable and editable until the app quits or lock() */
+  static async unlock(id: string, passphrase: string): Promise<void> {
+    await invoke('unlock_note', { id, passphrase });
+  }
+
+  static async lock(id: string): Promise<void> {
+    await invoke('lock_note', { id });
+  }
+
+  /** Notes linking here; renaming this note rewrites their [[Title]] links */
+  static async backlinks(id: string): Promise<NoteDTO[]> {
+    return await invoke<NoteDTO[]>('get_backlinks', { id });
+  }
+
+  static async outgoingLinks(id: string): Promise<OutgoingLinkDTO[]> {
+    return await invoke<OutgoingLinkDTO[]>('get_outgoing_links', { id });
+  }
+
+  static async brokenLinks(): Promise<BrokenLinkDTO[]> {
+    return await invoke<BrokenLinkDTO[]>('get_broken_links');
   }
 }
 
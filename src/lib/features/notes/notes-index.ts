@@ -6,10 +6,13 @@ export { noteService } from './store/notes-service.svelte';
 export type {
   Note,
   NoteDTO,
+  NoteFilter,
   Attachment,
   AttachmentDTO,
   NoteWithAttachments,
-  NoteWithAttachmentsDTO
+  NoteWithAttachmentsDTO,
+  OutgoingLinkDTO,
+  BrokenLinkDTO
 } from './notes-types';
 
 export { NotesAPI } from './api/notes-tauri-commands-wrapper';
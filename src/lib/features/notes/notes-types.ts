@@ -10,7 +10,22 @@ export interface NoteDTO {
   created_at: number; // Unix timestamp (i64 in Rust)
   updated_at: number; // Unix timestamp (i64 in Rust)
   file_path: string;
+  notebook: string | null;
+  version: number; // bumped by every change; pass back to update()
   is_deleted?: number; // SQLite boolean (0 or 1)
+  pinned?: boolean; // flags are left out when false
+  favorite?: boolean;
+  archived?: boolean;
+}
+
+/**
+ * Which notes list() / search() return - matches NoteFilter.
+ * Default: active, non-archived notes; pinned ones always come first.
+ */
+export interface NoteFilter {
+  archived?: boolean; // archived notes instead of the others
+  favorites?: boolean;
+  pinned?: boolean;
 }
 
 /**
@@ -39,6 +54,27 @@ export interface NoteWithAttachmentsDTO {
   file_path: string;
   is_deleted?: number;
   attachments: AttachmentDTO[];
+  /** Password protected and not unlocked: body is empty */
+  locked?: boolean;
+}
+
+/**
+ * A link from a note (`[[Title]]` or `note://<id>`) - matches OutgoingLink
+ */
+export interface OutgoingLinkDTO {
+  target: string; // title or id as written
+  note_id: string | null;
+  title: string | null;
+  broken: boolean;
+}
+
+/**
+ * A link to a missing or trashed note - matches BrokenLink
+ */
+export interface BrokenLinkDTO {
+  source_id: string;
+  source_title: string;
+  target: string;
 }
 
 /**
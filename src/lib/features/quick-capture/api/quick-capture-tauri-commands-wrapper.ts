@@ -0,0 +1,24 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { NoteDTO } from '$lib/features/notes/notes-types';
+
+/**
+ * Tauri API wrapper for the quick capture window
+ */
+export class QuickCaptureAPI {
+  /**
+   * Saves into the inbox notebook and hides the window. An empty title
+   * takes the first line of the text.
+   */
+  static async save(title: string, text: string): Promise<NoteDTO> {
+    return await invoke<NoteDTO>('save_quick_capture', { title, text });
+  }
+
+  static async dismiss(): Promise<void> {
+    await invoke('dismiss_quick_capture');
+  }
+
+  /** Clipboard text to start with, when `quick_capture.prefill_from_clipboard` is on. */
+  static async prefill(): Promise<string | null> {
+    return await invoke<string | null>('quick_capture_prefill');
+  }
+}
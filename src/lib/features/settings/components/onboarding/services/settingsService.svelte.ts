@@ -8,6 +8,49 @@ export interface Settings {
   auto_save_interval_secs: number;
   delete_behavior: DeleteBehavior;
   onboarding_completed: boolean;
+  shortcuts: ShortcutSettings;
+  quick_capture: QuickCaptureSettings;
+  tray: TraySettings;
+  encryption: EncryptionSettings;
+  daily_notes: DailyNoteSettings;
+  /** Set by `get_settings` when an unreadable settings file was reset to
+   * defaults; where the old file was kept. Never saved. */
+  recovered_from?: string | null;
+}
+
+export interface DailyNoteSettings {
+  /** chrono date format, e.g. `%Y-%m-%d` or `%A, %B %-d` */
+  title_format: string;
+  /** Empty keeps the template's notebook */
+  notebook: string;
+  /** Template id new daily notes start from; empty starts them blank */
+  template: string;
+}
+
+export interface EncryptionSettings {
+  /** Lock an encrypted vault after this many idle minutes; 0 never locks */
+  auto_lock_minutes: number;
+}
+
+export interface TraySettings {
+  /** Closing the window keeps the app running in the tray */
+  close_to_tray: boolean;
+  /** Recently edited notes listed in the tray menu (at most 20) */
+  recent_notes: number;
+}
+
+export interface QuickCaptureSettings {
+  /** Notebook captured notes are filed under */
+  notebook: string;
+  prefill_from_clipboard: boolean;
+}
+
+/** Global accelerators per action (e.g. `CmdOrCtrl+Alt+N`); `null` is off. */
+export interface ShortcutSettings {
+  new_note: string | null;
+  quick_capture: string | null;
+  search: string | null;
+  toggle_window: string | null;
 }
 
 class SettingsService {
@@ -18,6 +61,8 @@ class SettingsService {
   settings = $state<Settings | null>(null);
   isLoading = $state(false);
   error = $state<string | null>(null);
+  /** Per-field messages from the last rejected update, keyed like `api.port`. */
+  fieldErrors = $state<Record<string, string>>({});
 
   // ========================================================================
   // LOAD SETTINGS
@@ -46,16 +91,35 @@ class SettingsService {
    * Called after onboarding or from settings page
    */
   async update(newSettings: Settings) {
+    this.fieldErrors = {};
     try {
       await invoke('update_settings', { newSettings });
       this.settings = newSettings; // Update local cache
     } catch (err) {
-      this.error = err instanceof Error ? err.message : 'Failed to update settings';
+      // Rejected as `{ code: 'validation', details: { fields: [{ field, message }] } }`
+      const fields: { field: string; message: string }[] = (err as any)?.details?.fields ?? [];
+      this.fieldErrors = Object.fromEntries(fields.map((f) => [f.field, f.message]));
+      this.error = (err as any)?.message ?? 'Failed to update settings';
       console.error('Failed to update settings:', err);
       throw err;
     }
   }
 
+  /**
+   * Move the notes folder (notes, attachments, trash) to `newPath`.
+   * Calling it again after a failure resumes the same move.
+   */
+  async moveNotesFolder(newPath: string) {
+    try {
+      await invoke('move_notes_folder', { newPath });
+      await this.load();
+    } catch (err) {
+      this.error = (err as any)?.message ?? 'Failed to move notes folder';
+      console.error('Failed to move notes folder:', err);
+      throw err;
+    }
+  }
+
   /**
    * Get current delete behavior
    * Returns the user's preference from onboarding
@@ -64,6 +128,14 @@ class SettingsService {
     return this.settings?.delete_behavior ?? 'MoveToTrash';
   }
 
+  /**
+   * Where the settings file was kept if it couldn't be read and the
+   * defaults were used instead (`null` normally)
+   */
+  get recoveredFrom(): string | null {
+    return this.settings?.recovered_from ?? null;
+  }
+
   /**
    * Check if onboarding is completed
    */
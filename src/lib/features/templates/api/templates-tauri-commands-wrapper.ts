@@ -0,0 +1,26 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { NoteDTO } from '$lib/features/notes/notes-types';
+
+/** An HTML file in the `templates/` folder of the notes folder */
+export interface TemplateDTO {
+  id: string; // file name without `.html`
+  title: string; // placeholders not filled in yet
+  tags: string[];
+  notebook: string | null;
+  body: string;
+}
+
+/**
+ * Tauri API wrapper for note templates. Placeholders: {{date}}, {{time}},
+ * {{title}} and any custom name passed in `vars`.
+ */
+export class TemplatesAPI {
+  static async list(): Promise<TemplateDTO[]> {
+    return await invoke<TemplateDTO[]>('list_templates');
+  }
+
+  /** The note gets the template's tags and notebook */
+  static async createNote(templateId: string, vars?: Record<string, string>): Promise<NoteDTO> {
+    return await invoke<NoteDTO>('create_note_from_template', { templateId, vars });
+  }
+}
@@ -0,0 +1,44 @@
+import { invoke } from '@tauri-apps/api/core';
+
+export interface VaultDTO {
+  id: string;
+  name: string;
+  data_dir: string;
+}
+
+export interface VaultListDTO {
+  vaults: VaultDTO[];
+  /** Id of the vault currently open */
+  open: string;
+}
+
+/**
+ * Tauri API wrapper for vault operations
+ */
+export class VaultsAPI {
+  static async list(): Promise<VaultListDTO> {
+    return await invoke<VaultListDTO>('list_vaults');
+  }
+
+  /** Adds an empty vault; it is not opened. */
+  static async create(name: string): Promise<VaultDTO> {
+    return await invoke<VaultDTO>('create_vault', { name });
+  }
+
+  /**
+   * Switches the app to another vault. Notes and settings shown belong to
+   * the old one afterwards (a `vault_opened` app event follows); reload them.
+   */
+  static async open(id: string): Promise<VaultDTO> {
+    return await invoke<VaultDTO>('open_vault', { id });
+  }
+
+  static async rename(id: string, name: string): Promise<VaultDTO> {
+    return await invoke<VaultDTO>('rename_vault', { id, name });
+  }
+
+  /** Removes the vault from the list; its files stay on disk. */
+  static async close(id: string): Promise<void> {
+    await invoke('close_vault', { id });
+  }
+}
@@ -10,13 +10,25 @@ export async function registerShortcutListeners(): Promise<void> {
   // Clean up existing listeners
   await unregisterShortcutListeners();
 
-  // Create new note (Ctrl+N)
+  // Create new note (settings.shortcuts.new_note)
   const unlistenCreate = await listen('create-new-note', () => {
     console.log('📝 Shortcut triggered: Create new note');
     noteService.create();
   });
   unlisteners.push(unlistenCreate);
 
+  // Saved from the quick capture window
+  const unlistenCaptured = await listen('quick-capture-saved', () => {
+    noteService.load();
+  });
+  unlisteners.push(unlistenCaptured);
+
+  // Recent note picked from the tray menu
+  const unlistenOpen = await listen<string>('open-note', (event) => {
+    noteService.select(event.payload);
+  });
+  unlisteners.push(unlistenOpen);
+
   // Future: Add more listeners here
   // const unlistenDelete = await listen('delete-note', () => { ... });
   // unlisteners.push(unlistenDelete);